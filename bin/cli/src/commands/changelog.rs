@@ -1,10 +1,14 @@
 use clap::Parser;
 
 use crate::commands::changelog::init::InitCommand;
+use crate::commands::changelog::migrate::MigrateCommand;
 use crate::commands::changelog::release::ReleaseCommand;
+use crate::commands::changelog::templates::TemplatesCommand;
 
 pub mod init;
+pub mod migrate;
 pub mod release;
+pub mod templates;
 
 /// Manage SCM Hooks
 #[derive(Parser, Debug)]
@@ -18,7 +22,9 @@ pub struct ChangelogCommand {
 #[derive(Parser, Debug)]
 pub enum ChangelogSubCommands {
     Init(InitCommand),
-    Release(ReleaseCommand),
+    Migrate(MigrateCommand),
+    Release(Box<ReleaseCommand>),
+    Templates(TemplatesCommand),
 }
 
 #[async_trait::async_trait]
@@ -26,7 +32,9 @@ impl crate::commands::Command for ChangelogCommand {
     async fn execute(&self) -> anyhow::Result<Option<String>> {
         match &self.sub_command {
             ChangelogSubCommands::Init(cmd) => cmd.execute().await,
+            ChangelogSubCommands::Migrate(cmd) => cmd.execute().await,
             ChangelogSubCommands::Release(cmd) => cmd.execute().await,
+            ChangelogSubCommands::Templates(cmd) => cmd.execute().await,
         }
     }
 }
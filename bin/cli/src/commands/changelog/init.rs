@@ -1,17 +1,35 @@
 use std::path::PathBuf;
 
+use changelog::templates::ChangelogTemplate;
 use clap::Parser;
+use doctavious_cli::changelog::init::init;
+use strum::VariantNames;
 
+use crate::clap_enum_variants;
+
+/// Init changelog settings
 #[derive(Parser, Debug)]
 #[command()]
 pub struct InitCommand {
+    #[arg(long, short)]
     pub cwd: Option<PathBuf>,
+
+    /// Built-in template used to render the changelog
+    #[arg(
+        long,
+        short,
+        default_value_t = ChangelogTemplate::default(),
+        value_parser = clap_enum_variants!(ChangelogTemplate)
+    )]
+    pub template: ChangelogTemplate,
 }
 
 #[async_trait::async_trait]
 impl crate::commands::Command for InitCommand {
     async fn execute(&self) -> anyhow::Result<Option<String>> {
-        unimplemented!()
+        let cwd = self.resolve_cwd(self.cwd.as_ref())?;
+        init(&cwd, self.template)?;
+        Ok(None)
     }
 }
 
@@ -0,0 +1,30 @@
+use std::path::PathBuf;
+
+use changelog::cliff::DEFAULT_CLIFF_CONFIG;
+use clap::Parser;
+use doctavious_cli::changelog::migrate::migrate;
+use tracing::warn;
+
+/// Init changelog settings from an existing git-cliff configuration
+#[derive(Parser, Debug)]
+#[command()]
+pub struct MigrateCommand {
+    #[arg(long, short)]
+    pub cwd: Option<PathBuf>,
+
+    /// Path to the git-cliff configuration file
+    #[arg(default_value = DEFAULT_CLIFF_CONFIG)]
+    pub path: PathBuf,
+}
+
+#[async_trait::async_trait]
+impl crate::commands::Command for MigrateCommand {
+    async fn execute(&self) -> anyhow::Result<Option<String>> {
+        let cwd = self.resolve_cwd(self.cwd.as_ref())?;
+        for warning in migrate(&cwd, &self.path)? {
+            warn!("{warning}");
+        }
+
+        Ok(None)
+    }
+}
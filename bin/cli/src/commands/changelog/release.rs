@@ -154,6 +154,17 @@ pub struct ReleaseCommand {
     )]
     pub prepend: Option<PathBuf>,
 
+    /// Updates the output changelog in place.
+    /// Only the unreleased section and releases whose commits changed are regenerated.
+    /// Sections edited by hand are kept and reported as conflicts
+    #[arg(
+        long,
+        requires = "output",
+        conflicts_with_all = ["individual", "prepend"],
+        env = "DOCTAVIOUS_CHANGELOG_UPDATE"
+    )]
+    pub update: bool,
+
     /// Sets the tag for the latest version.
     #[arg(
         short,
@@ -163,6 +174,11 @@ pub struct ReleaseCommand {
     )]
     pub tag: Option<String>,
 
+    /// Commits the changelog and creates an annotated tag for the release, with the release as
+    /// the message, then pushes both. The tag is signed when the SCM is configured to sign tags.
+    #[arg(long, requires = "tag", env = "DOCTAVIOUS_CHANGELOG_TAG_COMMIT")]
+    pub tag_commit: bool,
+
     /// Determines how commits should be sorted within tags
     #[arg(
         long,
@@ -214,7 +230,7 @@ pub struct ReleaseCommand {
 
     // TODO: could use -R and --range instead of index
     /// Sets the commit range to process [possible values: current, latest, unreleased, or
-    /// in the format of <START>..<END>]
+    /// a commit range such as <START>..<END>, tag:<NAME>.., since:<YYYY-MM-DD> or last:<N>]
     #[arg(index = 1)]
     pub range: Option<ChangelogRange>,
 }
@@ -246,6 +262,7 @@ impl crate::commands::Command for ReleaseCommand {
             output: self.output.clone(),
             output_type,
             prepend: self.prepend.clone(),
+            update: self.update,
             range: self.range.clone(),
             include_paths: self.include_paths.clone(),
             exclude_paths: self.exclude_paths.clone(),
@@ -255,6 +272,7 @@ impl crate::commands::Command for ReleaseCommand {
             skip_tag_patterns: self.skip_tag_patterns.clone(),
             ignore_tag_patterns: self.ignore_tag_patterns.clone(),
             tag: self.tag.clone(),
+            tag_commit: self.tag_commit,
             ignore_commits: self.ignore_commits.clone(),
             strip: self.strip,
         })?;
@@ -0,0 +1,48 @@
+use changelog::templates::ChangelogTemplate;
+use clap::Parser;
+use strum::{IntoEnumIterator, VariantNames};
+
+use crate::clap_enum_variants;
+
+/// Built-in changelog templates
+#[derive(Parser, Debug)]
+#[command()]
+pub struct TemplatesCommand {
+    #[command(subcommand)]
+    pub sub_command: TemplatesSubCommands,
+}
+
+#[remain::sorted]
+#[derive(Parser, Debug)]
+pub enum TemplatesSubCommands {
+    List(ListTemplates),
+    Show(ShowTemplate),
+}
+
+/// List built-in changelog templates
+#[derive(Parser, Debug)]
+#[command()]
+pub struct ListTemplates {}
+
+/// Show the content of a built-in changelog template
+#[derive(Parser, Debug)]
+#[command()]
+pub struct ShowTemplate {
+    #[arg(value_parser = clap_enum_variants!(ChangelogTemplate))]
+    pub name: ChangelogTemplate,
+}
+
+#[async_trait::async_trait]
+impl crate::commands::Command for TemplatesCommand {
+    async fn execute(&self) -> anyhow::Result<Option<String>> {
+        match &self.sub_command {
+            TemplatesSubCommands::List(_) => Ok(Some(
+                ChangelogTemplate::iter()
+                    .map(|t| format!("{t}\t{}", t.description()))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            )),
+            TemplatesSubCommands::Show(cmd) => Ok(Some(cmd.name.content().to_string())),
+        }
+    }
+}
@@ -7,6 +7,7 @@ use clap::Args;
 use code_ownify::notify::CodeNotify;
 use continuous_integration::ContinuousIntegrationProvider;
 use scm::commit::ScmCommitRange;
+use scm::platforms::github::app::GitHubAppCredentials;
 use tracing::debug;
 
 #[derive(Args, Debug)]
@@ -37,15 +38,32 @@ pub struct NotifyCommand {
     )]
     pub subscriber_threshold: i8,
 
-    #[arg(long)]
+    #[arg(long, conflicts_with = "range")]
     pub base_ref: Option<String>,
 
-    #[arg(long)]
+    #[arg(long, conflicts_with = "range")]
     pub head_ref: Option<String>,
 
+    /// The commit range to diff, e.g., `merge-base:main..HEAD`, instead of base and head refs
+    #[arg(long)]
+    pub range: Option<ScmCommitRange>,
+
     /// The author of the file diff
     #[arg(long)]
     pub author: Option<String>,
+
+    /// ID of the GitHub App to comment as instead of using an access token.
+    /// Also read from `GITHUB_APP_ID` along with the other `GITHUB_APP_*` variables
+    #[arg(long, requires = "github_app_private_key")]
+    pub github_app_id: Option<i64>,
+
+    /// Path to the GitHub App's PEM private key
+    #[arg(long, requires = "github_app_id")]
+    pub github_app_private_key: Option<PathBuf>,
+
+    /// The GitHub App installation to authenticate as. Looked up from the repository when not set
+    #[arg(long, requires = "github_app_id")]
+    pub github_app_installation_id: Option<u64>,
 }
 
 #[async_trait::async_trait]
@@ -63,34 +81,56 @@ impl crate::commands::Command for NotifyCommand {
                 std::process::exit(1)
             }
 
-            let scm = match ci_provider.associated_bound_scm_client(&ci_context)? {
-                None => anyhow::bail!("unable to determine SCM platform client"),
-                Some(scm) => scm,
+            let commit_range = match &self.range {
+                Some(range) => range.clone(),
+                None => {
+                    let base_ref = self.base_ref.as_deref().unwrap_or(&ci_context.base);
+                    if base_ref.is_empty() {
+                        anyhow::bail!(
+                            "unable to determine base revision from {:?}. Use --base-ref or --range",
+                            ci_context.provider
+                        );
+                    }
+                    let head_ref = self.head_ref.as_deref().unwrap_or(&ci_context.head);
+                    ScmCommitRange::new(base_ref.parse()?, Some(head_ref.parse()?))
+                }
             };
 
-            let base_ref = self.base_ref.clone().unwrap_or(ci_context.base);
-            let head_ref = self.head_ref.clone().unwrap_or(ci_context.head);
-
             let code_notify = CodeNotify {
                 cwd: ci_context.build_directory,
                 format: "markdown".to_string(),
                 file_name,
                 subscriber_threshold,
-                commit_range: ScmCommitRange(base_ref, Some(head_ref)),
+                commit_range,
                 author: ci_context.author,
             };
 
             let code_notify_result = code_notify.notify()?;
 
-            // TODO: could likely just return errors here instead of the expects
-            let pr_number = u64::from_str(
-                &ci_context
-                    .pull_request
-                    .expect("Pull request cannot be none"),
-            )
-            .expect("unable to parse pull request");
+            // pushes, merge queues and manually triggered builds have no pull request to comment on
+            let Some(pull_request) = &ci_context.pull_request else {
+                write!(io::stdout(), "{}", code_notify_result.message)?;
+                return Ok(None);
+            };
+            let pr_number = u64::from_str(pull_request)?;
+
+            let github_app = match (self.github_app_id, &self.github_app_private_key) {
+                (Some(app_id), Some(private_key)) => Some(GitHubAppCredentials::from_file(
+                    app_id,
+                    private_key,
+                    self.github_app_installation_id,
+                )?),
+                _ => None,
+            };
+
+            let scm = match ci_provider
+                .associated_bound_scm_client_with_github_app(&ci_context, github_app)?
+            {
+                None => anyhow::bail!("unable to determine SCM platform client"),
+                Some(scm) => scm,
+            };
 
-            let notes = scm.list_all_merge_requests_notes(pr_number).await;
+            let notes = scm.list_all_merge_requests_notes(pr_number).await?;
             let mut comment_id = None;
             for note in notes {
                 if note.body.starts_with(&code_notify.markdown_comment_title()) {
@@ -101,26 +141,32 @@ impl crate::commands::Command for NotifyCommand {
 
             if let Some(comment_id) = comment_id {
                 scm.update_merge_request_note(pr_number, comment_id, code_notify_result.message)
-                    .await;
+                    .await?;
             } else {
                 if code_notify_result.notify.is_empty() {
                     debug!("not adding a comment because there are no notifications to send");
                 } else {
                     scm.create_merge_request_note(pr_number, code_notify_result.message)
-                        .await;
+                        .await?;
                 }
             }
         } else {
             // running locally
+            let commit_range = match (&self.range, &self.base_ref) {
+                (Some(range), _) => range.clone(),
+                (None, Some(base_ref)) => ScmCommitRange::new(
+                    base_ref.parse()?,
+                    self.head_ref.as_deref().map(str::parse).transpose()?,
+                ),
+                (None, None) => anyhow::bail!("either --range or --base-ref is required"),
+            };
+
             let code_notify = CodeNotify {
                 cwd: self.resolve_cwd(self.cwd.as_ref())?,
                 format: self.format.clone().unwrap_or("text".to_string()),
                 file_name,
                 subscriber_threshold,
-                commit_range: ScmCommitRange(
-                    self.base_ref.clone().unwrap_or_default(),
-                    self.head_ref.clone(),
-                ),
+                commit_range,
                 author: None,
             };
 
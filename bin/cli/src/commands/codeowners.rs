@@ -1,7 +1,10 @@
+mod assign;
 mod audit;
 
 use clap::{Args, Parser, Subcommand};
 
+use crate::commands::codeowners::assign::AssignCommand;
+
 #[derive(Parser, Debug)]
 #[command()]
 pub struct CodeOwnersCommand {
@@ -12,6 +15,9 @@ pub struct CodeOwnersCommand {
 #[remain::sorted]
 #[derive(Debug, Subcommand)]
 enum CodeOwnersSubcommand {
+    /// Request reviews of the pull request being built from the owners of its changed files
+    Assign(AssignCommand),
+
     // TODO: do we want to check all repositories
     // Do we want to check for consistency
     // See references
@@ -28,6 +34,7 @@ enum CodeOwnersSubcommand {
 impl crate::commands::Command for CodeOwnersCommand {
     async fn execute(&self) -> anyhow::Result<Option<String>> {
         match &self.sub_command {
+            CodeOwnersSubcommand::Assign(cmd) => cmd.execute().await,
             CodeOwnersSubcommand::Audit(cmd) => cmd.execute().await,
             CodeOwnersSubcommand::Unowned(cmd) => cmd.execute().await,
         }
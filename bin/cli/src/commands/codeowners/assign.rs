@@ -0,0 +1,116 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use clap::Args;
+use code_ownify::assign::ReviewerSelection;
+use code_ownify::owners::CodeOwners;
+use continuous_integration::ContinuousIntegrationProvider;
+use scm::commit::ScmCommitRange;
+use scm::drivers::{Scm, ScmRepository};
+use tracing::{debug, warn};
+
+#[derive(Args, Debug)]
+#[command()]
+pub struct AssignCommand {
+    #[arg(long, conflicts_with = "range")]
+    pub base_ref: Option<String>,
+
+    #[arg(long, conflicts_with = "range")]
+    pub head_ref: Option<String>,
+
+    /// The commit range to diff, e.g., `merge-base:main..HEAD`, instead of base and head refs
+    #[arg(long)]
+    pub range: Option<ScmCommitRange>,
+
+    /// The most reviewers, users and teams combined, to request
+    #[arg(long, env = "DOCTAVIOUS_CODEOWNERS_MAX_REVIEWERS")]
+    pub max_reviewers: Option<usize>,
+
+    /// Request a review from one member of each owning team, taking turns between pull requests,
+    /// rather than from the whole team
+    #[arg(long, env = "DOCTAVIOUS_CODEOWNERS_ROUND_ROBIN")]
+    pub round_robin: bool,
+}
+
+#[async_trait::async_trait]
+impl crate::commands::Command for AssignCommand {
+    async fn execute(&self) -> anyhow::Result<Option<String>> {
+        let Some(ci_provider) = ContinuousIntegrationProvider::from_env() else {
+            anyhow::bail!("reviewers can only be assigned when running in CI");
+        };
+
+        let ci_context = ci_provider.context_from_env()?;
+        let Some(pull_request) = &ci_context.pull_request else {
+            anyhow::bail!("{:?} build is not for a pull request", ci_context.provider);
+        };
+        let pr_number = u64::from_str(pull_request)?;
+
+        let scm = match ci_provider.associated_bound_scm_client(&ci_context)? {
+            None => anyhow::bail!("unable to determine SCM platform client"),
+            Some(scm) => scm,
+        };
+
+        let commit_range = match &self.range {
+            Some(range) => range.clone(),
+            None => {
+                let base_ref = self.base_ref.as_deref().unwrap_or(&ci_context.base);
+                if base_ref.is_empty() {
+                    anyhow::bail!(
+                        "unable to determine base revision from {:?}. Use --base-ref or --range",
+                        ci_context.provider
+                    );
+                }
+                let head_ref = self.head_ref.as_deref().unwrap_or(&ci_context.head);
+                ScmCommitRange::new(base_ref.parse()?, Some(head_ref.parse()?))
+            }
+        };
+
+        let paths = Scm::get(&ci_context.build_directory)?.diff_paths(Some(&commit_range))?;
+        let Some(code_owners) = CodeOwners::discover(ci_context.build_directory.clone())? else {
+            anyhow::bail!("no CODEOWNERS file found");
+        };
+        let owners = code_owners.owners_of(&paths);
+
+        let mut team_members = HashMap::new();
+        if self.round_robin {
+            for owner in &owners {
+                let team = owner.trim_start_matches('@');
+                if !team.contains('/') || team.contains('@') {
+                    continue;
+                }
+
+                match scm.list_team_members(team).await {
+                    Ok(members) => {
+                        team_members.insert(team.to_string(), members);
+                    }
+                    Err(e) => warn!("Requesting a review from all of team {team}: {e}"),
+                }
+            }
+        }
+
+        let reviewers = ReviewerSelection {
+            author: ci_context.author.as_deref(),
+            max_reviewers: self.max_reviewers,
+            team_members: &team_members,
+            turn: pr_number,
+        }
+        .select(&owners);
+
+        if reviewers.users.is_empty() && reviewers.teams.is_empty() {
+            debug!("not requesting reviews because the changed files have no other owners");
+            return Ok(None);
+        }
+
+        let requested = reviewers
+            .users
+            .iter()
+            .chain(&reviewers.teams)
+            .map(|r| format!("@{r}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        scm.request_merge_request_reviewers(pr_number, reviewers)
+            .await?;
+
+        Ok(Some(format!("Requested reviews from {requested}")))
+    }
+}
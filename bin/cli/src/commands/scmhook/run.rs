@@ -2,6 +2,7 @@ use std::path::PathBuf;
 
 use clap::Parser;
 use doctavious_cli::cmd::scm_hooks::run::{ScmHookRunFiles, run};
+use scm::commit::ScmCommitRange;
 
 /// Execute commands/scripts associated to the specified hook.
 ///
@@ -29,6 +30,10 @@ pub struct RunScmHookCommand {
     #[arg(long, action, group = "files")]
     pub all_files: bool,
 
+    /// Run hooks on files changed within the commit range, e.g., `origin/main..HEAD`
+    #[arg(long, group = "files")]
+    pub range: Option<ScmCommitRange>,
+
     /// Run only specified executions (commands / scripts)
     #[arg(long = "executions", short = 'e')]
     pub run_only_executions: Option<Vec<String>>,
@@ -52,7 +57,7 @@ impl crate::commands::Command for RunScmHookCommand {
         } else if self.all_files {
             Some(ScmHookRunFiles::All)
         } else {
-            None
+            self.range.clone().map(ScmHookRunFiles::Range)
         };
 
         run(
@@ -102,6 +107,7 @@ tags = ["backed", "style"]
             cwd: Some(temp_path.clone()),
             file: None,
             all_files: false,
+            range: None,
             run_only_executions: None,
             skip_auto_synchronize: false,
             force: false,
@@ -132,6 +138,7 @@ run = "echo '{files}' > test_specified_files.txt"
             cwd: Some(temp_path.clone()),
             file: Some(vec![PathBuf::from("/backend/src/lib.rs")]),
             all_files: false,
+            range: None,
             run_only_executions: None,
             skip_auto_synchronize: false,
             force: false,
@@ -163,6 +170,7 @@ run = "echo '{files}' > test_all_files.txt"
             cwd: Some(temp_path.clone()),
             file: None,
             all_files: true,
+            range: None,
             run_only_executions: None,
             skip_auto_synchronize: false,
             force: false,
@@ -193,6 +201,7 @@ runner = "bash"
             cwd: Some(temp_path.clone()),
             file: None,
             all_files: false,
+            range: None,
             run_only_executions: None,
             skip_auto_synchronize: false,
             force: false,
@@ -226,6 +235,7 @@ runner = "bash"
             cwd: Some(temp_path.clone()),
             file: None,
             all_files: false,
+            range: None,
             run_only_executions: Some(vec!["format-backend".to_string()]),
             skip_auto_synchronize: false,
             force: false,
@@ -259,6 +269,7 @@ skip = true
             cwd: Some(temp_path.clone()),
             file: None,
             all_files: false,
+            range: None,
             run_only_executions: None,
             skip_auto_synchronize: false,
             force: false,
@@ -275,6 +286,7 @@ skip = true
             cwd: Some(temp_path.clone()),
             file: None,
             all_files: false,
+            range: None,
             run_only_executions: None,
             skip_auto_synchronize: false,
             force: true,
@@ -309,6 +321,7 @@ runner = "bash"
             cwd: Some(temp_path.clone()),
             file: None,
             all_files: false,
+            range: None,
             run_only_executions: None,
             skip_auto_synchronize: false,
             force: true,
@@ -1,8 +1,8 @@
 use std::cmp::Ordering;
-use std::fs;
 use std::fs::File;
 use std::io::Write;
 use std::path::Path;
+use std::{fs, slice};
 
 use doctavious_std::regex::convert_to_regex;
 use doctavious_templating::{TemplateContext, Templates};
@@ -10,17 +10,22 @@ use git_conventional::Commit as GitConventionalCommit;
 use markup::MarkupFormat;
 use scm::commit::ScmCommit;
 use serde_derive::{Deserialize, Serialize};
-use somever::Somever;
+use somever::{Somever, VersioningScheme};
 use strum::{Display, EnumString, VariantNames};
 use tracing::{debug, warn};
 
 use crate::commits::ScmTaggedCommits;
-use crate::conventional::ConventionalCommit;
+use crate::conventional::{ConventionalCommit, is_merge_commit, reverted_summary};
+use crate::diagnostics::{CommitDiagnostic, CommitDiagnosticReason};
 use crate::entries::{ChangelogCommit, ChangelogEntry};
 use crate::errors::{ChangelogErrors, ChangelogResult};
-use crate::release::{Release, Releases};
+use crate::release::{PreviousRelease, Release, Releases};
 use crate::release_notes::ReleaseNotes;
-use crate::settings::{ChangelogSettings, CommitProcessor, CommitStyleSettings};
+use crate::remote::RemoteRepository;
+use crate::settings::{
+    ChangelogSettings, CommitProcessor, CommitStyleSettings, MergeCommitHandling,
+};
+use crate::update::{ReleaseSection, SectionConflict, UNRELEASED_KEY};
 
 // Not sure about the name but essentially controls if changelog should write details to a single
 // file or if they should be separated.
@@ -45,6 +50,8 @@ pub struct Changelog {
     footer_template: Option<String>,
     trim: bool,
     post_processors: Option<Vec<CommitProcessor>>,
+    diagnostics: Vec<CommitDiagnostic>,
+    remote: Option<RemoteRepository>,
     // additional_context: HashMap<String, serde_json::Value>,
 }
 
@@ -56,9 +63,10 @@ impl Changelog {
         tagged_commits = Self::ignore_tags(tagged_commits, settings.release.ignore_tags.as_ref())?;
 
         let mut releases: Vec<Release> = vec![];
+        let mut diagnostics = vec![];
         for mut tagged_commit in tagged_commits {
-            Self::preprocess(&mut tagged_commit, &settings);
-            let changelog_entries = Self::process(&mut tagged_commit, &settings)?;
+            Self::preprocess(&mut tagged_commit, &settings, &mut diagnostics);
+            let changelog_entries = Self::process(&tagged_commit, &settings, &mut diagnostics)?;
 
             // TODO: Should we have a ReleaseVersion which could be None as unreleased
             //       or a version / tag associated?
@@ -75,21 +83,56 @@ impl Changelog {
                 repository: tagged_commit.repository,
                 commits: changelog_entries,
                 timestamp: tagged_commit.timestamp,
+                tag: tag.map(|t| t.name),
+                previous: None,
+                compare_url: None,
             });
         }
 
         // TODO: add option to sort by version vs time
         //       should it replace the option for sorting tags? Is that still beneficial?
         Self::sort_releases(&mut releases, settings.version_suffixes.as_ref());
+        Self::link_previous_releases(&mut releases, settings.version_scheme)?;
 
-        Ok(Self {
+        let changelog = Self {
             releases,
             header_template: settings.template.header,
             body_template: settings.template.body,
             footer_template: settings.template.footer,
             trim: settings.template.trim,
             post_processors: settings.template.post_processors,
-        })
+            diagnostics,
+            remote: None,
+        };
+
+        let remote = settings
+            .remote
+            .as_ref()
+            .and_then(RemoteRepository::from_settings);
+        Ok(changelog.with_remote(remote))
+    }
+
+    /// Sets the remote repository used to build links, such as compare URLs, for releases
+    pub fn with_remote(mut self, remote: Option<RemoteRepository>) -> Self {
+        for release in &mut self.releases {
+            release.compare_url = remote.as_ref().and_then(|remote| {
+                let from = release.previous.as_ref()?.tag.as_deref()?;
+                let to = release.tag.as_deref().unwrap_or("HEAD");
+                Some(remote.compare_url(from, to))
+            });
+        }
+
+        self.remote = remote;
+        self
+    }
+
+    pub fn remote(&self) -> Option<&RemoteRepository> {
+        self.remote.as_ref()
+    }
+
+    /// Commits that were skipped, or kept in a degraded form, while building the changelog
+    pub fn diagnostics(&self) -> &[CommitDiagnostic] {
+        &self.diagnostics
     }
 
     fn ignore_tags(
@@ -115,7 +158,11 @@ impl Changelog {
             .collect())
     }
 
-    fn preprocess(mut tagged_commits: &mut ScmTaggedCommits, settings: &ChangelogSettings) {
+    fn preprocess(
+        tagged_commits: &mut ScmTaggedCommits,
+        settings: &ChangelogSettings,
+        diagnostics: &mut Vec<CommitDiagnostic>,
+    ) {
         let commit_preprocessors = &settings.commit.commit_preprocessors;
         let commit_style_settings = &settings.commit.commit_style;
         tagged_commits.commits = tagged_commits
@@ -135,6 +182,11 @@ impl Changelog {
                             e,
                             &commit.message
                         );
+                        diagnostics.push(CommitDiagnostic::skipped(
+                            &commit.id,
+                            &commit.message,
+                            CommitDiagnosticReason::PreprocessorFailed(e.to_string()),
+                        ));
                         return None;
                     }
                 }
@@ -165,6 +217,7 @@ impl Changelog {
     fn process(
         tagged_commits: &ScmTaggedCommits,
         settings: &ChangelogSettings,
+        diagnostics: &mut Vec<CommitDiagnostic>,
     ) -> ChangelogResult<Vec<ChangelogEntry>> {
         let mut changelog_entries = Vec::new();
 
@@ -174,8 +227,22 @@ impl Changelog {
 
         for commit in &tagged_commits.commits {
             let mut changelog_entry_commits = vec![];
+            // group to fall back to when commit doesn't match any group parser
+            let mut fallback_group = None;
             match &commit_style_settings {
                 CommitStyleSettings::Conventional(settings) => {
+                    if settings.merge_commits == MergeCommitHandling::Skip
+                        && is_merge_commit(&commit.message)
+                    {
+                        debug!("skipping merge commit {}", &commit.id);
+                        diagnostics.push(CommitDiagnostic::skipped(
+                            &commit.id,
+                            &commit.message,
+                            CommitDiagnosticReason::MergeCommit,
+                        ));
+                        continue;
+                    }
+
                     let conventional_commit = GitConventionalCommit::parse(&commit.message);
                     let c = match conventional_commit {
                         Ok(conv) => ChangelogCommit::from_conventional(ConventionalCommit {
@@ -183,11 +250,22 @@ impl Changelog {
                             conv,
                         }),
                         Err(e) => {
-                            if settings.include_unconventional {
-                                ChangelogCommit::from_scm_commit(&commit);
+                            if let Some(reverted) = reverted_summary(&commit.message) {
+                                ChangelogCommit::from_revert(commit, reverted)
+                            } else if settings.include_unconventional {
+                                diagnostics.push(CommitDiagnostic::kept(
+                                    &commit.id,
+                                    &commit.message,
+                                    CommitDiagnosticReason::Unconventional(e.to_string()),
+                                ));
+                                fallback_group = Some(settings.unconventional_group.clone());
+                                ChangelogCommit::from_scm_commit(commit)
+                            } else {
+                                return Err(ChangelogErrors::ChangelogError(format!(
+                                    "commit {} is not a conventional commit: {e}",
+                                    commit.id
+                                )));
                             }
-
-                            return Err(ChangelogErrors::ChangelogError(e.to_string()));
                         }
                     };
 
@@ -204,7 +282,7 @@ impl Changelog {
                     }
                 }
                 CommitStyleSettings::Standard(_) => {
-                    changelog_entry_commits.push(ChangelogCommit::from_scm_commit(&commit));
+                    changelog_entry_commits.push(ChangelogCommit::from_scm_commit(commit));
                 }
             };
 
@@ -219,25 +297,39 @@ impl Changelog {
                                 );
                             } else {
                                 debug!("ignoring commit {}", &commit.id);
+                                diagnostics.push(CommitDiagnostic::skipped(
+                                    &commit.id,
+                                    &commit.message,
+                                    CommitDiagnosticReason::Ignored,
+                                ));
                                 continue 'commits;
                             }
                         }
                     }
                 }
 
-                let entry = ChangelogEntry::new(
+                let mut entry = ChangelogEntry::new(
                     commit,
                     settings.commit.group_parsers.as_ref(),
                     settings.commit.link_parsers.as_ref(),
                 )?;
 
-                if entry.matched_group_parser || !filter_commits {
+                if !entry.matched_group_parser && fallback_group.is_some() {
+                    entry.group = fallback_group.clone();
+                }
+
+                if entry.matched_group_parser || entry.group.is_some() || !filter_commits {
                     changelog_entries.push(entry);
                 } else if filter_commits {
                     debug!(
                         "Skipping commit {} as it does not belong to any group",
                         &entry.id()
-                    )
+                    );
+                    diagnostics.push(CommitDiagnostic::skipped(
+                        entry.id(),
+                        entry.message(),
+                        CommitDiagnosticReason::Ungrouped,
+                    ));
                 }
             }
         }
@@ -245,6 +337,35 @@ impl Changelog {
         Ok(changelog_entries)
     }
 
+    /// Links each release to the next older tagged release of the same repository.
+    /// Releases are expected to be sorted newest first.
+    fn link_previous_releases(
+        releases: &mut [Release],
+        version_scheme: VersioningScheme,
+    ) -> ChangelogResult<()> {
+        for i in 0..releases.len() {
+            let previous = releases[i + 1..]
+                .iter()
+                .find(|r| r.repository == releases[i].repository && r.tag.is_some());
+
+            releases[i].previous = match previous {
+                Some(previous) => Some(PreviousRelease {
+                    version: previous
+                        .tag
+                        .as_deref()
+                        .map(|tag| Somever::new(version_scheme, tag))
+                        .transpose()?,
+                    tag: previous.tag.clone(),
+                    tag_id: previous.tag_id.clone(),
+                    timestamp: previous.timestamp,
+                }),
+                None => None,
+            };
+        }
+
+        Ok(())
+    }
+
     pub(crate) fn sort_releases(
         releases: &mut Vec<Release>,
         version_suffixes: Option<&Vec<String>>,
@@ -303,7 +424,8 @@ impl Changelog {
         fs::create_dir_all(&path)?;
 
         for release in &self.releases {
-            let context = TemplateContext::from_serialize(release)?;
+            let mut context = TemplateContext::from_serialize(release)?;
+            context.insert("remote", &self.remote);
 
             let file_name = if let Some(version) = &release.version {
                 format!("{}", version)
@@ -344,6 +466,7 @@ impl Changelog {
             // &HashMap::from([("releases", &self.releases)])
             &Releases {
                 releases: &self.releases,
+                remote: self.remote.as_ref(),
             },
         )?;
 
@@ -394,6 +517,78 @@ impl Changelog {
         Ok(())
     }
 
+    /// Generates the changelog and merges it into the existing changelog content.
+    ///
+    /// Only the unreleased section and releases whose commits changed are replaced. Sections
+    /// edited by hand are kept and returned as conflicts. See [`crate::update::merge`].
+    pub fn update<W: Write>(
+        &self,
+        existing: Option<&str>,
+        out: &mut W,
+    ) -> ChangelogResult<Vec<SectionConflict>> {
+        let context = TemplateContext::from_serialize(&Releases {
+            releases: &self.releases,
+            remote: self.remote.as_ref(),
+        })?;
+
+        let header = self
+            .header_template
+            .as_ref()
+            .map(|t| self.render(t, &context))
+            .transpose()?;
+
+        let footer = self
+            .footer_template
+            .as_ref()
+            .map(|t| self.render(t, &context))
+            .transpose()?;
+
+        let (changelog, conflicts) = crate::update::merge(
+            existing,
+            header.as_deref(),
+            &self.release_sections()?,
+            footer.as_deref(),
+        );
+
+        write!(out, "{changelog}")?;
+        Ok(conflicts)
+    }
+
+    /// Renders the body template for the release with the tag, e.g., to use as release notes.
+    /// Returns `None` when there is no release for the tag.
+    pub fn render_release(&self, tag: &str) -> ChangelogResult<Option<String>> {
+        let Some(release) = self.releases.iter().find(|r| r.tag.as_deref() == Some(tag)) else {
+            return Ok(None);
+        };
+
+        let context = TemplateContext::from_serialize(&Releases {
+            releases: slice::from_ref(release),
+            remote: self.remote.as_ref(),
+        })?;
+
+        Ok(Some(self.render(&self.body_template, &context)?))
+    }
+
+    /// Renders the body template for each release individually
+    fn release_sections(&self) -> ChangelogResult<Vec<ReleaseSection>> {
+        let mut sections = Vec::with_capacity(self.releases.len());
+        for release in &self.releases {
+            let context = TemplateContext::from_serialize(&Releases {
+                releases: slice::from_ref(release),
+                remote: self.remote.as_ref(),
+            })?;
+
+            let content = self.render(&self.body_template, &context)?;
+            sections.push(ReleaseSection::new(
+                release.tag.as_deref().unwrap_or(UNRELEASED_KEY),
+                release.commits.iter().map(|c| &c.commit.id),
+                &content,
+            ));
+        }
+
+        Ok(sections)
+    }
+
     // Increments the version for the unreleased changes
     pub fn bump_version(&self) {
         if let Some(release) = self.releases.first() {
@@ -413,7 +608,191 @@ mod test {
     use super::Release;
     use crate::changelog::Changelog;
     use crate::commits::ScmTaggedCommits;
-    use crate::settings::{ChangelogSettings, TemplateSettings};
+    use crate::diagnostics::CommitDiagnosticReason;
+    use crate::settings::{
+        ChangelogCommitSettings, ChangelogSettings, CommitStyleSettings,
+        ConventionalCommitSettings, TemplateSettings,
+    };
+
+    fn commit(id: &str, message: &str) -> ScmCommit {
+        let signature = ScmSignature {
+            name: None,
+            email: None,
+            timestamp: 0,
+        };
+        ScmCommit {
+            id: id.to_string(),
+            message: message.to_string(),
+            description: message.lines().next().unwrap_or_default().to_string(),
+            body: "".to_string(),
+            author: signature.clone(),
+            committer: signature,
+            timestamp: 0,
+        }
+    }
+
+    fn conventional_settings(include_unconventional: bool) -> ChangelogSettings {
+        ChangelogSettings {
+            template: TemplateSettings {
+                body: "{% for release in releases %}{% for commit in release.commits %}\
+                    {{ commit.group }}: {{ commit.description }}\n\
+                    {% endfor %}{% endfor %}"
+                    .to_string(),
+                ..Default::default()
+            },
+            commit: ChangelogCommitSettings {
+                commit_style: CommitStyleSettings::Conventional(ConventionalCommitSettings {
+                    include_unconventional,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    fn unreleased(commits: Vec<ScmCommit>) -> Vec<ScmTaggedCommits> {
+        vec![ScmTaggedCommits {
+            repository: "lib".to_string(),
+            tag: None,
+            commits,
+            timestamp: None,
+        }]
+    }
+
+    #[test]
+    fn update_should_preserve_edited_releases() {
+        let tagged_commits = |unreleased_commits: Vec<ScmCommit>| {
+            let mut tagged_commits = unreleased(unreleased_commits);
+            tagged_commits.push(ScmTaggedCommits {
+                repository: "lib".to_string(),
+                tag: Some(ScmTag {
+                    id: None,
+                    name: "v1.0.0".to_string(),
+                    message: None,
+                    timestamp: 0,
+                }),
+                commits: vec![commit("c1d2e3f4", "fix: initial bug")],
+                timestamp: Some(0),
+            });
+            tagged_commits
+        };
+
+        let changelog = Changelog::new(
+            tagged_commits(vec![commit("a1b2c3d4", "feat: add foo")]),
+            conventional_settings(false),
+        )
+        .unwrap();
+        let mut output = Vec::new();
+        assert!(changelog.update(None, &mut output).unwrap().is_empty());
+        let edited = String::from_utf8(output)
+            .unwrap()
+            .replace("initial bug", "initial bug (see migration guide)");
+
+        let changelog = Changelog::new(
+            tagged_commits(vec![
+                commit("a1b2c3d4", "feat: add foo"),
+                commit("b1b2c3d4", "feat: add bar"),
+            ]),
+            conventional_settings(false),
+        )
+        .unwrap();
+        let mut output = Vec::new();
+        let conflicts = changelog.update(Some(&edited), &mut output).unwrap();
+        let updated = String::from_utf8(output).unwrap();
+
+        assert!(conflicts.is_empty());
+        assert!(updated.contains("add bar"));
+        assert!(updated.contains("initial bug (see migration guide)"));
+    }
+
+    #[test]
+    fn render_release_should_only_render_tagged_release() {
+        let mut tagged_commits = unreleased(vec![commit("a1b2c3d4", "feat: add foo")]);
+        tagged_commits.push(ScmTaggedCommits {
+            repository: "lib".to_string(),
+            tag: Some(ScmTag {
+                id: None,
+                name: "v1.0.0".to_string(),
+                message: None,
+                timestamp: 0,
+            }),
+            commits: vec![commit("c1d2e3f4", "fix: initial bug")],
+            timestamp: Some(0),
+        });
+
+        let changelog = Changelog::new(tagged_commits, conventional_settings(false)).unwrap();
+        let notes = changelog.render_release("v1.0.0").unwrap().unwrap();
+        assert!(notes.contains("initial bug"));
+        assert!(!notes.contains("add foo"));
+        assert!(changelog.render_release("v2.0.0").unwrap().is_none());
+    }
+
+    #[test]
+    fn strict_conventional_should_fail_on_unconventional_commit() {
+        let result = Changelog::new(
+            unreleased(vec![
+                commit("a1b2c3d4", "feat: add foo"),
+                commit("e5f6a7b8", "fixed the thing"),
+            ]),
+            conventional_settings(false),
+        );
+
+        let err = result.unwrap_err();
+        assert!(err.to_string().contains("e5f6a7b8"));
+    }
+
+    #[test]
+    fn lenient_conventional_should_keep_unconventional_commits() {
+        let changelog = Changelog::new(
+            unreleased(vec![
+                commit("a1b2c3d4", "feat: add foo"),
+                commit("e5f6a7b8", "fixed the thing"),
+            ]),
+            conventional_settings(true),
+        )
+        .unwrap();
+
+        let entries = &changelog.releases[0].commits;
+        assert_eq!(2, entries.len());
+        assert_eq!(None, entries[0].group);
+        assert_eq!(Some("Other".to_string()), entries[1].group);
+
+        let diagnostics = changelog.diagnostics();
+        assert_eq!(1, diagnostics.len());
+        assert_eq!("e5f6a7b", diagnostics[0].short_id());
+        assert!(!diagnostics[0].skipped);
+        assert!(matches!(
+            diagnostics[0].reason,
+            CommitDiagnosticReason::Unconventional(_)
+        ));
+    }
+
+    #[test]
+    fn conventional_should_skip_merge_commits_and_keep_reverts() {
+        let changelog = Changelog::new(
+            unreleased(vec![
+                commit("a1b2c3d4", "feat(cli): add foo"),
+                commit("b1b2c3d4", "Merge pull request #1 from doctavious/foo"),
+                commit(
+                    "c1b2c3d4",
+                    "Revert \"feat(cli): add foo\"\n\nThis reverts commit a1b2c3d4.",
+                ),
+            ]),
+            conventional_settings(false),
+        )
+        .unwrap();
+
+        let entries = &changelog.releases[0].commits;
+        assert_eq!(2, entries.len());
+        assert_eq!(Some(&"revert".to_string()), entries[1].commit_type());
+        assert_eq!(Some(&"cli".to_string()), entries[1].scope());
+
+        let diagnostics = changelog.diagnostics();
+        assert_eq!(1, diagnostics.len());
+        assert!(diagnostics[0].skipped);
+        assert_eq!(CommitDiagnosticReason::MergeCommit, diagnostics[0].reason);
+    }
 
     #[test]
     fn test_generator_groupby_repo() {
@@ -618,6 +997,9 @@ mod test {
                 repository: "".to_string(),
                 commits: vec![],
                 timestamp: None,
+                tag: None,
+                previous: None,
+                compare_url: None,
             },
             Release {
                 version: Some(Somever::new(VersioningScheme::Semver, "1.0.0-alpha").unwrap()),
@@ -625,6 +1007,9 @@ mod test {
                 repository: "".to_string(),
                 commits: vec![],
                 timestamp: None,
+                tag: None,
+                previous: None,
+                compare_url: None,
             },
             Release {
                 version: Some(Somever::new(VersioningScheme::Semver, "1.0.0-final").unwrap()),
@@ -632,6 +1017,9 @@ mod test {
                 repository: "".to_string(),
                 commits: vec![],
                 timestamp: None,
+                tag: None,
+                previous: None,
+                compare_url: None,
             },
             Release {
                 version: Some(Somever::new(VersioningScheme::Semver, "1.0.0-rc").unwrap()),
@@ -639,6 +1027,9 @@ mod test {
                 repository: "".to_string(),
                 commits: vec![],
                 timestamp: None,
+                tag: None,
+                previous: None,
+                compare_url: None,
             },
             Release {
                 version: None,
@@ -646,6 +1037,9 @@ mod test {
                 repository: "".to_string(),
                 commits: vec![],
                 timestamp: None,
+                tag: None,
+                previous: None,
+                compare_url: None,
             },
         ];
 
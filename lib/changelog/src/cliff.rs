@@ -0,0 +1,326 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use regex::Regex;
+use scm::drivers::git::TagSort;
+use scm::platforms::ScmPlatform;
+use serde_derive::Deserialize;
+
+use crate::errors::ChangelogResult;
+use crate::settings::{
+    ChangelogBumpSettings, ChangelogCommitSettings, ChangelogCommitSort,
+    ChangelogReleaseConfiguration, ChangelogRemote, ChangelogRemoteSettings, ChangelogSettings,
+    CommitParser, CommitProcessor, CommitStyleSettings, ConventionalCommitSettings, GroupParser,
+    LinkParser, StandardCommitSettings, TemplateSettings,
+};
+
+pub const DEFAULT_CLIFF_CONFIG: &str = "cliff.toml";
+
+#[derive(Debug, Default, Deserialize)]
+struct CliffConfig {
+    #[serde(default)]
+    changelog: CliffChangelogConfig,
+    #[serde(default)]
+    git: CliffGitConfig,
+    remote: Option<HashMap<ScmPlatform, ChangelogRemote>>,
+    bump: Option<CliffBumpConfig>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct CliffChangelogConfig {
+    header: Option<String>,
+    body: Option<String>,
+    footer: Option<String>,
+    trim: Option<bool>,
+    postprocessors: Option<Vec<CommitProcessor>>,
+    output: Option<PathBuf>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct CliffGitConfig {
+    conventional_commits: Option<bool>,
+    filter_unconventional: Option<bool>,
+    require_conventional: Option<bool>,
+    split_commits: Option<bool>,
+    commit_preprocessors: Option<Vec<CommitProcessor>>,
+    commit_parsers: Option<Vec<CliffCommitParser>>,
+    protect_breaking_commits: Option<bool>,
+    link_parsers: Option<Vec<LinkParser>>,
+    filter_commits: Option<bool>,
+    tag_pattern: Option<String>,
+    skip_tags: Option<String>,
+    ignore_tags: Option<String>,
+    topo_order: Option<bool>,
+    sort_commits: Option<String>,
+    limit_commits: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CliffCommitParser {
+    message: Option<String>,
+    body: Option<String>,
+    footer: Option<String>,
+    sha: Option<String>,
+    field: Option<String>,
+    pattern: Option<String>,
+    group: Option<String>,
+    default_scope: Option<String>,
+    scope: Option<String>,
+    skip: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CliffBumpConfig {
+    features_always_bump_minor: Option<bool>,
+    breaking_always_bump_major: Option<bool>,
+}
+
+/// Result of migrating a git-cliff configuration
+#[derive(Debug)]
+pub struct CliffMigration {
+    pub settings: ChangelogSettings,
+
+    /// Settings that could not be migrated as-is and should be reviewed
+    pub warnings: Vec<String>,
+}
+
+/// Converts the content of a [git-cliff](https://git-cliff.org) configuration file into
+/// changelog settings.
+pub fn migrate(content: &str) -> ChangelogResult<CliffMigration> {
+    let config: CliffConfig = toml::from_str(content)?;
+    let mut warnings = vec![];
+
+    let CliffChangelogConfig {
+        header,
+        body,
+        footer,
+        trim,
+        postprocessors,
+        output,
+    } = config.changelog;
+
+    if header.is_some() || body.is_some() || footer.is_some() {
+        warnings.push(
+            "templates were copied verbatim. git-cliff templates are written for Tera and render \
+             a single release. Review them against the `releases` context"
+                .to_string(),
+        );
+    }
+
+    if let Some(output) = output {
+        warnings.push(format!(
+            "output `{}` was not migrated. Configure the changelog path instead",
+            output.to_string_lossy()
+        ));
+    }
+
+    let git = config.git;
+    let commit_style = if git.conventional_commits.unwrap_or(true) {
+        let filter_unconventional =
+            git.filter_unconventional.unwrap_or(true) || git.require_conventional.unwrap_or(false);
+        if filter_unconventional {
+            warnings.push(
+                "unconventional commits are reported as errors rather than being filtered out. \
+                 Set `include_unconventional` to keep them"
+                    .to_string(),
+            );
+        }
+
+        CommitStyleSettings::Conventional(ConventionalCommitSettings {
+            include_unconventional: !filter_unconventional,
+            split_commits: git.split_commits.unwrap_or(false),
+            ..Default::default()
+        })
+    } else {
+        CommitStyleSettings::Standard(StandardCommitSettings {
+            split_commits: git.split_commits.unwrap_or(false),
+        })
+    };
+
+    let mut ignore = vec![];
+    let mut group_parsers = vec![];
+    for parser in git.commit_parsers.unwrap_or_default() {
+        let Some(commit_parser) = commit_parser(&parser, &mut warnings)? else {
+            continue;
+        };
+
+        if parser.skip.unwrap_or(false) {
+            ignore.push(commit_parser);
+        } else {
+            group_parsers.push(GroupParser {
+                commit_parser,
+                group: parser.group,
+                default_scope: parser.default_scope,
+                scope: parser.scope,
+                skip: None,
+            });
+        }
+    }
+
+    let sort_commits = match git.sort_commits.as_deref() {
+        None => None,
+        Some("oldest") => Some(ChangelogCommitSort::OldestFirst),
+        Some("newest") => Some(ChangelogCommitSort::NewestFirst),
+        Some(other) => {
+            warnings.push(format!("unknown sort_commits value `{other}` was ignored"));
+            None
+        }
+    };
+
+    if git.topo_order.unwrap_or(false) {
+        warnings.push(
+            "topo_order is not supported. Tags are sorted chronologically instead".to_string(),
+        );
+    }
+
+    let settings = ChangelogSettings {
+        template: TemplateSettings {
+            header,
+            body: body.unwrap_or_default(),
+            footer,
+            trim: trim.unwrap_or(true),
+            post_processors: postprocessors,
+        },
+        commit: ChangelogCommitSettings {
+            commit_style,
+            commit_preprocessors: git.commit_preprocessors,
+            ignore: (!ignore.is_empty()).then_some(ignore),
+            group_parsers: (!group_parsers.is_empty()).then_some(group_parsers),
+            link_parsers: git.link_parsers,
+            sort_commits,
+            limit_commits: git.limit_commits,
+        },
+        release: ChangelogReleaseConfiguration {
+            tag_patterns: git.tag_pattern.map(|p| vec![p]),
+            skip_tags: git.skip_tags.map(|p| vec![p]),
+            ignore_tags: git.ignore_tags.map(|p| vec![p]),
+            tag_sort: git
+                .topo_order
+                .unwrap_or(false)
+                .then_some(TagSort::Chronological),
+        },
+        remote: config
+            .remote
+            .map(|providers| ChangelogRemoteSettings { providers }),
+        bump: config.bump.map(|bump| ChangelogBumpSettings {
+            features_always_bump_minor: bump.features_always_bump_minor.unwrap_or(true),
+            breaking_always_bump_major: bump.breaking_always_bump_major.unwrap_or(true),
+        }),
+        protect_breaking_commits: git.protect_breaking_commits.unwrap_or(false),
+        exclude_ungrouped: git.filter_commits.unwrap_or(false),
+        ..Default::default()
+    };
+
+    Ok(CliffMigration { settings, warnings })
+}
+
+fn commit_parser(
+    parser: &CliffCommitParser,
+    warnings: &mut Vec<String>,
+) -> ChangelogResult<Option<CommitParser>> {
+    let (field, pattern) = if let Some(message) = &parser.message {
+        ("message", message)
+    } else if let Some(body) = &parser.body {
+        ("body", body)
+    } else if let Some(sha) = &parser.sha {
+        ("id", sha)
+    } else if let (Some(field), Some(pattern)) = (&parser.field, &parser.pattern) {
+        (field.as_str(), pattern)
+    } else {
+        let reason = if parser.footer.is_some() {
+            "footer parsers are not supported. Use a `footers.<token>` field instead"
+        } else {
+            "commit parser has no field to match"
+        };
+        warnings.push(format!(
+            "{reason}: parser for group `{}` was skipped",
+            parser.group.as_deref().unwrap_or_default()
+        ));
+        return Ok(None);
+    };
+
+    Ok(Some(CommitParser {
+        field: field.to_string(),
+        pattern: Regex::new(pattern)?,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cliff::migrate;
+    use crate::settings::{ChangelogCommitSort, CommitStyleSettings};
+
+    #[test]
+    fn should_migrate_cliff_config() {
+        let content = r##"
+[changelog]
+header = "# Changelog\n"
+body = "{{ version }}"
+trim = true
+
+[git]
+conventional_commits = true
+filter_unconventional = false
+commit_parsers = [
+    { message = "^feat", group = "Features" },
+    { body = ".*security", group = "Security" },
+    { message = "^chore\\(release\\)", skip = true },
+    { footer = "^changelog: ?ignore", skip = true },
+]
+filter_commits = true
+tag_pattern = "v[0-9]*"
+sort_commits = "newest"
+
+[remote.github]
+owner = "doctavious"
+repo = "doctavious"
+"##;
+
+        let migration = migrate(content).unwrap();
+        let settings = migration.settings;
+
+        assert_eq!(Some("# Changelog\n".to_string()), settings.template.header);
+        assert!(matches!(
+            settings.commit.commit_style,
+            CommitStyleSettings::Conventional(ref s) if s.include_unconventional
+        ));
+
+        let group_parsers = settings.commit.group_parsers.unwrap();
+        assert_eq!(2, group_parsers.len());
+        assert_eq!("body", group_parsers[1].commit_parser.field);
+        assert_eq!(Some("Security".to_string()), group_parsers[1].group);
+        assert_eq!(1, settings.commit.ignore.unwrap().len());
+
+        assert!(settings.exclude_ungrouped);
+        assert_eq!(
+            Some(vec!["v[0-9]*".to_string()]),
+            settings.release.tag_patterns
+        );
+        assert_eq!(
+            Some(ChangelogCommitSort::NewestFirst),
+            settings.commit.sort_commits
+        );
+        assert_eq!(1, settings.remote.unwrap().providers.len());
+
+        assert!(
+            migration
+                .warnings
+                .iter()
+                .any(|w| w.starts_with("footer parsers are not supported"))
+        );
+    }
+
+    #[test]
+    fn should_use_strict_mode_when_filtering_unconventional_commits() {
+        let migration = migrate("[git]\nconventional_commits = true").unwrap();
+        assert!(matches!(
+            migration.settings.commit.commit_style,
+            CommitStyleSettings::Conventional(ref s) if !s.include_unconventional
+        ));
+    }
+
+    #[test]
+    fn should_error_on_invalid_regex() {
+        assert!(migrate("[git]\ncommit_parsers = [{ message = \"(\", group = \"a\" }]").is_err());
+    }
+}
@@ -1,7 +1,20 @@
 use git_conventional::Commit as GitConventionalCommit;
+use lazy_static::lazy_static;
+use regex::Regex;
 use scm::commit::ScmCommit;
 use serde_derive::Serialize;
 
+lazy_static! {
+    // default merge messages produced by git, GitHub, GitLab and Bitbucket
+    static ref MERGE_RE: Regex = Regex::new(
+        r"^(Merge (pull request|branch|remote-tracking branch|tag|commit|request) |Merged in )"
+    )
+    .unwrap();
+
+    // default revert message produced by `git revert`
+    static ref REVERT_RE: Regex = Regex::new(r#"^Revert "(?<reverted>.+)""#).unwrap();
+}
+
 /// Conventional Commit
 /// Wrap's git_conventional's commit in order to include the raw ScmCommit
 #[derive(Debug, Serialize)]
@@ -9,3 +22,43 @@ pub struct ConventionalCommit<'a> {
     pub commit: ScmCommit,
     pub conv: GitConventionalCommit<'a>,
 }
+
+/// Whether the commit message looks like a merge commit
+pub fn is_merge_commit(message: &str) -> bool {
+    MERGE_RE.is_match(message.trim_start())
+}
+
+/// Returns the summary of the reverted commit when the message uses `git revert`'s default format
+/// e.g. `Revert "feat: add foo"`
+pub fn reverted_summary(message: &str) -> Option<&str> {
+    REVERT_RE
+        .captures(message.trim_start())
+        .and_then(|c| c.name("reverted"))
+        .map(|m| m.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::{is_merge_commit, reverted_summary};
+
+    #[test_case("Merge pull request #42 from doctavious/feature" => true; "github")]
+    #[test_case("Merge branch 'feature' into 'main'" => true; "gitlab")]
+    #[test_case("Merge remote-tracking branch 'origin/main'" => true; "remote tracking")]
+    #[test_case("Merged in feature (pull request #7)" => true; "bitbucket")]
+    #[test_case("feat: merge configuration files" => false; "conventional")]
+    #[test_case("Merging is hard" => false; "unconventional")]
+    fn merge_commits(message: &str) -> bool {
+        is_merge_commit(message)
+    }
+
+    #[test]
+    fn reverted_commit_summary() {
+        assert_eq!(
+            Some("feat(cli): add foo"),
+            reverted_summary("Revert \"feat(cli): add foo\"\n\nThis reverts commit abc.")
+        );
+        assert_eq!(None, reverted_summary("revert: feat(cli): add foo"));
+    }
+}
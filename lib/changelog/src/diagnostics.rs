@@ -0,0 +1,100 @@
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use serde_derive::Serialize;
+
+/// Per-commit report of anything noteworthy that happened while processing commits.
+/// This allows callers to surface which commits were skipped, or kept in a degraded form, and why
+/// rather than having a single malformed commit fail the entire changelog.
+#[derive(Clone, Debug, Serialize)]
+pub struct CommitDiagnostic {
+    pub commit_id: String,
+
+    /// First line of the commit message
+    pub summary: String,
+
+    pub reason: CommitDiagnosticReason,
+
+    /// Whether the commit was dropped from the changelog
+    pub skipped: bool,
+}
+
+impl CommitDiagnostic {
+    pub fn skipped(commit_id: &str, message: &str, reason: CommitDiagnosticReason) -> Self {
+        Self::new(commit_id, message, reason, true)
+    }
+
+    pub fn kept(commit_id: &str, message: &str, reason: CommitDiagnosticReason) -> Self {
+        Self::new(commit_id, message, reason, false)
+    }
+
+    fn new(commit_id: &str, message: &str, reason: CommitDiagnosticReason, skipped: bool) -> Self {
+        Self {
+            commit_id: commit_id.to_string(),
+            summary: message
+                .lines()
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_string(),
+            reason,
+            skipped,
+        }
+    }
+
+    /// Abbreviated commit ID suitable for display
+    pub fn short_id(&self) -> &str {
+        self.commit_id.get(..7).unwrap_or(&self.commit_id)
+    }
+}
+
+impl Display for CommitDiagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let action = if self.skipped { "skipped" } else { "kept" };
+        write!(
+            f,
+            "{} {} ({}): {}",
+            self.short_id(),
+            action,
+            self.reason,
+            self.summary
+        )
+    }
+}
+
+#[remain::sorted]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(tag = "type", content = "detail", rename_all = "snake_case")]
+pub enum CommitDiagnosticReason {
+    /// Commit matched one of the configured commit ignore parsers
+    Ignored,
+
+    /// Merge commit skipped based on `merge_commits` setting
+    MergeCommit,
+
+    /// A commit preprocessor failed to run against the commit
+    PreprocessorFailed(String),
+
+    /// Commit could not be parsed as a conventional commit.
+    /// Contains the parse error
+    Unconventional(String),
+
+    /// Commit did not match any group parser and `exclude_ungrouped` is enabled
+    Ungrouped,
+}
+
+impl Display for CommitDiagnosticReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            CommitDiagnosticReason::Ignored => write!(f, "matched ignore parser"),
+            CommitDiagnosticReason::MergeCommit => write!(f, "merge commit"),
+            CommitDiagnosticReason::PreprocessorFailed(e) => {
+                write!(f, "preprocessor failed: {e}")
+            }
+            CommitDiagnosticReason::Unconventional(e) => {
+                write!(f, "not a conventional commit: {e}")
+            }
+            CommitDiagnosticReason::Ungrouped => write!(f, "does not belong to any group"),
+        }
+    }
+}
@@ -1,8 +1,10 @@
 use std::fmt;
 use std::fmt::{Display, Formatter};
 
-use git_conventional::Footer as ConventionalFooter;
+use git_conventional::{Commit as GitConventionalCommit, Footer as ConventionalFooter};
 use git2::Signature as CommitSignature;
+use lazy_static::lazy_static;
+use regex::Regex;
 use scm::commit::{ScmCommit, ScmSignature};
 use serde::Serialize;
 
@@ -11,6 +13,21 @@ use crate::errors::ChangelogResult;
 use crate::release_notes::ReleaseNote;
 use crate::settings::{GroupParser, LinkParser};
 
+const BREAKING_PHRASE: &str = "BREAKING CHANGE";
+const BREAKING_ARROW: &str = "BREAKING-CHANGE";
+const CO_AUTHORED_BY: &str = "co-authored-by";
+
+lazy_static! {
+    // git trailer / conventional commit footer. Tokens use `-` in place of whitespace with the
+    // exception of `BREAKING CHANGE`
+    static ref FOOTER_RE: Regex =
+        Regex::new(r"^(?<token>BREAKING CHANGE|[A-Za-z0-9][A-Za-z0-9-]*)(?<separator>: ?| #)(?<value>.*)$")
+            .unwrap();
+
+    // `Name <email>` as used by Co-authored-by and similar trailers
+    static ref IDENTITY_RE: Regex = Regex::new(r"^(?<name>[^<]*?)\s*<(?<email>[^>]+)>$").unwrap();
+}
+
 // Initially had the following structure
 // #[derive(Debug, Serialize)]
 // pub enum ChangelogCommit<'a> {
@@ -34,60 +51,75 @@ pub struct ChangelogCommit {
     pub scope: Option<String>,
     pub author: ScmSignature,
     pub committer: ScmSignature,
+    /// Additional authors credited via `Co-authored-by` footers
+    pub co_authors: Vec<Signature>,
     pub breaking: bool,
 }
 
 impl ChangelogCommit {
     pub fn from_scm_commit(commit: &ScmCommit) -> Self {
+        let footers = Footer::parse_all(&commit.message);
         Self {
             id: commit.id.to_string(),
             message: commit.message.trim().to_string(),
             description: commit.description.to_string(),
             body: commit.body.to_string(),
-            footers: None, // TODO: parse footers for ScmCommit
             timestamp: commit.timestamp,
             commit_style: "".to_string(),
             commit_type: None,
             scope: None,
             author: commit.author.clone(),
             committer: commit.committer.clone(),
-            // TODO: support breaking change?
-            breaking: false,
+            co_authors: Footer::co_authors(&footers, commit.timestamp),
+            breaking: footers.iter().any(|f| f.breaking),
+            footers: Some(footers),
         }
     }
 
     pub fn from_release_note(release_note: &ReleaseNote) -> Self {
+        let footers = Footer::parse_all(&release_note.commit.message);
         Self {
             id: release_note.commit.id.to_string(),
             message: release_note.commit.message.trim().to_string(),
             description: release_note.commit.description.trim().to_string(),
             body: release_note.commit.body.trim().to_string(),
-            footers: None, // TODO: parse footers for ScmCommit
             timestamp: release_note.commit.timestamp,
             commit_style: "".to_string(),
             commit_type: None,
-            scope: None,
+            scope: release_note.category.clone(),
             author: release_note.commit.author.clone(),
             committer: release_note.commit.committer.clone(),
-            // TODO: support breaking change
-            breaking: false,
+            co_authors: Footer::co_authors(&footers, release_note.commit.timestamp),
+            breaking: release_note.breaking_change || footers.iter().any(|f| f.breaking),
+            footers: Some(footers),
         }
     }
 
+    /// Commit created by `git revert` which references the summary of the reverted commit.
+    /// The scope of the reverted commit is retained when it was a conventional commit.
+    pub fn from_revert(commit: &ScmCommit, reverted_summary: &str) -> Self {
+        let mut changelog_commit = Self::from_scm_commit(commit);
+        changelog_commit.commit_type = Some("revert".to_string());
+        changelog_commit.scope = GitConventionalCommit::parse(reverted_summary)
+            .ok()
+            .and_then(|c| c.scope().map(|scope| scope.to_string()));
+        changelog_commit
+    }
+
     pub fn from_conventional(conventional: ConventionalCommit) -> Self {
+        let footers = conventional
+            .conv
+            .footers()
+            .iter()
+            .map(Footer::from)
+            .collect::<Vec<Footer>>();
         Self {
             id: conventional.commit.id.to_string(),
             message: conventional.commit.message.trim().to_string(),
             description: conventional.commit.description.trim().to_string(),
             body: conventional.commit.body.trim().to_string(),
-            footers: Some(
-                conventional
-                    .conv
-                    .footers()
-                    .iter()
-                    .map(Footer::from)
-                    .collect::<Vec<Footer>>(),
-            ),
+            co_authors: Footer::co_authors(&footers, conventional.commit.timestamp),
+            footers: Some(footers),
             timestamp: conventional.commit.timestamp,
             commit_style: "".to_string(),
             commit_type: Some(conventional.conv.type_().to_string()),
@@ -311,6 +343,94 @@ pub struct Footer {
     breaking: bool,
 }
 
+impl Footer {
+    /// Parses footers (git trailers) from the last paragraph of a commit message.
+    ///
+    /// Follows the git trailer / conventional commit footer format of `Token: value` or
+    /// `Token #value` with indented lines continuing the previous footer's value. The paragraph is
+    /// only treated as footers when every line is a footer or a continuation and the paragraph is
+    /// not the commit summary.
+    pub fn parse_all(message: &str) -> Vec<Footer> {
+        let paragraphs = message
+            .trim()
+            .split("\n\n")
+            .map(|p| p.trim_matches('\n'))
+            .filter(|p| !p.trim().is_empty())
+            .collect::<Vec<&str>>();
+
+        // first paragraph is the summary
+        if paragraphs.len() < 2 {
+            return vec![];
+        }
+
+        let mut footers: Vec<Footer> = vec![];
+        for line in paragraphs[paragraphs.len() - 1].lines() {
+            let line = line.trim_end_matches('\r');
+            if line.starts_with([' ', '\t']) {
+                match footers.last_mut() {
+                    Some(footer) => {
+                        footer.value.push('\n');
+                        footer.value.push_str(line.trim());
+                        continue;
+                    }
+                    None => return vec![],
+                }
+            }
+
+            match FOOTER_RE.captures(line) {
+                Some(captures) => {
+                    let token = &captures["token"];
+                    footers.push(Footer {
+                        token: token.to_string(),
+                        separator: captures["separator"].trim_end().to_string(),
+                        value: captures["value"].trim().to_string(),
+                        breaking: token == BREAKING_PHRASE || token == BREAKING_ARROW,
+                    });
+                }
+                None => return vec![],
+            }
+        }
+
+        footers
+    }
+
+    /// Signatures for all `Co-authored-by` footers in the format of `Name <email>`
+    pub fn co_authors(footers: &[Footer], timestamp: i64) -> Vec<Signature> {
+        footers
+            .iter()
+            .filter(|f| f.token.eq_ignore_ascii_case(CO_AUTHORED_BY))
+            .map(|f| match IDENTITY_RE.captures(&f.value) {
+                Some(captures) => Signature {
+                    name: Some(captures["name"].to_string()).filter(|n| !n.is_empty()),
+                    email: Some(captures["email"].to_string()),
+                    timestamp,
+                },
+                None => Signature {
+                    name: Some(f.value.to_string()),
+                    email: None,
+                    timestamp,
+                },
+            })
+            .collect()
+    }
+
+    pub fn token(&self) -> &str {
+        self.token.as_str()
+    }
+
+    pub fn separator(&self) -> &str {
+        self.separator.as_str()
+    }
+
+    pub fn value(&self) -> &str {
+        self.value.as_str()
+    }
+
+    pub fn breaking(&self) -> bool {
+        self.breaking
+    }
+}
+
 impl<'a> Display for Footer {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         let Self {
@@ -333,3 +453,38 @@ impl<'a> From<&'a ConventionalFooter<'a>> for Footer {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Footer;
+
+    #[test]
+    fn should_parse_trailers() {
+        let footers = Footer::parse_all(
+            "Add foo\n\nSome body\n\nRefs: #123\nReviewed-by: Jane <jane@example.com>\n\
+            Co-authored-by: John Doe <john@example.com>\nFixes #42\n\
+            BREAKING CHANGE: foo is removed\n  use bar instead",
+        );
+
+        assert_eq!(5, footers.len());
+        assert_eq!("Refs", footers[0].token());
+        assert_eq!("#123", footers[0].value());
+        assert_eq!("Reviewed-by", footers[1].token());
+        assert_eq!("Fixes", footers[3].token());
+        assert_eq!(" #", footers[3].separator());
+        assert_eq!("42", footers[3].value());
+        assert!(footers[4].breaking());
+        assert_eq!("foo is removed\nuse bar instead", footers[4].value());
+
+        let co_authors = Footer::co_authors(&footers, 10);
+        assert_eq!(1, co_authors.len());
+        assert_eq!(Some("John Doe".to_string()), co_authors[0].name);
+        assert_eq!(Some("john@example.com".to_string()), co_authors[0].email);
+    }
+
+    #[test]
+    fn should_not_parse_trailers_from_summary_or_prose() {
+        assert!(Footer::parse_all("Refs: #123").is_empty());
+        assert!(Footer::parse_all("Add foo\n\nNote: this is prose\nthat keeps going").is_empty());
+    }
+}
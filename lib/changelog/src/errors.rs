@@ -26,6 +26,9 @@ pub enum ChangelogErrors {
 
     #[error(transparent)]
     SomeverError(#[from] SomeverError),
+
+    #[error(transparent)]
+    TomlDeserialize(#[from] toml::de::Error),
 }
 
 pub type ChangelogResult<T> = Result<T, ChangelogErrors>;
@@ -1,8 +1,13 @@
 pub mod changelog;
+pub mod cliff;
 pub mod commits;
 pub mod conventional;
+pub mod diagnostics;
 pub mod entries;
 pub mod errors;
 pub mod release;
 pub mod release_notes;
+pub mod remote;
 pub mod settings;
+pub mod templates;
+pub mod update;
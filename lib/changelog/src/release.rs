@@ -2,6 +2,7 @@ use serde::Serialize;
 use somever::Somever;
 
 use crate::entries::ChangelogEntry;
+use crate::remote::RemoteRepository;
 
 #[derive(Default, Debug, Serialize)]
 pub struct Release {
@@ -19,11 +20,37 @@ pub struct Release {
 
     /// Timestamp of the release in seconds, from epoch.
     pub timestamp: Option<i64>,
+
+    /// Name of the tag associated with the release
+    pub tag: Option<String>,
+
+    /// Previous release of the same repository
+    pub previous: Option<PreviousRelease>,
+
+    /// URL comparing this release to the previous release. Requires a remote repository
+    pub compare_url: Option<String>,
+}
+
+/// Metadata of the release preceding another release
+#[derive(Debug, Serialize)]
+pub struct PreviousRelease {
+    pub version: Option<Somever>,
+
+    pub tag: Option<String>,
+
+    /// Commit ID of the tag.
+    pub tag_id: Option<String>,
+
+    /// Timestamp of the release in seconds, from epoch.
+    pub timestamp: Option<i64>,
 }
 
 /// Representation of a list of releases.
 #[derive(Serialize)]
 pub struct Releases<'a> {
     /// Releases.
-    pub releases: &'a Vec<Release>,
+    pub releases: &'a [Release],
+
+    /// Remote repository the changelog is generated for
+    pub remote: Option<&'a RemoteRepository>,
 }
@@ -32,16 +32,28 @@ impl ReleaseNotes {
         for line in commit.message.lines() {
             if RE.is_match(line) {
                 let captures = RE.captures(line).unwrap();
-                let category = captures.name("category").map(|c| c.as_str().to_string());
+                // categories are commonly wrapped in parens e.g. `Release note (bug fix): ...`
+                let category = captures
+                    .name("category")
+                    .map(|c| {
+                        c.as_str()
+                            .trim()
+                            .trim_start_matches('(')
+                            .trim_end_matches(')')
+                            .trim()
+                            .to_string()
+                    })
+                    .filter(|c| !c.is_empty());
                 let description = captures
                     .name("description")
-                    .map_or(String::new(), |c| c.as_str().to_string());
+                    .map_or(String::new(), |c| c.as_str().trim().to_string());
 
                 let breaking_change = category
                     .as_ref()
-                    .is_some_and(|c| *c == self.breaking_change_category);
+                    .is_some_and(|c| c.eq_ignore_ascii_case(&self.breaking_change_category));
 
-                if description.to_lowercase().trim() == "none" {
+                // `Release note: None` explicitly opts the commit out of release notes
+                if !description.eq_ignore_ascii_case("none") {
                     release_notes.push(ReleaseNote {
                         category,
                         description,
@@ -55,3 +67,59 @@ impl ReleaseNotes {
         release_notes
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use scm::commit::{ScmCommit, ScmSignature};
+
+    use super::ReleaseNotes;
+
+    fn commit(message: &str) -> ScmCommit {
+        let signature = ScmSignature {
+            name: None,
+            email: None,
+            timestamp: 0,
+        };
+        ScmCommit {
+            id: "a1b2c3d".to_string(),
+            message: message.to_string(),
+            description: message.lines().next().unwrap_or_default().to_string(),
+            body: "".to_string(),
+            author: signature.clone(),
+            committer: signature,
+            timestamp: 0,
+        }
+    }
+
+    #[test]
+    fn should_parse_release_notes() {
+        let release_notes = ReleaseNotes {
+            breaking_change_category: "backward-incompatible change".to_string(),
+        };
+
+        let notes = release_notes.parse_commit(&commit(
+            "sql: drop foo\n\nRelease note (backward-incompatible change): foo was removed\n\
+            Release note (bug fix): bar no longer panics",
+        ));
+
+        assert_eq!(2, notes.len());
+        assert_eq!(
+            Some("backward-incompatible change".to_string()),
+            notes[0].category
+        );
+        assert_eq!("foo was removed", notes[0].description);
+        assert!(notes[0].breaking_change);
+        assert_eq!(Some("bug fix".to_string()), notes[1].category);
+        assert!(!notes[1].breaking_change);
+    }
+
+    #[test]
+    fn should_skip_release_note_none() {
+        let release_notes = ReleaseNotes {
+            breaking_change_category: "breaking".to_string(),
+        };
+
+        let notes = release_notes.parse_commit(&commit("chore: bump deps\n\nRelease note: None"));
+        assert!(notes.is_empty());
+    }
+}
@@ -0,0 +1,129 @@
+use scm::platforms::ScmPlatform;
+use scm::remote::ScmRemote;
+use serde_derive::Serialize;
+
+use crate::settings::ChangelogRemoteSettings;
+
+/// Repository hosted on an SCM platform used to build links within the changelog
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct RemoteRepository {
+    pub platform: ScmPlatform,
+
+    pub host: String,
+
+    /// Owner of the repository. For GitLab this includes any subgroups
+    pub owner: String,
+
+    pub repo: String,
+
+    /// Web URL of the repository
+    pub url: String,
+}
+
+impl RemoteRepository {
+    pub fn new(platform: ScmPlatform, host: &str, owner: &str, repo: &str) -> Self {
+        Self {
+            platform,
+            host: host.to_string(),
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+            url: format!("https://{host}/{owner}/{repo}"),
+        }
+    }
+
+    /// Derives the remote repository from a git remote URL.
+    /// Returns `None` when the URL can't be parsed or the host isn't a known GitHub, GitLab or
+    /// Gitea instance.
+    pub fn from_url(url: &str) -> Option<Self> {
+        Self::from_remote(ScmRemote::parse(url)?)
+    }
+
+    /// Remote repository for a parsed remote.
+    /// Returns `None` for platforms other than GitHub, GitLab or Gitea.
+    pub fn from_remote(remote: ScmRemote) -> Option<Self> {
+        match remote.platform {
+            ScmPlatform::GitHub | ScmPlatform::GitLab | ScmPlatform::Gitea => Some(Self::new(
+                remote.platform,
+                &remote.host,
+                &remote.owner,
+                &remote.repo,
+            )),
+            _ => None,
+        }
+    }
+
+    /// Remote repository from configured settings.
+    /// Only platforms with a well known host are supported.
+    pub fn from_settings(settings: &ChangelogRemoteSettings) -> Option<Self> {
+        settings.providers.iter().find_map(|(platform, remote)| {
+            let host = match platform {
+                ScmPlatform::GitHub => "github.com",
+                ScmPlatform::GitLab => "gitlab.com",
+                _ => return None,
+            };
+
+            Some(Self::new(
+                platform.clone(),
+                host,
+                &remote.owner,
+                &remote.repo,
+            ))
+        })
+    }
+
+    /// URL comparing two revisions
+    pub fn compare_url(&self, from: &str, to: &str) -> String {
+        match self.platform {
+            ScmPlatform::GitLab => format!("{}/-/compare/{from}...{to}", self.url),
+            _ => format!("{}/compare/{from}...{to}", self.url),
+        }
+    }
+
+    /// URL of a single commit
+    pub fn commit_url(&self, id: &str) -> String {
+        match self.platform {
+            ScmPlatform::GitLab => format!("{}/-/commit/{id}", self.url),
+            _ => format!("{}/commit/{id}", self.url),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use scm::platforms::ScmPlatform;
+    use test_case::test_case;
+
+    use crate::remote::RemoteRepository;
+
+    #[test_case("git@github.com:doctavious/doctavious.git" => Some((ScmPlatform::GitHub, "doctavious".to_string(), "doctavious".to_string())); "github scp")]
+    #[test_case("https://github.com/doctavious/doctavious" => Some((ScmPlatform::GitHub, "doctavious".to_string(), "doctavious".to_string())); "github https")]
+    #[test_case("ssh://git@gitlab.com:2222/group/sub/project.git" => Some((ScmPlatform::GitLab, "group/sub".to_string(), "project".to_string())); "gitlab subgroup")]
+    #[test_case("https://user@codeberg.org/owner/repo.git/" => Some((ScmPlatform::Gitea, "owner".to_string(), "repo".to_string())); "codeberg")]
+    #[test_case("git@bitbucket.org:workspace/repo.git" => None; "unsupported platform")]
+    #[test_case("https://example.com/owner/repo.git" => None; "unknown host")]
+    #[test_case("/srv/git/repo" => None; "local path")]
+    fn from_url(url: &str) -> Option<(ScmPlatform, String, String)> {
+        RemoteRepository::from_url(url).map(|r| (r.platform, r.owner, r.repo))
+    }
+
+    #[test]
+    fn should_build_platform_urls() {
+        let github =
+            RemoteRepository::from_url("git@github.com:doctavious/doctavious.git").unwrap();
+        assert_eq!("https://github.com/doctavious/doctavious", github.url);
+        assert_eq!(
+            "https://github.com/doctavious/doctavious/compare/v1.0.0...v1.1.0",
+            github.compare_url("v1.0.0", "v1.1.0")
+        );
+
+        let gitlab = RemoteRepository::from_url("https://gitlab.com/group/project.git").unwrap();
+        assert_eq!(
+            "https://gitlab.com/group/project/-/compare/v1.0.0...HEAD",
+            gitlab.compare_url("v1.0.0", "HEAD")
+        );
+        assert_eq!(
+            "https://gitlab.com/group/project/-/commit/abc",
+            gitlab.commit_url("abc")
+        );
+    }
+}
@@ -11,7 +11,7 @@ use serde_derive::{Deserialize, Serialize};
 use serde_json::Value;
 use somever::VersioningScheme;
 use strum::{Display, EnumIter, EnumString, VariantNames};
-use tracing::warn;
+use tracing::{debug, warn};
 
 use crate::entries::ChangelogCommit;
 use crate::errors::{ChangelogErrors, ChangelogResult};
@@ -141,7 +141,7 @@ pub struct TemplateSettings {
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct ChangelogRemoteSettings {
     // TODO: flatten? ScmProvider lowercase?
-    providers: HashMap<ScmPlatform, ChangelogRemote>,
+    pub providers: HashMap<ScmPlatform, ChangelogRemote>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -179,15 +179,55 @@ pub struct ChangelogBumpSettings {
     pub breaking_always_bump_major: bool,
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct ConventionalCommitSettings {
     /// Whether to include unconventional commits.
+    /// When `false` (strict) a commit that fails to parse as a conventional commit is an error.
+    /// When `true` (lenient) the commit is kept and grouped under `unconventional_group`.
     pub include_unconventional: bool,
 
+    /// Group assigned to unconventional commits that don't match any group parser.
+    /// Defaults to `Other`
+    #[serde(default = "default_unconventional_group")]
+    pub unconventional_group: String,
+
+    /// How merge commits should be handled. Defaults to skipping them.
+    #[serde(default)]
+    pub merge_commits: MergeCommitHandling,
+
     /// Whether to split commits by line, processing each line as an individual commit.
     pub split_commits: bool,
 }
 
+impl Default for ConventionalCommitSettings {
+    fn default() -> Self {
+        Self {
+            include_unconventional: false,
+            unconventional_group: default_unconventional_group(),
+            merge_commits: MergeCommitHandling::default(),
+            split_commits: false,
+        }
+    }
+}
+
+fn default_unconventional_group() -> String {
+    "Other".to_string()
+}
+
+#[remain::sorted]
+#[derive(Clone, Copy, Debug, Default, Deserialize, Display, EnumString, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum MergeCommitHandling {
+    /// Treat merge commits like any other commit.
+    /// With conventional commits these are subject to `include_unconventional`.
+    Include,
+
+    /// Drop merge commits from the changelog
+    #[default]
+    Skip,
+}
+
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct ReleaseNoteSettings {
     /// Category that flags commit as a breaking change.
@@ -278,6 +318,36 @@ impl CommitParser {
             })
     }
 
+    /// Similar to [`CommitParser::dot_path`] but resolves every value along the path fanning out
+    /// over arrays. Array elements that are footers are addressed by their token so that
+    /// `footers.Refs` resolves to the value of every `Refs` footer.
+    pub fn dot_path_all<'a>(value: &'a Value, path: &str) -> Vec<&'a Value> {
+        path.split('.').fold(vec![value], |targets, token| {
+            targets
+                .into_iter()
+                .flat_map(|target| match target {
+                    Value::Object(map) => map.get(token).into_iter().collect(),
+                    Value::Array(items) => items
+                        .iter()
+                        .filter_map(|item| {
+                            let map = item.as_object()?;
+                            let is_footer = map
+                                .get("token")
+                                .and_then(Value::as_str)
+                                .is_some_and(|t| t.eq_ignore_ascii_case(token));
+                            if is_footer {
+                                map.get("value")
+                            } else {
+                                map.get(token)
+                            }
+                        })
+                        .collect(),
+                    _ => vec![],
+                })
+                .collect()
+        })
+    }
+
     pub fn matched(&self, commit: &ChangelogCommit) -> ChangelogResult<Option<String>> {
         let mut checks = Vec::new();
         // TODO: need to support multiple field checks
@@ -287,15 +357,23 @@ impl CommitParser {
         let pattern = &self.pattern;
 
         let commit_value = serde_json::to_value(commit)?;
-        let value = Self::dot_path(&commit_value, field);
-        if let Some(v) = value {
+        let root = field.split('.').next().unwrap_or_default();
+        if commit_value.get(root).is_none() {
+            return Err(ChangelogErrors::ChangelogError(format!(
+                "invalid group parser field {field}",
+            )));
+        }
+
+        for v in Self::dot_path_all(&commit_value, field) {
             match v {
-                Value::Bool(_) | Value::Number(_) | Value::String(_) => {
-                    checks.push(Some(v.to_string()))
-                }
-                Value::Array(a) => checks.extend(a.iter().map(|f| Some(f.to_string()))),
+                Value::String(s) => checks.push(s.to_string()),
+                Value::Bool(_) | Value::Number(_) => checks.push(v.to_string()),
+                Value::Array(a) => checks.extend(a.iter().map(|f| match f {
+                    Value::String(s) => s.to_string(),
+                    _ => f.to_string(),
+                })),
                 Value::Null => {
-                    warn!("skipping commit parser with field {field} as it has no value");
+                    debug!("skipping commit parser with field {field} as it has no value");
                 }
                 Value::Object(_) => {
                     warn!(
@@ -305,19 +383,9 @@ impl CommitParser {
             }
         }
 
-        if checks.is_empty() {
-            return Err(ChangelogErrors::ChangelogError(format!(
-                "invalid group parser field {field}",
-            )));
-        }
-
         for text in checks {
-            if let Some(text) = text {
-                if pattern.is_match(&text) {
-                    return Ok(Some(text));
-                }
-            } else {
-                warn!("Skip group parser for field {field} does not have a value")
+            if pattern.is_match(&text) {
+                return Ok(Some(text));
             }
         }
 
@@ -441,8 +509,11 @@ impl ChangelogCommitSort {
 
 #[cfg(test)]
 mod tests {
+    use regex::Regex;
+    use scm::commit::{ScmCommit, ScmSignature};
     use serde_derive::Serialize;
 
+    use crate::entries::ChangelogCommit;
     use crate::settings::CommitParser;
 
     #[derive(Debug, Clone, PartialEq, Serialize)]
@@ -471,4 +542,47 @@ mod tests {
         let r = CommitParser::dot_path(&value, "body");
         println!("{:?}", r);
     }
+
+    #[test]
+    fn should_match_footers_by_token() {
+        let commit = ChangelogCommit::from_scm_commit(&ScmCommit {
+            id: "a1b2c3d".to_string(),
+            message: "Add foo\n\nRefs: JIRA-123\nCo-authored-by: Jane <jane@example.com>"
+                .to_string(),
+            description: "Add foo".to_string(),
+            body: "".to_string(),
+            author: ScmSignature {
+                name: None,
+                email: None,
+                timestamp: 0,
+            },
+            committer: ScmSignature {
+                name: None,
+                email: None,
+                timestamp: 0,
+            },
+            timestamp: 0,
+        });
+
+        let parser = CommitParser {
+            field: "footers.Refs".to_string(),
+            pattern: Regex::new("^JIRA-").unwrap(),
+        };
+        assert_eq!(
+            Some("JIRA-123".to_string()),
+            parser.matched(&commit).unwrap()
+        );
+
+        let parser = CommitParser {
+            field: "footers.Fixes".to_string(),
+            pattern: Regex::new(".*").unwrap(),
+        };
+        assert_eq!(None, parser.matched(&commit).unwrap());
+
+        let parser = CommitParser {
+            field: "unknown".to_string(),
+            pattern: Regex::new(".*").unwrap(),
+        };
+        assert!(parser.matched(&commit).is_err());
+    }
 }
@@ -0,0 +1,278 @@
+use regex::Regex;
+use serde_derive::{Deserialize, Serialize};
+use strum::{Display, EnumIter, EnumString, VariantNames};
+
+use crate::settings::{
+    ChangelogCommitSettings, ChangelogSettings, CommitParser, CommitStyleSettings,
+    ConventionalCommitSettings, GroupParser, TemplateSettings,
+};
+
+const BODY_MARKER: &str = "<!-- body -->";
+const FOOTER_MARKER: &str = "<!-- footer -->";
+
+// conventional commit types and the group they are rendered under
+const DEFAULT_GROUPS: [(&str, &str); 11] = [
+    ("^feat", "Features"),
+    ("^fix", "Bug Fixes"),
+    ("^doc", "Documentation"),
+    ("^perf", "Performance"),
+    ("^refactor", "Refactor"),
+    ("^style", "Styling"),
+    ("^test", "Testing"),
+    ("^build", "Build"),
+    ("^ci", "Continuous Integration"),
+    ("^revert", "Reverts"),
+    ("^chore", "Miscellaneous Tasks"),
+];
+
+/// Built-in changelog templates
+#[remain::sorted]
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Deserialize,
+    Display,
+    EnumIter,
+    EnumString,
+    PartialEq,
+    Serialize,
+    VariantNames,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ChangelogTemplate {
+    Cocogitto,
+    Github,
+    Gitlab,
+    #[default]
+    KeepAChangelog,
+}
+
+impl ChangelogTemplate {
+    pub fn description(&self) -> &'static str {
+        match self {
+            ChangelogTemplate::Cocogitto => {
+                "Cocogitto style changelog with scoped entries listed first"
+            }
+            ChangelogTemplate::Github => "GitHub style release notes",
+            ChangelogTemplate::Gitlab => "GitLab style changelog with change counts per group",
+            ChangelogTemplate::KeepAChangelog => "https://keepachangelog.com changelog",
+        }
+    }
+
+    /// Raw template content.
+    /// Templates may contain `<!-- body -->` and `<!-- footer -->` markers which separate the
+    /// header, body and footer templates.
+    pub fn content(&self) -> &'static str {
+        match self {
+            ChangelogTemplate::Cocogitto => include_str!("../templates/cocogitto.tmpl"),
+            ChangelogTemplate::Github => include_str!("../templates/github.tmpl"),
+            ChangelogTemplate::Gitlab => include_str!("../templates/gitlab.tmpl"),
+            ChangelogTemplate::KeepAChangelog => {
+                include_str!("../templates/keep_a_changelog.tmpl")
+            }
+        }
+    }
+
+    /// Splits the template content into header, body and footer templates
+    pub fn template_settings(&self) -> TemplateSettings {
+        let content = self.content();
+        let (header, rest) = match content.split_once(BODY_MARKER) {
+            Some((header, rest)) => (Some(header), rest),
+            None => (None, content),
+        };
+
+        let (body, footer) = match rest.split_once(FOOTER_MARKER) {
+            Some((body, footer)) => (body, Some(footer)),
+            None => (rest, None),
+        };
+
+        let non_empty = |s: &str| {
+            let s = s.trim();
+            (!s.is_empty()).then(|| format!("{s}\n"))
+        };
+
+        TemplateSettings {
+            header: header.and_then(non_empty),
+            body: body.trim_start_matches('\n').to_string(),
+            footer: footer.and_then(non_empty),
+            // templates manage their own whitespace
+            trim: false,
+            post_processors: None,
+        }
+    }
+
+    /// Changelog settings using this template along with conventional commits grouped by type
+    pub fn settings(&self) -> ChangelogSettings {
+        let group_parsers = DEFAULT_GROUPS
+            .iter()
+            .map(|(pattern, group)| GroupParser {
+                commit_parser: CommitParser {
+                    field: "commit_type".to_string(),
+                    pattern: Regex::new(pattern).expect("default group pattern should be valid"),
+                },
+                group: Some(group.to_string()),
+                default_scope: None,
+                scope: None,
+                skip: None,
+            })
+            .collect();
+
+        ChangelogSettings {
+            template: self.template_settings(),
+            commit: ChangelogCommitSettings {
+                commit_style: CommitStyleSettings::Conventional(ConventionalCommitSettings {
+                    include_unconventional: true,
+                    ..Default::default()
+                }),
+                ignore: Some(vec![CommitParser {
+                    field: "message".to_string(),
+                    pattern: Regex::new(r"^chore\(release\): prepare for")
+                        .expect("default ignore pattern should be valid"),
+                }]),
+                group_parsers: Some(group_parsers),
+                ..Default::default()
+            },
+            protect_breaking_commits: true,
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use doctavious_templating::{TemplateContext, Templates};
+    use scm::commit::{ScmCommit, ScmSignature, ScmTag};
+    use strum::IntoEnumIterator;
+
+    use super::ChangelogTemplate;
+    use crate::changelog::Changelog;
+    use crate::commits::ScmTaggedCommits;
+    use crate::remote::RemoteRepository;
+
+    fn tagged_commits() -> Vec<ScmTaggedCommits> {
+        let commit = |id: &str, message: &str| ScmCommit {
+            id: id.to_string(),
+            message: message.to_string(),
+            description: message.to_string(),
+            body: "".to_string(),
+            author: ScmSignature {
+                name: Some("Jane".to_string()),
+                email: None,
+                timestamp: 0,
+            },
+            committer: ScmSignature {
+                name: None,
+                email: None,
+                timestamp: 0,
+            },
+            timestamp: 0,
+        };
+
+        let tag = |name: &str| ScmTag {
+            id: None,
+            name: name.to_string(),
+            message: None,
+            timestamp: 0,
+        };
+
+        vec![
+            ScmTaggedCommits {
+                repository: "".to_string(),
+                tag: None,
+                commits: vec![commit("c1b2c3d4e5", "docs: describe templates")],
+                timestamp: None,
+            },
+            ScmTaggedCommits {
+                repository: "".to_string(),
+                tag: Some(tag("v1.1.0")),
+                commits: vec![
+                    commit("a1b2c3d4e5", "feat(cli): add templates"),
+                    commit("b1b2c3d4e5", "fix: handle empty changelog"),
+                ],
+                timestamp: Some(0),
+            },
+            ScmTaggedCommits {
+                repository: "".to_string(),
+                tag: Some(tag("v1.0.0")),
+                commits: vec![commit("d1b2c3d4e5", "feat: initial release")],
+                timestamp: Some(0),
+            },
+        ]
+    }
+
+    #[test]
+    fn should_split_header_from_body() {
+        let settings = ChangelogTemplate::KeepAChangelog.template_settings();
+        assert!(settings.header.unwrap().contains("# Changelog"));
+        assert!(!settings.body.contains("<!-- body -->"));
+        assert!(!settings.body.contains("<!-- footer -->"));
+        assert!(settings.footer.unwrap().contains("compare_url"));
+
+        let settings = ChangelogTemplate::Github.template_settings();
+        assert!(settings.header.is_none());
+    }
+
+    #[test]
+    fn should_parse_template_names() {
+        assert_eq!(
+            ChangelogTemplate::KeepAChangelog,
+            ChangelogTemplate::from_str("keep_a_changelog").unwrap()
+        );
+        assert!(ChangelogTemplate::from_str("unknown").is_err());
+    }
+
+    #[test]
+    fn should_render_all_templates() {
+        for template in ChangelogTemplate::iter() {
+            let settings = template.settings();
+            if let Some(header) = &settings.template.header {
+                Templates::one_off(header, &TemplateContext::new(), false).unwrap();
+            }
+
+            let changelog = Changelog::new(tagged_commits(), settings).unwrap();
+            let mut output = Vec::new();
+            changelog.generate(&mut output).unwrap();
+            let output = String::from_utf8(output).unwrap();
+
+            assert!(
+                output.contains("add templates"),
+                "{template} did not render commits:\n{output}"
+            );
+            assert!(
+                output.contains("handle empty changelog"),
+                "{template} did not render commits:\n{output}"
+            );
+        }
+    }
+
+    #[test]
+    fn should_render_compare_urls() {
+        let remote = RemoteRepository::from_url("git@github.com:doctavious/doctavious.git");
+        let changelog = Changelog::new(
+            tagged_commits(),
+            ChangelogTemplate::KeepAChangelog.settings(),
+        )
+        .unwrap()
+        .with_remote(remote);
+
+        let mut output = Vec::new();
+        changelog.generate(&mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        assert!(output.contains(
+            "[unreleased]: https://github.com/doctavious/doctavious/compare/v1.1.0...HEAD"
+        ));
+        assert!(
+            output.contains(
+                "[1.1.0]: https://github.com/doctavious/doctavious/compare/v1.0.0...v1.1.0"
+            )
+        );
+        assert!(!output.contains("[1.0.0]:"));
+        assert!(output.contains("- Feat(cli): add templates"));
+    }
+}
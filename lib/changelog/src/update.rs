@@ -0,0 +1,534 @@
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde_derive::Serialize;
+
+lazy_static! {
+    static ref SECTION_START_RE: Regex = Regex::new(
+        r#"^<!-- doctavious:release key="(?<key>[^"]*)" commits="(?<commits>[^"]*)" checksum="(?<checksum>[^"]*)" -->$"#
+    )
+    .unwrap();
+}
+
+const SECTION_END: &str = "<!-- doctavious:release-end -->";
+const FOOTER_START: &str = "<!-- doctavious:footer -->";
+const FOOTER_END: &str = "<!-- doctavious:footer-end -->";
+pub const UNRELEASED_KEY: &str = "unreleased";
+
+/// Rendered release section of a changelog along with the metadata used to detect changes
+#[derive(Debug)]
+pub struct ReleaseSection {
+    /// Tag of the release or `unreleased`
+    pub key: String,
+
+    /// Checksum of the commits that make up the release
+    pub commits: String,
+
+    pub content: String,
+}
+
+impl ReleaseSection {
+    pub fn new<I, S>(key: &str, commit_ids: I, content: &str) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut hasher = blake3::Hasher::new();
+        for id in commit_ids {
+            hasher.update(id.as_ref().as_bytes());
+            hasher.update(b"\n");
+        }
+
+        Self {
+            key: key.to_string(),
+            commits: short_hash(hasher.finalize()),
+            content: content.trim().to_string(),
+        }
+    }
+
+    fn render(&self) -> String {
+        format!(
+            "<!-- doctavious:release key=\"{}\" commits=\"{}\" checksum=\"{}\" -->\n{}\n{SECTION_END}",
+            self.key,
+            self.commits,
+            checksum(&self.content),
+            self.content
+        )
+    }
+}
+
+/// A release section that was edited by hand and could not be updated
+#[derive(Clone, Debug, Serialize)]
+pub struct SectionConflict {
+    pub release: String,
+    pub reason: SectionConflictReason,
+}
+
+impl Display for SectionConflict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {}. Kept the edited section",
+            self.release, self.reason
+        )
+    }
+}
+
+#[remain::sorted]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SectionConflictReason {
+    /// Section was edited and the commits of the release changed
+    CommitsChanged,
+
+    /// Unreleased section was edited but is no longer generated, typically because it was released
+    Removed,
+}
+
+impl Display for SectionConflictReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SectionConflictReason::CommitsChanged => {
+                write!(f, "section was edited manually and its commits changed")
+            }
+            SectionConflictReason::Removed => {
+                write!(f, "section was edited manually and is no longer generated")
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct ExistingSection {
+    key: String,
+
+    /// Commits checksum. Not present for sections without markers
+    commits: Option<String>,
+
+    /// Whether the content no longer matches the checksum it was generated with.
+    /// Sections without markers are always considered edited
+    edited: bool,
+
+    /// Section as it appears in the changelog, including any markers
+    raw: String,
+
+    /// Hand-written text between the section and the next one, or the footer, which is kept as-is
+    trailing: String,
+}
+
+#[derive(Debug, Default)]
+struct ExistingChangelog {
+    preamble: String,
+    sections: Vec<ExistingSection>,
+    /// Whether sections were identified by markers rather than release headings
+    tracked: bool,
+}
+
+impl ExistingChangelog {
+    /// Parses the existing changelog. Changelogs without section markers are split on headings of
+    /// the level release sections are rendered with, so sub-headings such as
+    /// `### Upgrading to 2.0.0` stay part of their release.
+    fn parse(content: &str, heading_level: usize) -> Self {
+        if content.lines().any(|l| SECTION_START_RE.is_match(l)) {
+            Self::parse_tracked(content)
+        } else {
+            Self::parse_untracked(content, heading_level)
+        }
+    }
+
+    fn parse_tracked(content: &str) -> Self {
+        let mut changelog = ExistingChangelog {
+            tracked: true,
+            ..Default::default()
+        };
+        let mut preamble = vec![];
+        // text following the last completed section
+        let mut trailing = vec![];
+        let mut in_footer = false;
+        let mut current: Option<(ExistingSection, String, Vec<&str>)> = None;
+        for line in content.lines() {
+            if let Some((mut section, checksum_value, lines)) = current.take() {
+                if line.trim() == SECTION_END {
+                    let body = lines[1..].join("\n");
+                    section.edited = checksum(body.trim()) != checksum_value;
+                    section.raw = format!("{}\n{line}", lines.join("\n"));
+                    changelog.sections.push(section);
+                } else {
+                    let mut lines = lines;
+                    lines.push(line);
+                    current = Some((section, checksum_value, lines));
+                }
+                continue;
+            }
+
+            // the footer is regenerated
+            if in_footer {
+                in_footer = line.trim() != FOOTER_END;
+                continue;
+            }
+            if line.trim() == FOOTER_START {
+                in_footer = true;
+                continue;
+            }
+
+            if let Some(captures) = SECTION_START_RE.captures(line) {
+                if let Some(previous) = changelog.sections.last_mut() {
+                    previous.trailing = trailing.join("\n");
+                    trailing.clear();
+                }
+                current = Some((
+                    ExistingSection {
+                        key: captures["key"].to_string(),
+                        commits: Some(captures["commits"].to_string()),
+                        edited: false,
+                        raw: String::new(),
+                        trailing: String::new(),
+                    },
+                    captures["checksum"].to_string(),
+                    vec![line],
+                ));
+            } else if changelog.sections.is_empty() {
+                preamble.push(line);
+            } else {
+                trailing.push(line);
+            }
+        }
+
+        // an unterminated section is treated as edited
+        if let Some((mut section, _, lines)) = current {
+            section.edited = true;
+            section.raw = lines.join("\n");
+            changelog.sections.push(section);
+        } else if let Some(last) = changelog.sections.last_mut() {
+            last.trailing = trailing.join("\n");
+        }
+
+        changelog.preamble = preamble.join("\n");
+        changelog
+    }
+
+    fn parse_untracked(content: &str, heading_level: usize) -> Self {
+        let release_heading_re = Regex::new(&format!(
+            r"(?i)^#{{{heading_level}}} .*?\b(?<key>unreleased|v?\d+\.\d+\.\d+[\w.+-]*)"
+        ))
+        .expect("release heading regex should be valid");
+
+        let mut changelog = ExistingChangelog::default();
+        let mut preamble = vec![];
+        let mut current: Option<(String, Vec<&str>)> = None;
+        for line in content.lines() {
+            if let Some(captures) = release_heading_re.captures(line) {
+                if let Some((key, lines)) = current.take() {
+                    changelog
+                        .sections
+                        .push(ExistingSection::untracked(key, &lines));
+                }
+                current = Some((captures["key"].to_string(), vec![line]));
+            } else if let Some((_, lines)) = current.as_mut() {
+                lines.push(line);
+            } else {
+                preamble.push(line);
+            }
+        }
+
+        if let Some((key, lines)) = current {
+            changelog
+                .sections
+                .push(ExistingSection::untracked(key, &lines));
+        }
+
+        changelog.preamble = preamble.join("\n");
+        changelog
+    }
+}
+
+impl ExistingSection {
+    fn untracked(key: String, lines: &[&str]) -> Self {
+        Self {
+            key,
+            commits: None,
+            edited: true,
+            raw: lines.join("\n"),
+            trailing: String::new(),
+        }
+    }
+
+    /// Pushes the section, followed by any hand-written text that came after it
+    fn write_to(&self, parts: &mut Vec<String>) {
+        parts.push(self.raw.trim_end().to_string());
+        push_trailing(&self.trailing, parts);
+    }
+}
+
+fn push_trailing(trailing: &str, parts: &mut Vec<String>) {
+    let trailing = trailing.trim_start_matches('\n').trim_end();
+    if !trailing.is_empty() {
+        parts.push(trailing.to_string());
+    }
+}
+
+/// Level of the first markdown heading of the generated sections, which is used to identify
+/// releases in changelogs without section markers. Defaults to 2, i.e., `## 1.0.0`
+fn release_heading_level(sections: &[ReleaseSection]) -> usize {
+    sections
+        .iter()
+        .flat_map(|s| s.content.lines())
+        .find_map(|line| {
+            let level = line.chars().take_while(|c| *c == '#').count();
+            ((1..=6).contains(&level) && line[level..].starts_with(' ')).then_some(level)
+        })
+        .unwrap_or(2)
+}
+
+/// Merges newly generated release sections into an existing changelog.
+///
+/// - Sections whose commits haven't changed are left as-is, including any hand edits.
+/// - The unreleased section and sections whose commits changed are replaced.
+/// - Sections that were edited by hand are never replaced and are reported as conflicts instead.
+/// - Changelogs without section markers are matched by release heading and all existing sections
+///   are treated as hand-written.
+/// - Hand-written text between sections, or after the last one, is kept along with the section it
+///   follows. Only the footer, which is rendered between footer markers, is regenerated.
+///
+/// Returns the updated changelog along with any conflicts.
+pub fn merge(
+    existing: Option<&str>,
+    header: Option<&str>,
+    sections: &[ReleaseSection],
+    footer: Option<&str>,
+) -> (String, Vec<SectionConflict>) {
+    let heading_level = release_heading_level(sections);
+    let existing = existing.map(|e| ExistingChangelog::parse(e, heading_level));
+    let mut conflicts = vec![];
+    let mut parts = vec![];
+
+    match existing.as_ref() {
+        Some(existing) if !existing.preamble.trim().is_empty() => {
+            parts.push(existing.preamble.trim_end().to_string());
+        }
+        Some(_) => {}
+        None => parts.extend(header.map(|h| h.trim_end().to_string())),
+    }
+
+    let existing_sections = existing
+        .as_ref()
+        .map(|e| e.sections.as_slice())
+        .unwrap_or_default();
+    let mut matched = vec![false; existing_sections.len()];
+    let mut merged = vec![];
+
+    for section in sections {
+        let position = existing_sections
+            .iter()
+            .position(|e| normalize_key(&e.key) == normalize_key(&section.key));
+
+        let Some(position) = position else {
+            merged.push(section.render());
+            continue;
+        };
+
+        matched[position] = true;
+        let current = &existing_sections[position];
+        let is_unreleased = normalize_key(&section.key) == UNRELEASED_KEY;
+        let changed = current.commits.as_deref() != Some(section.commits.as_str());
+        if current.edited {
+            // sections without markers only conflict when they are expected to be regenerated
+            let conflicted = match current.commits {
+                Some(_) => changed,
+                None => is_unreleased,
+            };
+
+            if conflicted {
+                conflicts.push(SectionConflict {
+                    release: section.key.clone(),
+                    reason: SectionConflictReason::CommitsChanged,
+                });
+            }
+            current.write_to(&mut merged);
+        } else if changed || is_unreleased {
+            merged.push(section.render());
+            push_trailing(&current.trailing, &mut merged);
+        } else {
+            current.write_to(&mut merged);
+        }
+    }
+
+    // releases that are no longer generated are kept after the generated sections.
+    // Unreleased sections are dropped unless they were edited in which case they stay on top.
+    // Text following a dropped section stays on top as well
+    for (section, _) in existing_sections
+        .iter()
+        .zip(matched)
+        .filter(|(_, matched)| !matched)
+    {
+        if normalize_key(&section.key) != UNRELEASED_KEY {
+            section.write_to(&mut merged);
+        } else if section.edited {
+            conflicts.push(SectionConflict {
+                release: section.key.clone(),
+                reason: SectionConflictReason::Removed,
+            });
+            section.write_to(&mut parts);
+        } else {
+            push_trailing(&section.trailing, &mut parts);
+        }
+    }
+    parts.extend(merged);
+
+    // hand-written changelogs manage their own footer
+    if existing.as_ref().is_none_or(|e| e.tracked) {
+        parts.extend(
+            footer
+                .map(str::trim)
+                .filter(|f| !f.is_empty())
+                .map(|f| format!("{FOOTER_START}\n{f}\n{FOOTER_END}")),
+        );
+    }
+
+    (format!("{}\n", parts.join("\n\n")), conflicts)
+}
+
+fn normalize_key(key: &str) -> String {
+    key.trim_start_matches('v').to_lowercase()
+}
+
+fn checksum(content: &str) -> String {
+    short_hash(blake3::hash(content.as_bytes()))
+}
+
+fn short_hash(hash: blake3::Hash) -> String {
+    hash.to_hex()[..16].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::update::{ReleaseSection, SectionConflictReason, merge};
+
+    fn sections(unreleased: &[&str], released: &[&str]) -> Vec<ReleaseSection> {
+        vec![
+            ReleaseSection::new(
+                "unreleased",
+                unreleased,
+                &format!("## Unreleased\n- {}", unreleased.join("\n- ")),
+            ),
+            ReleaseSection::new(
+                "v1.0.0",
+                released,
+                &format!("## 1.0.0\n- {}", released.join("\n- ")),
+            ),
+        ]
+    }
+
+    #[test]
+    fn should_preserve_edits_to_unchanged_releases() {
+        let (initial, conflicts) =
+            merge(None, Some("# Changelog"), &sections(&["b"], &["a"]), None);
+        assert!(conflicts.is_empty());
+
+        let edited = initial.replace("- a", "- a with curated notes");
+        let (updated, conflicts) = merge(
+            Some(&edited),
+            Some("# Changelog"),
+            &sections(&["b", "c"], &["a"]),
+            None,
+        );
+
+        assert!(conflicts.is_empty());
+        assert!(updated.starts_with("# Changelog\n"));
+        assert!(updated.contains("- b\n- c"));
+        assert!(updated.contains("- a with curated notes"));
+    }
+
+    #[test]
+    fn should_report_conflict_when_edited_section_changes() {
+        let (initial, _) = merge(None, None, &sections(&["b"], &["a"]), None);
+        let edited = initial.replace("- b", "- b with curated notes");
+
+        let (updated, conflicts) = merge(Some(&edited), None, &sections(&["b", "c"], &["a"]), None);
+
+        assert_eq!(1, conflicts.len());
+        assert_eq!("unreleased", conflicts[0].release);
+        assert_eq!(SectionConflictReason::CommitsChanged, conflicts[0].reason);
+        assert!(updated.contains("- b with curated notes"));
+        assert!(!updated.contains("- c"));
+    }
+
+    #[test]
+    fn should_replace_unreleased_section_once_released() {
+        let (initial, _) = merge(None, None, &sections(&["b"], &["a"]), None);
+
+        let released = vec![
+            ReleaseSection::new("v1.1.0", ["b"], "## 1.1.0\n- b"),
+            ReleaseSection::new("v1.0.0", ["a"], "## 1.0.0\n- a"),
+        ];
+        let (updated, conflicts) = merge(Some(&initial), None, &released, Some("[footer]"));
+
+        assert!(conflicts.is_empty());
+        assert!(!updated.contains("Unreleased"));
+        assert!(updated.find("## 1.1.0").unwrap() < updated.find("## 1.0.0").unwrap());
+        assert!(updated.ends_with("[footer]\n<!-- doctavious:footer-end -->\n"));
+    }
+
+    #[test]
+    fn should_keep_hand_written_changelog_sections() {
+        let existing =
+            "# Changelog\n\n## [Unreleased]\n- old\n\n## [1.0.0] - 2024-01-01\n- hand written\n";
+
+        let released = vec![
+            ReleaseSection::new("v1.1.0", ["b"], "## 1.1.0\n- b"),
+            ReleaseSection::new("v1.0.0", ["a"], "## 1.0.0\n- a"),
+        ];
+        let (updated, conflicts) = merge(Some(existing), None, &released, Some("[footer]"));
+
+        assert_eq!(1, conflicts.len());
+        assert_eq!(SectionConflictReason::Removed, conflicts[0].reason);
+        assert!(updated.starts_with("# Changelog\n\n## [Unreleased]\n- old\n\n<!-- doctavious"));
+        assert!(updated.contains("- hand written"));
+        assert!(!updated.contains("## 1.0.0"));
+        assert!(!updated.contains("[footer]"));
+    }
+
+    #[test]
+    fn should_keep_text_between_and_after_tracked_sections() {
+        let (initial, _) = merge(None, None, &sections(&["b"], &["a"]), Some("[footer]"));
+        let edited = initial
+            .replace(
+                "<!-- doctavious:release key=\"v1.0.0\"",
+                "Notes between releases\n\n<!-- doctavious:release key=\"v1.0.0\"",
+            )
+            .replace(
+                "<!-- doctavious:footer -->",
+                "Notes after the last release\n\n<!-- doctavious:footer -->",
+            );
+
+        let (updated, conflicts) = merge(
+            Some(&edited),
+            None,
+            &sections(&["b", "c"], &["a"]),
+            Some("[new footer]"),
+        );
+
+        assert!(conflicts.is_empty());
+        assert!(updated.contains("- b\n- c"));
+        let between = updated.find("Notes between releases").unwrap();
+        assert!(updated.find("- c").unwrap() < between);
+        assert!(between < updated.find("## 1.0.0").unwrap());
+        let after = updated.find("Notes after the last release").unwrap();
+        assert!(updated.find("## 1.0.0").unwrap() < after);
+        assert!(after < updated.find("[new footer]").unwrap());
+        assert!(!updated.contains("[footer]"));
+    }
+
+    #[test]
+    fn should_only_split_hand_written_changelog_on_release_headings() {
+        let existing = "# Changelog\n\n## [2.0.0] - 2024-02-01\n- hand written\n\n### Upgrading to 2.0.0\n- steps\n";
+
+        let released = vec![ReleaseSection::new("v2.0.0", ["a"], "## 2.0.0\n- a")];
+        let (updated, conflicts) = merge(Some(existing), None, &released, None);
+
+        assert!(conflicts.is_empty());
+        assert_eq!(existing, updated);
+    }
+}
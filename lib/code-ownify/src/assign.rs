@@ -0,0 +1,159 @@
+use std::collections::HashMap;
+
+use scm::platforms::ScmPlatformReviewers;
+use tracing::debug;
+
+/// Picks who to request reviews from out of the owners, or subscribers, of a pull request's files
+pub struct ReviewerSelection<'a> {
+    /// Author of the pull request, who can't review it
+    pub author: Option<&'a str>,
+
+    /// The most reviewers, users and teams combined, to request. `None` requests all owners
+    pub max_reviewers: Option<usize>,
+
+    /// Members of teams to take turns reviewing, with one member requested instead of the whole
+    /// team. Teams without members here are requested as a whole.
+    pub team_members: &'a HashMap<String, Vec<String>>,
+
+    /// Whose turn it is within teams, such as the pull request number, so that successive pull
+    /// requests rotate through each team's members
+    pub turn: u64,
+}
+
+impl ReviewerSelection<'_> {
+    /// Selects reviewers out of owners in CODEOWNERS form, `@user`, `@org/team` or an email.
+    /// Emails are skipped as platforms request reviews by username.
+    pub fn select(&self, owners: &[String]) -> ScmPlatformReviewers {
+        let mut reviewers = ScmPlatformReviewers::default();
+        for owner in owners {
+            if self
+                .max_reviewers
+                .is_some_and(|max| reviewers.users.len() + reviewers.teams.len() >= max)
+            {
+                debug!("Reached the maximum number of reviewers");
+                break;
+            }
+
+            let owner = owner.trim_start_matches('@');
+            if owner.contains('@') {
+                debug!("Skipping owner {owner} as reviews can't be requested by email");
+                continue;
+            }
+
+            if owner.contains('/') {
+                match self.team_members.get(owner) {
+                    Some(members) => match self.next_member(members, &reviewers.users) {
+                        Some(member) => reviewers.users.push(member.to_string()),
+                        None => debug!("No other members of team {owner} to request a review from"),
+                    },
+                    None if !contains(&reviewers.teams, owner) => {
+                        reviewers.teams.push(owner.to_string())
+                    }
+                    None => {}
+                }
+            } else if !self.is_author(owner) && !contains(&reviewers.users, owner) {
+                reviewers.users.push(owner.to_string());
+            }
+        }
+
+        reviewers
+    }
+
+    /// Member whose turn it is to review, out of those who aren't the author or already reviewers
+    fn next_member<'m>(&self, members: &'m [String], reviewers: &[String]) -> Option<&'m String> {
+        let eligible: Vec<&String> = members
+            .iter()
+            .filter(|m| !self.is_author(m) && !contains(reviewers, m))
+            .collect();
+        if eligible.is_empty() {
+            return None;
+        }
+
+        Some(eligible[(self.turn % eligible.len() as u64) as usize])
+    }
+
+    fn is_author(&self, user: &str) -> bool {
+        self.author
+            .is_some_and(|author| author.trim_start_matches('@').eq_ignore_ascii_case(user))
+    }
+}
+
+/// Whether a username is in the list, ignoring case as platforms do
+fn contains(usernames: &[String], username: &str) -> bool {
+    usernames.iter().any(|u| u.eq_ignore_ascii_case(username))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use scm::platforms::ScmPlatformReviewers;
+
+    use crate::assign::ReviewerSelection;
+
+    fn owners(owners: &[&str]) -> Vec<String> {
+        owners.iter().map(|o| o.to_string()).collect()
+    }
+
+    #[test]
+    fn should_skip_author_and_emails() {
+        let team_members = HashMap::new();
+        let selection = ReviewerSelection {
+            author: Some("Octocat"),
+            max_reviewers: None,
+            team_members: &team_members,
+            turn: 0,
+        };
+
+        assert_eq!(
+            ScmPlatformReviewers {
+                users: vec!["hubot".to_string()],
+                teams: vec!["doctavious/docs".to_string()],
+            },
+            selection.select(&owners(&[
+                "@octocat",
+                "@hubot",
+                "docs@doctavious.com",
+                "@doctavious/docs",
+                "@HUBOT",
+            ]))
+        );
+    }
+
+    #[test]
+    fn should_limit_reviewers() {
+        let team_members = HashMap::new();
+        let selection = ReviewerSelection {
+            author: None,
+            max_reviewers: Some(2),
+            team_members: &team_members,
+            turn: 0,
+        };
+
+        let reviewers = selection.select(&owners(&["@a", "@doctavious/docs", "@b"]));
+        assert_eq!(vec!["a".to_string()], reviewers.users);
+        assert_eq!(vec!["doctavious/docs".to_string()], reviewers.teams);
+    }
+
+    #[test]
+    fn should_take_turns_within_teams() {
+        let team_members = HashMap::from([(
+            "doctavious/docs".to_string(),
+            owners(&["octocat", "hubot", "monalisa"]),
+        )]);
+        let select = |turn: u64| {
+            ReviewerSelection {
+                author: Some("octocat"),
+                max_reviewers: None,
+                team_members: &team_members,
+                turn,
+            }
+            .select(&owners(&["@doctavious/docs"]))
+            .users
+        };
+
+        assert_eq!(vec!["hubot".to_string()], select(4));
+        assert_eq!(vec!["monalisa".to_string()], select(5));
+        assert_eq!(vec!["hubot".to_string()], select(6));
+    }
+}
@@ -1,3 +1,4 @@
+pub mod assign;
 pub mod notify;
 pub mod owners;
 mod parser;
@@ -11,7 +11,7 @@ use thiserror::Error;
 use tracing::{debug, info};
 
 use crate::parser;
-use crate::parser::pattern_to_regex;
+use crate::parser::PathPattern;
 
 #[remain::sorted]
 #[derive(Debug, Error)]
@@ -100,6 +100,20 @@ impl CodeNotify {
         Ok(notifications)
     }
 
+    /// Describes the commit range similar to git's `base...head` notation
+    fn diff_description(&self) -> String {
+        match &self.commit_range {
+            ScmCommitRange::Between { start, end } => format!(
+                "{}...{}",
+                start.revision,
+                end.as_ref()
+                    .map(|e| e.revision.to_string())
+                    .unwrap_or_default()
+            ),
+            range => range.to_string(),
+        }
+    }
+
     fn write_notifications(
         &self,
         notifications: &HashMap<String, Vec<String>>,
@@ -125,17 +139,7 @@ impl CodeNotify {
         let mut writer = Vec::new();
         match self.format.as_str() {
             "text" => {
-                writeln!(
-                    writer,
-                    "{}...{}",
-                    &self.commit_range.0,
-                    &self
-                        .commit_range
-                        .1
-                        .as_ref()
-                        .map(|s| s.to_string())
-                        .unwrap_or_default()
-                )?;
+                writeln!(writer, "{}", self.diff_description())?;
                 if subs.is_empty() {
                     write!(writer, "No notifications")?;
                 } else {
@@ -150,15 +154,9 @@ impl CodeNotify {
                 write!(writer, "{}", self.markdown_comment_title())?;
                 write!(
                     writer,
-                    "[CodeNotify](https://github.com/doctavious): Notifying subscribers in {} files for diff {}...{}.\n\n",
+                    "[CodeNotify](https://github.com/doctavious): Notifying subscribers in {} files for diff {}.\n\n",
                     &self.file_name,
-                    &self.commit_range.0,
-                    &self
-                        .commit_range
-                        .1
-                        .as_ref()
-                        .map(|s| s.to_string())
-                        .unwrap_or_default()
+                    self.diff_description()
                 )?;
                 if subs.is_empty() {
                     write!(writer, "No notifications")?;
@@ -201,8 +199,8 @@ impl CodeNotify {
                                 continue;
                             }
 
-                            let re = pattern_to_regex(&rule_pattern)?;
-                            if re.is_match(&relative.to_string_lossy()) {
+                            let pattern = PathPattern::relative(&rule_pattern)?;
+                            if pattern.is_match(&relative.to_string_lossy()) {
                                 subscribers.extend(pattern_subscribers);
                             }
                         }
@@ -226,7 +224,7 @@ mod tests {
     use std::path::PathBuf;
 
     use git2::Signature;
-    use scm::commit::ScmCommitRange;
+    use scm::commit::{ScmCommitRange, ScmRevision};
     use scm::drivers::git::GitScmRepository;
     use testing::guard::TempDirGuard;
 
@@ -255,7 +253,10 @@ mod tests {
             subscriber_threshold: 0,
             // TODO: avoid these clones..its a test so this instance doesn't matter but we want to
             // make the usage ergonomic
-            commit_range: ScmCommitRange(br.clone(), Some(hr.clone())),
+            commit_range: ScmCommitRange::new(
+                ScmRevision::Ref(br.clone()),
+                Some(ScmRevision::Ref(hr.clone())),
+            ),
             author: None,
         };
 
@@ -305,7 +306,10 @@ mod tests {
                 opts: Opts {
                     file_name: "CODENOTIFY".to_string(),
                     format: "markdown".to_string(),
-                    commit_range: ScmCommitRange("a".to_string(), Some("b".to_string())),
+                    commit_range: ScmCommitRange::new(
+                        ScmRevision::Ref("a".to_string()),
+                        Some(ScmRevision::Ref("b".to_string())),
+                    ),
                     subscriber_threshold: 0,
                     author: None,
                 },
@@ -323,7 +327,10 @@ mod tests {
                 opts: Opts {
                     file_name: "CODENOTIFY".to_string(),
                     format: "text".to_string(),
-                    commit_range: ScmCommitRange("a".to_string(), Some("b".to_string())),
+                    commit_range: ScmCommitRange::new(
+                        ScmRevision::Ref("a".to_string()),
+                        Some(ScmRevision::Ref("b".to_string())),
+                    ),
                     subscriber_threshold: 0,
                     author: None,
                 },
@@ -336,7 +343,10 @@ mod tests {
                 opts: Opts {
                     file_name: "CODENOTIFY".to_string(),
                     format: "markdown".to_string(),
-                    commit_range: ScmCommitRange("a".to_string(), Some("b".to_string())),
+                    commit_range: ScmCommitRange::new(
+                        ScmRevision::Ref("a".to_string()),
+                        Some(ScmRevision::Ref("b".to_string())),
+                    ),
                     subscriber_threshold: 0,
                     author: None,
                 },
@@ -367,7 +377,10 @@ mod tests {
                 opts: Opts {
                     file_name: "CODENOTIFY".to_string(),
                     format: "text".to_string(),
-                    commit_range: ScmCommitRange("a".to_string(), Some("b".to_string())),
+                    commit_range: ScmCommitRange::new(
+                        ScmRevision::Ref("a".to_string()),
+                        Some(ScmRevision::Ref("b".to_string())),
+                    ),
                     subscriber_threshold: 0,
                     author: None,
                 },
@@ -394,7 +407,10 @@ mod tests {
                 opts: Opts {
                     file_name: "CODENOTIFY".to_string(),
                     format: "pdf".to_string(),
-                    commit_range: ScmCommitRange("a".to_string(), Some("b".to_string())),
+                    commit_range: ScmCommitRange::new(
+                        ScmRevision::Ref("a".to_string()),
+                        Some(ScmRevision::Ref("b".to_string())),
+                    ),
                     subscriber_threshold: 0,
                     author: None,
                 },
@@ -410,7 +426,10 @@ mod tests {
                 opts: Opts {
                     file_name: "CODENOTIFY".to_string(),
                     format: "text".to_string(),
-                    commit_range: ScmCommitRange("a".to_string(), Some("b".to_string())),
+                    commit_range: ScmCommitRange::new(
+                        ScmRevision::Ref("a".to_string()),
+                        Some(ScmRevision::Ref("b".to_string())),
+                    ),
                     subscriber_threshold: 1,
                     author: None,
                 },
@@ -496,7 +515,10 @@ mod tests {
                 opts: Opts {
                     file_name: "CODENOTIFY".to_string(),
                     format: "markdown".to_string(),
-                    commit_range: ScmCommitRange("a".to_string(), Some("b".to_string())),
+                    commit_range: ScmCommitRange::new(
+                        ScmRevision::Ref("a".to_string()),
+                        Some(ScmRevision::Ref("b".to_string())),
+                    ),
                     subscriber_threshold: 0,
                     author: None,
                 },
@@ -513,7 +535,10 @@ mod tests {
                 opts: Opts {
                     file_name: "CODENOTIFY".to_string(),
                     format: "markdown".to_string(),
-                    commit_range: ScmCommitRange("a".to_string(), Some("b".to_string())),
+                    commit_range: ScmCommitRange::new(
+                        ScmRevision::Ref("a".to_string()),
+                        Some(ScmRevision::Ref("b".to_string())),
+                    ),
                     subscriber_threshold: 0,
                     author: None,
                 },
@@ -533,7 +558,10 @@ mod tests {
                 opts: Opts {
                     file_name: "CODENOTIFY".to_string(),
                     format: "markdown".to_string(),
-                    commit_range: ScmCommitRange("a".to_string(), Some("b".to_string())),
+                    commit_range: ScmCommitRange::new(
+                        ScmRevision::Ref("a".to_string()),
+                        Some(ScmRevision::Ref("b".to_string())),
+                    ),
                     subscriber_threshold: 0,
                     author: None,
                 },
@@ -550,7 +578,10 @@ mod tests {
                 opts: Opts {
                     file_name: "CODENOTIFY".to_string(),
                     format: "markdown".to_string(),
-                    commit_range: ScmCommitRange("a".to_string(), Some("b".to_string())),
+                    commit_range: ScmCommitRange::new(
+                        ScmRevision::Ref("a".to_string()),
+                        Some(ScmRevision::Ref("b".to_string())),
+                    ),
                     subscriber_threshold: 0,
                     author: None,
                 },
@@ -570,7 +601,10 @@ mod tests {
                 opts: Opts {
                     file_name: "CODENOTIFY".to_string(),
                     format: "markdown".to_string(),
-                    commit_range: ScmCommitRange("a".to_string(), Some("b".to_string())),
+                    commit_range: ScmCommitRange::new(
+                        ScmRevision::Ref("a".to_string()),
+                        Some(ScmRevision::Ref("b".to_string())),
+                    ),
                     subscriber_threshold: 0,
                     author: None,
                 },
@@ -590,7 +624,10 @@ mod tests {
                 opts: Opts {
                     file_name: "CODENOTIFY".to_string(),
                     format: "markdown".to_string(),
-                    commit_range: ScmCommitRange("a".to_string(), Some("b".to_string())),
+                    commit_range: ScmCommitRange::new(
+                        ScmRevision::Ref("a".to_string()),
+                        Some(ScmRevision::Ref("b".to_string())),
+                    ),
                     subscriber_threshold: 0,
                     author: None,
                 },
@@ -610,7 +647,10 @@ mod tests {
                 opts: Opts {
                     file_name: "CODENOTIFY".to_string(),
                     format: "markdown".to_string(),
-                    commit_range: ScmCommitRange("a".to_string(), Some("b".to_string())),
+                    commit_range: ScmCommitRange::new(
+                        ScmRevision::Ref("a".to_string()),
+                        Some(ScmRevision::Ref("b".to_string())),
+                    ),
                     subscriber_threshold: 0,
                     author: None,
                 },
@@ -630,7 +670,10 @@ mod tests {
                 opts: Opts {
                     file_name: "CODENOTIFY".to_string(),
                     format: "markdown".to_string(),
-                    commit_range: ScmCommitRange("a".to_string(), Some("b".to_string())),
+                    commit_range: ScmCommitRange::new(
+                        ScmRevision::Ref("a".to_string()),
+                        Some(ScmRevision::Ref("b".to_string())),
+                    ),
                     subscriber_threshold: 0,
                     author: None,
                 },
@@ -656,7 +699,10 @@ mod tests {
                 opts: Opts {
                     file_name: "CODENOTIFY".to_string(),
                     format: "markdown".to_string(),
-                    commit_range: ScmCommitRange("a".to_string(), Some("b".to_string())),
+                    commit_range: ScmCommitRange::new(
+                        ScmRevision::Ref("a".to_string()),
+                        Some(ScmRevision::Ref("b".to_string())),
+                    ),
                     subscriber_threshold: 0,
                     author: None,
                 },
@@ -681,7 +727,10 @@ mod tests {
                 opts: Opts {
                     file_name: "CODENOTIFY".to_string(),
                     format: "markdown".to_string(),
-                    commit_range: ScmCommitRange("a".to_string(), Some("b".to_string())),
+                    commit_range: ScmCommitRange::new(
+                        ScmRevision::Ref("a".to_string()),
+                        Some(ScmRevision::Ref("b".to_string())),
+                    ),
                     subscriber_threshold: 0,
                     author: None,
                 },
@@ -705,7 +754,10 @@ mod tests {
                 opts: Opts {
                     file_name: "CODENOTIFY".to_string(),
                     format: "markdown".to_string(),
-                    commit_range: ScmCommitRange("a".to_string(), Some("b".to_string())),
+                    commit_range: ScmCommitRange::new(
+                        ScmRevision::Ref("a".to_string()),
+                        Some(ScmRevision::Ref("b".to_string())),
+                    ),
                     subscriber_threshold: 0,
                     author: None,
                 },
@@ -726,7 +778,10 @@ mod tests {
                 opts: Opts {
                     file_name: "CODENOTIFY".to_string(),
                     format: "markdown".to_string(),
-                    commit_range: ScmCommitRange("a".to_string(), Some("b".to_string())),
+                    commit_range: ScmCommitRange::new(
+                        ScmRevision::Ref("a".to_string()),
+                        Some(ScmRevision::Ref("b".to_string())),
+                    ),
                     subscriber_threshold: 0,
                     author: None,
                 },
@@ -746,7 +801,10 @@ mod tests {
                 opts: Opts {
                     file_name: "CODENOTIFY".to_string(),
                     format: "markdown".to_string(),
-                    commit_range: ScmCommitRange("a".to_string(), Some("b".to_string())),
+                    commit_range: ScmCommitRange::new(
+                        ScmRevision::Ref("a".to_string()),
+                        Some(ScmRevision::Ref("b".to_string())),
+                    ),
                     subscriber_threshold: 0,
                     author: None,
                 },
@@ -767,7 +825,10 @@ mod tests {
                 opts: Opts {
                     file_name: "CODENOTIFY".to_string(),
                     format: "markdown".to_string(),
-                    commit_range: ScmCommitRange("a".to_string(), Some("b".to_string())),
+                    commit_range: ScmCommitRange::new(
+                        ScmRevision::Ref("a".to_string()),
+                        Some(ScmRevision::Ref("b".to_string())),
+                    ),
                     subscriber_threshold: 0,
                     author: None,
                 },
@@ -788,7 +849,10 @@ mod tests {
                 opts: Opts {
                     file_name: "CODENOTIFY".to_string(),
                     format: "markdown".to_string(),
-                    commit_range: ScmCommitRange("a".to_string(), Some("b".to_string())),
+                    commit_range: ScmCommitRange::new(
+                        ScmRevision::Ref("a".to_string()),
+                        Some(ScmRevision::Ref("b".to_string())),
+                    ),
                     subscriber_threshold: 0,
                     author: None,
                 },
@@ -800,7 +864,10 @@ mod tests {
                 opts: Opts {
                     file_name: "CODENOTIFY".to_string(),
                     format: "markdown".to_string(),
-                    commit_range: ScmCommitRange("a".to_string(), Some("b".to_string())),
+                    commit_range: ScmCommitRange::new(
+                        ScmRevision::Ref("a".to_string()),
+                        Some(ScmRevision::Ref("b".to_string())),
+                    ),
                     subscriber_threshold: 0,
                     author: None,
                 },
@@ -945,7 +1012,10 @@ mod tests {
                 opts: Opts {
                     file_name: "OWNERS".to_string(),
                     format: "markdown".to_string(),
-                    commit_range: ScmCommitRange("a".to_string(), Some("b".to_string())),
+                    commit_range: ScmCommitRange::new(
+                        ScmRevision::Ref("a".to_string()),
+                        Some(ScmRevision::Ref("b".to_string())),
+                    ),
                     subscriber_threshold: 0,
                     author: None,
                 },
@@ -963,7 +1033,10 @@ mod tests {
                 opts: Opts {
                     file_name: "OWNERS".to_string(),
                     format: "markdown".to_string(),
-                    commit_range: ScmCommitRange("a".to_string(), Some("b".to_string())),
+                    commit_range: ScmCommitRange::new(
+                        ScmRevision::Ref("a".to_string()),
+                        Some(ScmRevision::Ref("b".to_string())),
+                    ),
                     subscriber_threshold: 0,
                     author: None,
                 },
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::ffi::OsStr;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::string::FromUtf8Error;
@@ -6,9 +6,10 @@ use std::{fs, io};
 
 use scm::platforms::ScmPlatform;
 use thiserror::Error;
-use tracing::info;
+use tracing::debug;
 
 use crate::parser;
+use crate::parser::PathPattern;
 
 const CODEOWNERS: &'static str = "CODEOWNERS";
 
@@ -18,51 +19,149 @@ pub enum CodeOwnersError {
     #[error(transparent)]
     FromUtf8Error(#[from] FromUtf8Error),
 
+    #[error("invalid pattern on line {0}: {1}")]
+    InvalidPattern(usize, regex::Error),
+
     #[error(transparent)]
     IoError(#[from] io::Error),
-
-    #[error("regex error: {0}")]
-    RegexError(#[from] regex::Error),
 }
 
 pub type CodeOwnersResult<T> = Result<T, CodeOwnersError>;
 
+/// Flavour of CODEOWNERS syntax. Both follow gitignore patterns with the owners of a file being
+/// those of the last pattern matching it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CodeOwnersDialect {
+    GitHub,
+    /// Adds `[Section]` headers, each section having its own last matching pattern, along with
+    /// `^[Optional]` sections, `[Section][2]` approval counts and section default owners
+    GitLab,
+}
+
+impl CodeOwnersDialect {
+    /// Dialect of the platform directory the file is in, otherwise GitLab's when the file has
+    /// section headers
+    fn detect(location: &Path, contents: &str) -> Self {
+        match location
+            .parent()
+            .and_then(Path::file_name)
+            .and_then(OsStr::to_str)
+        {
+            Some(".github") => CodeOwnersDialect::GitHub,
+            Some(".gitlab") => CodeOwnersDialect::GitLab,
+            _ if contents
+                .lines()
+                .any(|l| parser::parse_section_header(l).is_some()) =>
+            {
+                CodeOwnersDialect::GitLab
+            }
+            _ => CodeOwnersDialect::GitHub,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct CodeOwnersRule {
+    /// The pattern with any escaped spaces unescaped
+    pub pattern: String,
+
+    /// Owners of the matching files. Empty when the files have no owners or, in GitLab
+    /// sections, are owned by the section's default owners
+    pub owners: Vec<String>,
+
+    /// Line of the rule in the file, starting at 1
+    pub line: usize,
+
+    matcher: PathPattern,
+}
+
+impl CodeOwnersRule {
+    /// Whether the rule matches a path relative to the repository root
+    pub fn matches(&self, path: &Path) -> bool {
+        self.matcher.is_match(&path.to_string_lossy())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct CodeOwnersSection {
+    /// `None` for the rules before the first section header, which are all of GitHub's
+    pub name: Option<String>,
+
+    /// Whether the owners' approval is optional, i.e., a `^[Section]`
+    pub optional: bool,
+
+    /// Number of approvals required from the section's owners, zero when optional
+    pub approvals_required: u64,
+
+    /// Owners of the section's rules which don't list any
+    pub default_owners: Vec<String>,
+
+    /// Line of the section header, starting at 1
+    pub line: Option<usize>,
+
+    /// Rules in file order. Sections with the same name are combined
+    pub rules: Vec<CodeOwnersRule>,
+}
+
+impl CodeOwnersSection {
+    fn default_section() -> Self {
+        Self {
+            name: None,
+            optional: false,
+            approvals_required: 1,
+            default_owners: Vec::new(),
+            line: None,
+            rules: Vec::new(),
+        }
+    }
+
+    /// Owners of a rule of the section, falling back to the section's default owners
+    pub fn owners<'a>(&'a self, rule: &'a CodeOwnersRule) -> &'a [String] {
+        if rule.owners.is_empty() {
+            &self.default_owners
+        } else {
+            &rule.owners
+        }
+    }
+}
+
 pub struct CodeOwners {
     location: PathBuf,
-    owners: HashMap<String, Vec<String>>,
+    dialect: CodeOwnersDialect,
+    sections: Vec<CodeOwnersSection>,
 }
 
 impl CodeOwners {
+    /// Parses a CODEOWNERS file, detecting its dialect
     pub fn new(location: PathBuf) -> CodeOwnersResult<CodeOwners> {
-        Ok(Self {
-            owners: CodeOwners::parse(&location)?,
-            location,
-        })
+        let contents = fs::read_to_string(&location)?;
+        let dialect = CodeOwnersDialect::detect(&location, &contents);
+        Self::parse(location, &contents, dialect)
+    }
+
+    pub fn with_dialect(
+        location: PathBuf,
+        dialect: CodeOwnersDialect,
+    ) -> CodeOwnersResult<CodeOwners> {
+        let contents = fs::read_to_string(&location)?;
+        Self::parse(location, &contents, dialect)
     }
 
     pub fn discover(root: PathBuf) -> CodeOwnersResult<Option<CodeOwners>> {
         let root_codeowners = root.join(CODEOWNERS);
         if root_codeowners.exists() {
-            return Ok(Some(Self {
-                owners: CodeOwners::parse(&root_codeowners)?,
-                location: root_codeowners,
-            }));
+            return Ok(Some(Self::new(root_codeowners)?));
         }
 
         let docs_codeowners = root.join("docs").join(CODEOWNERS);
         if docs_codeowners.exists() {
-            return Ok(Some(Self {
-                owners: CodeOwners::parse(&docs_codeowners)?,
-                location: docs_codeowners,
-            }));
+            return Ok(Some(Self::new(docs_codeowners)?));
         }
 
         for dot_directory in ScmPlatform::dot_directories() {
-            if dot_directory.exists() {
-                return Ok(Some(Self {
-                    owners: CodeOwners::parse(&dot_directory)?,
-                    location: dot_directory,
-                }));
+            let dot_codeowners = root.join(dot_directory).join(CODEOWNERS);
+            if dot_codeowners.exists() {
+                return Ok(Some(Self::new(dot_codeowners)?));
             }
         }
 
@@ -73,39 +172,171 @@ impl CodeOwners {
         self.location.as_path()
     }
 
-    pub fn owners(&self) -> &HashMap<String, Vec<String>> {
-        &self.owners
+    pub fn dialect(&self) -> CodeOwnersDialect {
+        self.dialect
+    }
+
+    pub fn sections(&self) -> &[CodeOwnersSection] {
+        &self.sections
+    }
+
+    /// Rules of all sections in file order
+    pub fn rules(&self) -> Vec<&CodeOwnersRule> {
+        let mut rules: Vec<&CodeOwnersRule> =
+            self.sections.iter().flat_map(|s| s.rules.iter()).collect();
+        rules.sort_by_key(|r| r.line);
+        rules
+    }
+
+    /// The rule that applies to a path relative to the repository root, the last one matching
+    /// it, of each section
+    pub fn matching_rules(&self, path: &Path) -> Vec<(&CodeOwnersSection, &CodeOwnersRule)> {
+        self.sections
+            .iter()
+            .filter_map(|section| {
+                section
+                    .rules
+                    .iter()
+                    .rev()
+                    .find(|rule| rule.matches(path))
+                    .map(|rule| (section, rule))
+            })
+            .collect()
+    }
+
+    /// Owners of a path relative to the repository root
+    pub fn owners_for(&self, path: &Path) -> Vec<String> {
+        let mut owners: Vec<String> = Vec::new();
+        for (section, rule) in self.matching_rules(path) {
+            for owner in section.owners(rule) {
+                if !owners.contains(owner) {
+                    owners.push(owner.clone());
+                }
+            }
+        }
+
+        owners
+    }
+
+    /// Owners of any of the paths, such as those of a pull request's diff, in the order of the
+    /// paths they own
+    pub fn owners_of(&self, paths: &[PathBuf]) -> Vec<String> {
+        let mut owners: Vec<String> = Vec::new();
+        for path in paths {
+            for owner in self.owners_for(path) {
+                if !owners.contains(&owner) {
+                    owners.push(owner);
+                }
+            }
+        }
+
+        owners
     }
 
-    fn parse(path: &Path) -> CodeOwnersResult<HashMap<String, Vec<String>>> {
-        let mut owners = HashMap::new();
-        for line in fs::read_to_string(path)?.lines() {
-            if let Some((rule_pattern, pattern_owners)) = parser::parse_line(line) {
-                if pattern_owners.is_empty() {
-                    info!(
-                        "expected subscribers for rule in {}: {}",
-                        path.to_string_lossy(),
+    fn parse(
+        location: PathBuf,
+        contents: &str,
+        dialect: CodeOwnersDialect,
+    ) -> CodeOwnersResult<CodeOwners> {
+        let mut sections = vec![CodeOwnersSection::default_section()];
+        let mut current = 0;
+        for (index, line) in contents.lines().enumerate() {
+            let line_number = index + 1;
+            if dialect == CodeOwnersDialect::GitLab
+                && let Some(header) = parser::parse_section_header(line)
+            {
+                // like GitLab, combine sections with the same name regardless of case
+                let existing = sections.iter().position(|s| {
+                    s.name
+                        .as_ref()
+                        .is_some_and(|name| name.eq_ignore_ascii_case(&header.name))
+                });
+                current = match existing {
+                    Some(existing) => existing,
+                    None => {
+                        sections.push(CodeOwnersSection {
+                            name: Some(header.name),
+                            optional: header.optional,
+                            approvals_required: if header.optional {
+                                0
+                            } else {
+                                header.approvals_required.unwrap_or(1)
+                            },
+                            default_owners: header.default_owners,
+                            line: Some(line_number),
+                            rules: Vec::new(),
+                        });
+                        sections.len() - 1
+                    }
+                };
+                continue;
+            }
+
+            if let Some((pattern, owners)) = parser::parse_line(line) {
+                if owners.is_empty() && sections[current].default_owners.is_empty() {
+                    debug!(
+                        "files matching rule on line {} of {} have no owners: {}",
+                        line_number,
+                        location.to_string_lossy(),
                         line
                     );
-                    continue;
                 }
 
-                owners.insert(rule_pattern.to_string(), pattern_owners.to_vec());
+                let matcher = PathPattern::gitignore(&pattern)
+                    .map_err(|e| CodeOwnersError::InvalidPattern(line_number, e))?;
+                sections[current].rules.push(CodeOwnersRule {
+                    pattern,
+                    owners,
+                    line: line_number,
+                    matcher,
+                });
             }
         }
 
-        Ok(owners)
+        // the default section is only needed when it has rules
+        sections.retain(|s| s.name.is_some() || !s.rules.is_empty());
+
+        Ok(Self {
+            location,
+            dialect,
+            sections,
+        })
     }
 
     pub fn render(&self, format: &'static str) -> CodeOwnersResult<String> {
         let mut writer = Vec::<u8>::new();
-        let owners = self.owners();
+        let mut rules: Vec<(&CodeOwnersSection, &CodeOwnersRule)> = self
+            .sections
+            .iter()
+            .flat_map(|s| s.rules.iter().map(move |r| (s, r)))
+            .collect();
+        rules.sort_by_key(|(_, r)| r.line);
+
+        let has_sections = self.sections.iter().any(|s| s.name.is_some());
         match format {
+            "markdown" if has_sections => {
+                writeln!(writer, "| Section | File(s) | Owners |")?;
+                writeln!(writer, "|-|-|-|")?;
+                for (section, rule) in rules {
+                    writeln!(
+                        writer,
+                        "| {} | {} | {} |",
+                        section.name.as_deref().unwrap_or_default(),
+                        rule.pattern,
+                        section.owners(rule).join("<br>")
+                    )?;
+                }
+            }
             "markdown" => {
                 writeln!(writer, "| File(s) | Owners |")?;
                 writeln!(writer, "|-|-|")?;
-                for (pattern, code_owners) in owners {
-                    writeln!(writer, "| {} | {} |", pattern, code_owners.join("<br>"))?;
+                for (section, rule) in rules {
+                    writeln!(
+                        writer,
+                        "| {} | {} |",
+                        rule.pattern,
+                        section.owners(rule).join("<br>")
+                    )?;
                 }
             }
             _ => {}
@@ -117,12 +348,25 @@ impl CodeOwners {
 
 #[cfg(test)]
 mod tests {
-    use std::collections::HashMap;
     use std::fs;
+    use std::path::{Path, PathBuf};
 
     use testing::guard::TempDirGuard;
 
-    use crate::owners::CodeOwners;
+    use crate::owners::{CodeOwners, CodeOwnersDialect};
+
+    fn rules(code_owners: &CodeOwners) -> Vec<(&str, Vec<&str>)> {
+        code_owners
+            .rules()
+            .into_iter()
+            .map(|r| {
+                (
+                    r.pattern.as_str(),
+                    r.owners.iter().map(String::as_str).collect(),
+                )
+            })
+            .collect()
+    }
 
     #[test]
     fn new() {
@@ -133,11 +377,7 @@ mod tests {
         let code_owners =
             CodeOwners::new(temp_dir.join("CODEOWNERS")).expect("Should have found CODEOWNER file");
 
-        let owners = code_owners.owners();
-        assert_eq!(
-            &HashMap::from([("**/*.md".to_string(), vec!["@markdown".to_string()])]),
-            owners
-        );
+        assert_eq!(vec![("**/*.md", vec!["@markdown"])], rules(&code_owners));
     }
 
     #[test]
@@ -150,12 +390,7 @@ mod tests {
             .expect("Should have found CODEOWNER file")
             .unwrap();
 
-        let owners = code_owners.owners();
-
-        assert_eq!(
-            &HashMap::from([("**/*.md".to_string(), vec!["@markdown".to_string()])]),
-            owners
-        );
+        assert_eq!(vec![("**/*.md", vec!["@markdown"])], rules(&code_owners));
     }
 
     #[test]
@@ -171,12 +406,7 @@ mod tests {
             .expect("Should have found CODEOWNER file")
             .unwrap();
 
-        let owners = code_owners.owners();
-
-        assert_eq!(
-            &HashMap::from([("**/*.md".to_string(), vec!["@markdown".to_string()])]),
-            owners
-        );
+        assert_eq!(vec![("**/*.md", vec!["@markdown"])], rules(&code_owners));
     }
 
     #[test]
@@ -188,16 +418,160 @@ mod tests {
         fs::write(dot_dir.join("CODEOWNERS"), "**/*.md @markdown").unwrap();
         fs::write(dot_dir.join("file.md"), "").unwrap();
 
-        let code_owners = CodeOwners::discover(dot_dir)
+        let code_owners = CodeOwners::discover(temp_dir)
             .expect("Should have found CODEOWNER file")
             .unwrap();
 
-        let owners = code_owners.owners();
+        assert_eq!(vec![("**/*.md", vec!["@markdown"])], rules(&code_owners));
+    }
+
+    #[test]
+    fn owners_of() {
+        let (temp_dir, _tempdir_guard) = TempDirGuard::new().unwrap();
+
+        fs::write(
+            temp_dir.join("CODEOWNERS"),
+            "**/*.md @markdown\n/src/ @rust @doctavious/core",
+        )
+        .unwrap();
+
+        let code_owners = CodeOwners::new(temp_dir.join("CODEOWNERS")).unwrap();
+
+        assert_eq!(
+            vec!["@rust", "@doctavious/core", "@markdown"],
+            code_owners.owners_of(&[
+                PathBuf::from("src/lib.rs"),
+                PathBuf::from("docs/README.md"),
+                PathBuf::from("Cargo.toml"),
+            ])
+        );
+    }
+
+    #[test]
+    fn last_matching_rule_wins() {
+        let (temp_dir, _tempdir_guard) = TempDirGuard::new().unwrap();
+
+        fs::write(
+            temp_dir.join("CODEOWNERS"),
+            "# global owners\n* @global\n\n/docs/ @docs\n/docs/api/\n*.rs @rust # rust files\n",
+        )
+        .unwrap();
 
+        let code_owners = CodeOwners::new(temp_dir.join("CODEOWNERS")).unwrap();
+        assert_eq!(CodeOwnersDialect::GitHub, code_owners.dialect());
+        assert_eq!(
+            vec!["@docs"],
+            code_owners.owners_for(Path::new("docs/guide.md"))
+        );
+        assert_eq!(
+            Vec::<String>::new(),
+            code_owners.owners_for(Path::new("docs/api/index.md"))
+        );
+        assert_eq!(
+            vec!["@rust"],
+            code_owners.owners_for(Path::new("docs/api/lib.rs"))
+        );
         assert_eq!(
-            &HashMap::from([("**/*.md".to_string(), vec!["@markdown".to_string()])]),
-            owners
+            vec!["@global"],
+            code_owners.owners_for(Path::new("Cargo.toml"))
         );
+
+        let matching = code_owners.matching_rules(Path::new("docs/api/index.md"));
+        assert_eq!(
+            vec![5],
+            matching.iter().map(|(_, r)| r.line).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn gitlab_sections() {
+        let (temp_dir, _tempdir_guard) = TempDirGuard::new().unwrap();
+
+        fs::write(
+            temp_dir.join("CODEOWNERS"),
+            [
+                "* @default",
+                "[Docs][2] @docs-team",
+                "docs/",
+                "README.md @readme",
+                "^[Optional]",
+                "*.rs @rustaceans",
+                "[docs]",
+                "/docs/guides/ @guides",
+                r"/docs/my\ guide.md @mine",
+            ]
+            .join("\n"),
+        )
+        .unwrap();
+
+        let code_owners = CodeOwners::new(temp_dir.join("CODEOWNERS")).unwrap();
+        assert_eq!(CodeOwnersDialect::GitLab, code_owners.dialect());
+
+        let sections: Vec<(Option<&str>, bool, u64, Option<usize>)> = code_owners
+            .sections()
+            .iter()
+            .map(|s| (s.name.as_deref(), s.optional, s.approvals_required, s.line))
+            .collect();
+        assert_eq!(
+            vec![
+                (None, false, 1, None),
+                (Some("Docs"), false, 2, Some(2)),
+                (Some("Optional"), true, 0, Some(5)),
+            ],
+            sections
+        );
+
+        assert_eq!(
+            vec!["@default", "@docs-team"],
+            code_owners.owners_for(Path::new("docs/index.md"))
+        );
+        assert_eq!(
+            vec!["@default", "@guides"],
+            code_owners.owners_for(Path::new("docs/guides/start.md"))
+        );
+        assert_eq!(
+            vec!["@default", "@mine"],
+            code_owners.owners_for(Path::new("docs/my guide.md"))
+        );
+        assert_eq!(
+            vec!["@default", "@docs-team", "@rustaceans"],
+            code_owners.owners_for(Path::new("docs/lib.rs"))
+        );
+        assert_eq!(
+            vec!["@default", "@readme"],
+            code_owners.owners_for(Path::new("README.md"))
+        );
+    }
+
+    #[test]
+    fn github_treats_brackets_as_patterns() {
+        let (temp_dir, _tempdir_guard) = TempDirGuard::new().unwrap();
+
+        let dot_dir = temp_dir.join(".github");
+        fs::create_dir_all(&dot_dir).unwrap();
+        fs::write(dot_dir.join("CODEOWNERS"), "[Docs] @docs\n").unwrap();
+
+        let code_owners = CodeOwners::discover(temp_dir).unwrap().unwrap();
+        assert_eq!(CodeOwnersDialect::GitHub, code_owners.dialect());
+        assert_eq!(vec![("[Docs]", vec!["@docs"])], rules(&code_owners));
+    }
+
+    #[test]
+    fn render_markdown_in_file_order() {
+        let (temp_dir, _tempdir_guard) = TempDirGuard::new().unwrap();
+
+        fs::write(
+            temp_dir.join("CODEOWNERS"),
+            "[Rust] @rust\n*.rs\n[Docs]\n*.md @docs\n[rust]\nCargo.toml @cargo\n",
+        )
+        .unwrap();
+
+        let code_owners = CodeOwners::new(temp_dir.join("CODEOWNERS")).unwrap();
+
+        assert_eq!(
+            "| Section | File(s) | Owners |\n|-|-|-|\n| Rust | *.rs | @rust |\n| Docs | *.md | @docs |\n| Rust | Cargo.toml | @cargo |\n",
+            code_owners.render("markdown").unwrap()
+        )
     }
 
     #[test]
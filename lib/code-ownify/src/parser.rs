@@ -1,21 +1,15 @@
 use regex::Regex;
 
+/// Parses a rule line of a CODEOWNERS or CODENOTIFY file into its pattern and owners, or
+/// subscribers. Spaces escaped with a backslash are part of the pattern and everything from an
+/// unescaped `#` is a comment.
 pub(crate) fn parse_line(line: &str) -> Option<(String, Vec<String>)> {
     let trimmed_line = line.trim();
     if trimmed_line.is_empty() || trimmed_line.starts_with("#") {
         return None;
     }
 
-    let fields: Vec<String> = line.split_whitespace().map(str::to_string).collect();
-    // if fields.len() == 1 {
-    //     info!(
-    //         "expected at least two fields for rule in {}: {}",
-    //         file.to_string_lossy(),
-    //         line
-    //     );
-    //     continue;
-    // }
-
+    let fields = split_fields(trimmed_line);
     if let Some((rule_pattern, rest)) = fields.split_first() {
         Some((rule_pattern.to_owned(), rest.to_vec()))
     } else {
@@ -23,27 +17,216 @@ pub(crate) fn parse_line(line: &str) -> Option<(String, Vec<String>)> {
     }
 }
 
-/// Transform CODEOWNER and CODENOTIFY patterns to regex
-/// CODENOTIFY files mimic the structure of CODEOWNER files so this is a shared function.
-/// If we wanted to have the rules diverge at any point each crate would have their own parsing function
-pub(crate) fn pattern_to_regex(pattern: &str) -> Result<Regex, regex::Error> {
-    let mut pattern = pattern.to_string();
+/// Header of a GitLab CODEOWNERS section, e.g., `^[Section name][2] @default-owner`
+pub(crate) struct SectionHeader {
+    pub name: String,
+    pub optional: bool,
+    pub approvals_required: Option<u64>,
+    pub default_owners: Vec<String>,
+}
+
+pub(crate) fn parse_section_header(line: &str) -> Option<SectionHeader> {
+    let trimmed_line = line.trim();
+    let (optional, rest) = match trimmed_line.strip_prefix('^') {
+        Some(rest) => (true, rest),
+        None => (false, trimmed_line),
+    };
+
+    let (name, rest) = rest.strip_prefix('[')?.split_once(']')?;
+    if name.trim().is_empty() {
+        return None;
+    }
+
+    let (approvals_required, rest) = match rest.strip_prefix('[') {
+        Some(count) => {
+            let (count, rest) = count.split_once(']')?;
+            (Some(count.trim().parse().ok()?), rest)
+        }
+        None => (None, rest),
+    };
+
+    // anything else directly after the brackets makes it a pattern, e.g., `[abc].md`
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+
+    Some(SectionHeader {
+        name: name.trim().to_string(),
+        optional,
+        approvals_required,
+        default_owners: split_fields(rest),
+    })
+}
+
+/// Splits on unescaped whitespace, stopping at an unescaped `#`
+fn split_fields(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some(escaped @ (' ' | '#')) => field.push(escaped),
+                Some(escaped) => {
+                    field.push(c);
+                    field.push(escaped);
+                }
+                None => field.push(c),
+            },
+            '#' if field.is_empty() => break,
+            c if c.is_whitespace() => {
+                if !field.is_empty() {
+                    fields.push(std::mem::take(&mut field));
+                }
+            }
+            c => field.push(c),
+        }
+    }
+
+    if !field.is_empty() {
+        fields.push(field);
+    }
+
+    fields
+}
+
+/// Compiled pattern of a CODEOWNERS or CODENOTIFY rule.
+/// CODENOTIFY files mimic the structure of CODEOWNER files so this is a shared matcher.
+#[derive(Clone, Debug)]
+pub(crate) struct PathPattern {
+    regex: Regex,
+}
+
+impl PathPattern {
+    /// Pattern of a CODENOTIFY file which matches paths relative to the file's directory from
+    /// start to end, i.e., `*.md` only matches files of that directory
+    pub(crate) fn relative(pattern: &str) -> Result<Self, regex::Error> {
+        let mut pattern = pattern.to_string();
+
+        // If the pattern ends with '/', append '**'
+        if pattern.ends_with('/') {
+            pattern.push_str("**");
+        }
+
+        Ok(Self {
+            regex: Regex::new(&format!("^{}$", glob_to_regex(&pattern)))?,
+        })
+    }
+
+    /// Pattern of a CODEOWNERS file, which follows gitignore rules:
+    /// - a pattern without a `/`, other than a trailing one, matches at any depth
+    /// - a pattern with a leading or middle `/` is relative to the repository root
+    /// - a pattern ending with `/` only matches directories and everything within them
+    /// - a pattern whose last segment has no wildcards also matches everything within the
+    ///   directory it names, e.g., `/docs`, while `docs/*` doesn't match nested files
+    pub(crate) fn gitignore(pattern: &str) -> Result<Self, regex::Error> {
+        let directory = pattern.ends_with('/');
+        let trimmed = pattern.trim_end_matches('/');
+        let anchored = trimmed.contains('/');
+        let trimmed = trimmed.trim_start_matches('/');
+
+        let prefix = if anchored { "^" } else { "^(?:.*/)?" };
+        let last_segment = trimmed.rsplit('/').next().unwrap_or_default();
+        let suffix = if directory {
+            "/.*$"
+        } else if last_segment.contains(['*', '?']) {
+            "$"
+        } else {
+            "(?:/.*)?$"
+        };
+
+        Ok(Self {
+            regex: Regex::new(&format!("{prefix}{}{suffix}", glob_to_regex(trimmed)))?,
+        })
+    }
+
+    /// Whether a `/` separated path, relative to the root of the pattern, matches
+    pub(crate) fn is_match(&self, path: &str) -> bool {
+        self.regex.is_match(path)
+    }
+}
+
+/// Transforms a glob to an unanchored regex, `*` and `?` matching within a path segment and a
+/// `**` segment matching any number of directories
+fn glob_to_regex(pattern: &str) -> String {
+    let segments: Vec<&str> = pattern.split('/').collect();
+    let mut regex = String::new();
+    for (i, segment) in segments.iter().enumerate() {
+        let last = i == segments.len() - 1;
+        if *segment == "**" {
+            regex.push_str(if last { ".*" } else { "(?:[^/]+/)*" });
+            continue;
+        }
+
+        for c in segment.chars() {
+            match c {
+                '*' => regex.push_str("[^/]*"),
+                '?' => regex.push_str("[^/]"),
+                c => regex.push_str(&regex::escape(&c.to_string())),
+            }
+        }
+
+        if !last {
+            regex.push('/');
+        }
+    }
+
+    regex
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::{PathPattern, parse_line, parse_section_header};
 
-    // If the pattern ends with '/', append '**'
-    if pattern.ends_with('/') {
-        pattern.push_str("**");
+    #[test]
+    fn should_parse_escaped_spaces_and_comments() {
+        assert_eq!(
+            Some((
+                "docs/my file.md".to_string(),
+                vec!["@a".to_string(), "@b".to_string()]
+            )),
+            parse_line(r"docs/my\ file.md  @a @b # docs owners")
+        );
+        assert_eq!(None, parse_line("  # comment"));
     }
 
-    let pattern = regex::escape(&pattern);
-    let pattern = pattern
-        .replace(r"/\*\*/", r"/([^/]*/)*")
-        .replace(r"\*\*/", r"([^/]+/)*")
-        .replace(r"/\*\*", r".*")
-        .replace(r"\*\*", r".*")
-        .replace(r"\*", r"[^/]*");
+    #[test]
+    fn should_parse_section_headers() {
+        let header = parse_section_header("^[Documentation team][2] @docs").unwrap();
+        assert_eq!("Documentation team", header.name);
+        assert!(header.optional);
+        assert_eq!(Some(2), header.approvals_required);
+        assert_eq!(vec!["@docs".to_string()], header.default_owners);
 
-    // Add regex anchors
-    let pattern = format!("^{}$", pattern);
+        assert!(parse_section_header("[abc].md @a").is_none());
+        assert!(parse_section_header("*.md @a").is_none());
+    }
 
-    Ok(Regex::new(&pattern)?)
+    #[test]
+    fn should_match_gitignore_patterns() {
+        let cases = [
+            ("*", "a/b/c.rs", true),
+            ("*.js", "src/app.js", true),
+            ("/build/logs/", "build/logs/a/b.log", true),
+            ("/build/logs/", "build/logs", false),
+            ("docs/*", "docs/getting-started.md", true),
+            ("docs/*", "docs/build-app/troubleshooting.md", false),
+            ("docs/*", "src/docs/a.md", false),
+            ("apps/", "nested/apps/a.rs", true),
+            ("/docs", "docs/a/b.md", true),
+            ("**/logs", "deeply/nested/logs/a.log", true),
+            ("/scripts/**", "scripts/a/b.sh", true),
+            ("my file.md", "docs/my file.md", true),
+            ("lib/*.rs", "lib/a.rs", true),
+            ("lib/*.rs", "lib/nested/a.rs", false),
+        ];
+
+        for (pattern, path, matches) in cases {
+            assert_eq!(
+                matches,
+                PathPattern::gitignore(pattern).unwrap().is_match(path),
+                "{pattern} matching {path}"
+            );
+        }
+    }
 }
@@ -0,0 +1,225 @@
+//! Event payloads of GitHub Actions and Gitea Actions, which are also what their webhooks deliver.
+//!
+//! Gitea Actions mirrors the GitHub Actions variables and payloads but its objects lack many of
+//! the fields GitHub sends so only the fields needed to build a context are deserialized.
+//! https://docs.github.com/en/actions/reference/workflows-and-actions/events-that-trigger-workflows
+
+use std::path::PathBuf;
+
+use scm::platforms::ScmPlatform;
+use serde_derive::Deserialize;
+
+use crate::{
+    ContinuousIntegrationContext, ContinuousIntegrationProvider, ContinuousIntegrationResult,
+    NULL_SHA, branch_name, env_var,
+};
+
+#[derive(Debug, Default, Deserialize)]
+struct User {
+    login: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Repository {
+    full_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PullRequestBranch {
+    #[serde(rename = "ref")]
+    reference: String,
+    sha: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PullRequest {
+    /// The number used by the API and UI, not to be confused with the pull request's `id`
+    number: u64,
+    #[serde(default)]
+    draft: bool,
+    user: Option<User>,
+    base: PullRequestBranch,
+    head: PullRequestBranch,
+}
+
+#[derive(Debug, Deserialize)]
+struct PullRequestEvent {
+    pull_request: PullRequest,
+}
+
+#[derive(Debug, Deserialize)]
+struct PushEvent {
+    #[serde(rename = "ref")]
+    reference: Option<String>,
+    before: Option<String>,
+    after: Option<String>,
+    sender: Option<User>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MergeGroup {
+    head_sha: String,
+    base_sha: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct MergeGroupEvent {
+    merge_group: MergeGroup,
+    sender: Option<User>,
+}
+
+/// Any other event, such as `workflow_dispatch` or `schedule`, which isn't tied to a change
+#[derive(Debug, Default, Deserialize)]
+struct OtherEvent {
+    sender: Option<User>,
+}
+
+#[derive(Debug)]
+enum ActionsEvent {
+    MergeGroup(MergeGroupEvent),
+    Other(OtherEvent),
+    PullRequest(PullRequestEvent),
+    Push(PushEvent),
+}
+
+impl ActionsEvent {
+    /// Parses the payload at `GITHUB_EVENT_PATH` based on `GITHUB_EVENT_NAME`
+    fn from_env() -> ContinuousIntegrationResult<Self> {
+        let Some(path) = env_var("GITHUB_EVENT_PATH") else {
+            return Ok(ActionsEvent::Other(OtherEvent::default()));
+        };
+
+        let data = std::fs::read_to_string(path)?;
+        let value = serde_json::from_str(&data)?;
+        Self::from_value(env_var("GITHUB_EVENT_NAME").as_deref(), value)
+    }
+
+    /// Webhooks deliver the event name in a header so when it isn't known the event is determined
+    /// from the payload's fields
+    fn from_value(
+        name: Option<&str>,
+        value: serde_json::Value,
+    ) -> ContinuousIntegrationResult<Self> {
+        let name = name.or_else(|| {
+            if value.get("pull_request").is_some() {
+                Some("pull_request")
+            } else if value.get("merge_group").is_some() {
+                Some("merge_group")
+            } else if value.get("before").is_some() && value.get("after").is_some() {
+                Some("push")
+            } else {
+                None
+            }
+        });
+
+        Ok(match name {
+            Some("pull_request" | "pull_request_target") => {
+                ActionsEvent::PullRequest(serde_json::from_value(value)?)
+            }
+            Some("push") => ActionsEvent::Push(serde_json::from_value(value)?),
+            Some("merge_group") => ActionsEvent::MergeGroup(serde_json::from_value(value)?),
+            _ => ActionsEvent::Other(serde_json::from_value(value)?),
+        })
+    }
+
+    fn apply(self, context: &mut ContinuousIntegrationContext) {
+        match self {
+            ActionsEvent::MergeGroup(event) => {
+                context.base = event.merge_group.base_sha;
+                context.head = event.merge_group.head_sha;
+                context.author = mention(event.sender);
+            }
+            ActionsEvent::Other(event) => {
+                context.author = mention(event.sender);
+            }
+            ActionsEvent::PullRequest(event) => {
+                let pull_request = event.pull_request;
+                context.base = pull_request.base.sha;
+                context.head = pull_request.head.sha;
+                context.draft = pull_request.draft;
+                context.author = mention(pull_request.user);
+                // GITHUB_REF_NAME is the merge ref, e.g. `42/merge`, for pull requests
+                context.branch = Some(pull_request.head.reference);
+                context.is_pull_request = true;
+                context.pull_request = Some(pull_request.number.to_string());
+            }
+            ActionsEvent::Push(event) => {
+                context.base = event.before.filter(|b| b != NULL_SHA).unwrap_or_default();
+                if let Some(after) = event.after {
+                    context.head = after;
+                }
+                if let Some(reference) = event.reference {
+                    context.branch = Some(branch_name(&reference));
+                }
+                context.author = mention(event.sender);
+            }
+        }
+    }
+}
+
+fn mention(user: Option<User>) -> Option<String> {
+    user.map(|u| format!("@{}", u.login))
+}
+
+pub(crate) fn context_from_env(
+    provider: ContinuousIntegrationProvider,
+    scm_platform: ScmPlatform,
+) -> ContinuousIntegrationResult<ContinuousIntegrationContext> {
+    let build_directory: PathBuf = doctavious_std::env::parse("GITHUB_WORKSPACE")?;
+    let commit = env_var("GITHUB_SHA");
+    let mut context = ContinuousIntegrationContext {
+        is_ci: provider.in_ci(),
+        provider,
+        build_directory,
+        base: String::new(),
+        head: commit.clone().unwrap_or_default(),
+        draft: false,
+        author: None,
+        repository: env_var("GITHUB_REPOSITORY"),
+        branch: env_var("GITHUB_REF_NAME"),
+        commit,
+        is_pull_request: false,
+        pull_request: None,
+        scm_platform: Some(scm_platform),
+        metadata: Default::default(),
+    };
+
+    ActionsEvent::from_env()?.apply(&mut context);
+
+    Ok(context)
+}
+
+pub(crate) fn context_from_webhook(
+    provider: ContinuousIntegrationProvider,
+    scm_platform: ScmPlatform,
+    data: &str,
+) -> ContinuousIntegrationResult<ContinuousIntegrationContext> {
+    let value: serde_json::Value = serde_json::from_str(data)?;
+    let repository = value
+        .get("repository")
+        .cloned()
+        .map(serde_json::from_value::<Repository>)
+        .transpose()?;
+
+    let mut context = ContinuousIntegrationContext {
+        provider,
+        is_ci: false,
+        build_directory: PathBuf::new(),
+        base: String::new(),
+        head: String::new(),
+        draft: false,
+        author: None,
+        repository: repository.map(|r| r.full_name),
+        branch: None,
+        commit: None,
+        is_pull_request: false,
+        pull_request: None,
+        scm_platform: Some(scm_platform),
+        metadata: Default::default(),
+    };
+
+    ActionsEvent::from_value(None, value)?.apply(&mut context);
+    context.commit = Some(context.head.clone()).filter(|head| !head.is_empty());
+
+    Ok(context)
+}
@@ -0,0 +1,118 @@
+//! GitLab webhook payloads.
+//! https://docs.gitlab.com/user/project/integrations/webhook_events/
+
+use std::path::PathBuf;
+
+use scm::platforms::ScmPlatform;
+use serde_derive::Deserialize;
+
+use crate::{
+    ContinuousIntegrationContext, ContinuousIntegrationProvider, ContinuousIntegrationResult,
+    NULL_SHA, branch_name, remote_branch,
+};
+
+#[derive(Debug, Deserialize)]
+struct User {
+    username: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Project {
+    path_with_namespace: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Commit {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct MergeRequest {
+    /// The project level ID which is what the merge request API expects
+    iid: u64,
+    /// Older GitLab versions only send `work_in_progress`
+    draft: Option<bool>,
+    work_in_progress: Option<bool>,
+    source_branch: String,
+    target_branch: String,
+    last_commit: Option<Commit>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MergeRequestEvent {
+    user: Option<User>,
+    project: Project,
+    object_attributes: MergeRequest,
+}
+
+#[derive(Debug, Deserialize)]
+struct PushEvent {
+    #[serde(rename = "ref")]
+    reference: String,
+    before: String,
+    after: String,
+    user_username: Option<String>,
+    project: Project,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "object_kind", rename_all = "snake_case")]
+enum WebhookEvent {
+    MergeRequest(Box<MergeRequestEvent>),
+    Push(PushEvent),
+    #[serde(other)]
+    Other,
+}
+
+pub(crate) fn context_from_webhook(
+    data: &str,
+) -> ContinuousIntegrationResult<ContinuousIntegrationContext> {
+    let mut context = ContinuousIntegrationContext {
+        provider: ContinuousIntegrationProvider::GitLab,
+        is_ci: false,
+        build_directory: PathBuf::new(),
+        base: String::new(),
+        head: String::new(),
+        draft: false,
+        author: None,
+        repository: None,
+        branch: None,
+        commit: None,
+        is_pull_request: false,
+        pull_request: None,
+        scm_platform: Some(ScmPlatform::GitLab),
+        metadata: Default::default(),
+    };
+
+    match serde_json::from_str(data)? {
+        WebhookEvent::MergeRequest(event) => {
+            let merge_request = event.object_attributes;
+            // merge request events don't include the target's SHA, only its branch
+            context.base = remote_branch(&merge_request.target_branch);
+            context.head = merge_request.last_commit.map(|c| c.id).unwrap_or_default();
+            context.draft = merge_request
+                .draft
+                .or(merge_request.work_in_progress)
+                .unwrap_or(false);
+            context.author = event.user.map(|u| format!("@{}", u.username));
+            context.repository = Some(event.project.path_with_namespace);
+            context.branch = Some(merge_request.source_branch);
+            context.is_pull_request = true;
+            context.pull_request = Some(merge_request.iid.to_string());
+        }
+        WebhookEvent::Push(event) => {
+            context.base = Some(event.before)
+                .filter(|b| b != NULL_SHA)
+                .unwrap_or_default();
+            context.head = event.after;
+            context.author = event.user_username.map(|u| format!("@{u}"));
+            context.repository = Some(event.project.path_with_namespace);
+            context.branch = Some(branch_name(&event.reference));
+        }
+        WebhookEvent::Other => {}
+    }
+
+    context.commit = Some(context.head.clone()).filter(|head| !head.is_empty());
+
+    Ok(context)
+}
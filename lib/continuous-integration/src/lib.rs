@@ -1,10 +1,13 @@
+mod actions;
+mod gitlab;
+
 use std::collections::HashMap;
 use std::io;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use github_client::webhook::PullRequestWebhookEventPayload;
-use scm::platforms::github::provider::GithubRepositoryBoundedProvider;
-use scm::platforms::{ScmPlatform, ScmPlatformRepositoryBoundedClient, github};
+use scm::platforms::github::app::GitHubAppCredentials;
+use scm::platforms::{ScmPlatform, ScmPlatformError, ScmPlatformRepositoryBoundedClient};
+use scm::remote::ScmRemote;
 use serde_derive::{Deserialize, Serialize};
 use strum::{EnumIter, IntoEnumIterator};
 use thiserror::Error;
@@ -17,14 +20,17 @@ pub enum ContinuousIntegrationError {
     EnvVarError(#[from] doctavious_std::env::EnvVarError),
 
     #[error(transparent)]
-    GithubScmPlatformError(#[from] github::ClientError),
+    IoError(#[from] io::Error),
 
     #[error(transparent)]
-    IoError(#[from] io::Error),
+    ScmPlatformError(#[from] ScmPlatformError),
 
     #[error(transparent)]
     SerdeJson(#[from] serde_json::Error),
 
+    #[error("webhooks are not supported for {0:?}")]
+    UnsupportedWebhook(ContinuousIntegrationProvider),
+
     #[error(transparent)]
     VarError(#[from] std::env::VarError),
 }
@@ -93,33 +99,7 @@ impl ContinuousIntegrationProvider {
     pub fn context_from_env(&self) -> ContinuousIntegrationResult<ContinuousIntegrationContext> {
         Ok(match self {
             ContinuousIntegrationProvider::GitHubActions => {
-                let build_directory = doctavious_std::env::parse("GITHUB_WORKSPACE")?;
-                let event_path = std::env::var("GITHUB_EVENT_PATH")?;
-                let data = std::fs::read_to_string(event_path)?;
-                // TODO: do we have to handle if this isn't the payload we expect?
-                let event: PullRequestWebhookEventPayload = serde_json::from_str(&data)?;
-
-                ContinuousIntegrationContext {
-                    provider: ContinuousIntegrationProvider::GitHubActions,
-                    is_ci: self.in_ci(),
-                    build_directory,
-                    base: event.pull_request.base.sha,
-                    head: event.pull_request.head.sha,
-                    draft: event.pull_request.draft.unwrap_or(false),
-                    author: event
-                        .pull_request
-                        .user
-                        .and_then(|u| Some(format!("@{}", u.login).to_string())),
-
-                    repository: std::env::var("GITHUB_REPOSITORY").ok(),
-                    branch: std::env::var("GITHUB_REF_NAME").ok(),
-                    commit: std::env::var("GITHUB_SHA").ok(),
-                    is_pull_request: std::env::var("GITHUB_EVENT_NAME").ok()
-                        == Some("pull_request".to_string()),
-                    pull_request: Some(event.pull_request.id.to_string()),
-                    scm_platform: Some(ScmPlatform::GitHub),
-                    metadata: Default::default(),
-                }
+                actions::context_from_env(self.clone(), ScmPlatform::GitHub)?
             }
             ContinuousIntegrationProvider::GitLab => {
                 let build_directory = doctavious_std::env::parse("CI_BUILDS_DIR")?;
@@ -142,8 +122,9 @@ impl ContinuousIntegrationProvider {
                     repository: std::env::var("CI_PROJECT_PATH").ok(),
                     branch: std::env::var("CI_COMMIT_REF_NAME").ok(),
                     commit: std::env::var("CI_COMMIT_SHA").ok(),
-                    is_pull_request: std::env::var("CI_MERGE_REQUEST_ID").is_ok(),
-                    pull_request: std::env::var("CI_MERGE_REQUEST_ID").ok(),
+                    is_pull_request: std::env::var("CI_MERGE_REQUEST_IID").is_ok(),
+                    // the project level IID is what the merge request API expects
+                    pull_request: std::env::var("CI_MERGE_REQUEST_IID").ok(),
                     scm_platform: Some(ScmPlatform::GitLab),
                     metadata: Default::default(),
                 }
@@ -151,139 +132,382 @@ impl ContinuousIntegrationProvider {
             ContinuousIntegrationProvider::Gitea => {
                 // Gitea Actions is heavily inspired by GitHub Actions and they use the same
                 // variables for the sake of compatability.
-                let build_directory = doctavious_std::env::parse("GITHUB_WORKSPACE")?;
-                let event_path = std::env::var("GITHUB_EVENT_PATH")?;
-                let data = std::fs::read_to_string(event_path)?;
-                let event: PullRequestWebhookEventPayload = serde_json::from_str(&data)?;
+                actions::context_from_env(self.clone(), ScmPlatform::Gitea)?
+            }
+            ContinuousIntegrationProvider::AzureDevOpsPipelines => {
+                // Azure Pipelines builds repositories hosted on Azure Repos, GitHub and Bitbucket
+                let platform =
+                    env_var("BUILD_REPOSITORY_PROVIDER").and_then(|p| match p.as_str() {
+                        "TfsGit" => Some(ScmPlatform::Azure),
+                        "GitHub" | "GitHubEnterprise" => Some(ScmPlatform::GitHub),
+                        "Bitbucket" => Some(ScmPlatform::BitBucket),
+                        _ => None,
+                    });
+                let remote = env_var("BUILD_REPOSITORY_URI").and_then(|url| match &platform {
+                    Some(platform) => ScmRemote::parse_for_platform(&url, platform.clone()),
+                    None => ScmRemote::parse(&url),
+                });
+                let commit = env_var("BUILD_SOURCEVERSION");
+                // Azure Repos pull requests only have an ID while GitHub pull requests also have
+                // a number, which is what GitHub's API expects
+                let pull_request = env_var("SYSTEM_PULLREQUEST_PULLREQUESTNUMBER")
+                    .or_else(|| env_var("SYSTEM_PULLREQUEST_PULLREQUESTID"));
+
+                ContinuousIntegrationContext {
+                    provider: ContinuousIntegrationProvider::AzureDevOpsPipelines,
+                    is_ci: self.in_ci(),
+                    build_directory: build_directory("BUILD_SOURCESDIRECTORY")?,
+                    head: env_var("SYSTEM_PULLREQUEST_SOURCECOMMITID")
+                        .or_else(|| commit.clone())
+                        .unwrap_or_default(),
+                    base: env_var("SYSTEM_PULLREQUEST_TARGETBRANCH")
+                        .map(|b| remote_branch(&branch_name(&b)))
+                        .unwrap_or_default(),
+                    draft: doctavious_std::env::as_boolean("SYSTEM_PULLREQUEST_ISDRAFT"),
+                    author: None,
+                    repository: remote
+                        .as_ref()
+                        .map(slug)
+                        .or_else(|| env_var("BUILD_REPOSITORY_NAME")),
+                    branch: env_var("SYSTEM_PULLREQUEST_SOURCEBRANCH")
+                        .or_else(|| env_var("BUILD_SOURCEBRANCH"))
+                        .map(|b| branch_name(&b)),
+                    commit,
+                    is_pull_request: env_var("BUILD_REASON").as_deref() == Some("PullRequest"),
+                    pull_request,
+                    scm_platform: platform.or_else(|| remote.map(|r| r.platform)),
+                    metadata: Default::default(),
+                }
+            }
+            ContinuousIntegrationProvider::BitBucket => {
+                let pull_request = env_var("BITBUCKET_PR_ID");
+                ContinuousIntegrationContext {
+                    provider: ContinuousIntegrationProvider::BitBucket,
+                    is_ci: self.in_ci(),
+                    build_directory: build_directory("BITBUCKET_CLONE_DIR")?,
+                    head: env_var("BITBUCKET_COMMIT").unwrap_or_default(),
+                    base: env_var("BITBUCKET_PR_DESTINATION_COMMIT")
+                        .or_else(|| {
+                            env_var("BITBUCKET_PR_DESTINATION_BRANCH").map(|b| remote_branch(&b))
+                        })
+                        .unwrap_or_default(),
+                    draft: false,
+                    author: None,
+                    repository: env_var("BITBUCKET_REPO_FULL_NAME"),
+                    branch: env_var("BITBUCKET_BRANCH"),
+                    commit: env_var("BITBUCKET_COMMIT"),
+                    is_pull_request: pull_request.is_some(),
+                    pull_request,
+                    scm_platform: Some(ScmPlatform::BitBucket),
+                    metadata: Default::default(),
+                }
+            }
+            ContinuousIntegrationProvider::Buildkite => {
+                let remote = env_var("BUILDKITE_REPO").and_then(|url| ScmRemote::parse(&url));
+                let pull_request = env_var("BUILDKITE_PULL_REQUEST").filter(|pr| pr != "false");
+                ContinuousIntegrationContext {
+                    provider: ContinuousIntegrationProvider::Buildkite,
+                    is_ci: self.in_ci(),
+                    build_directory: build_directory("BUILDKITE_BUILD_CHECKOUT_PATH")?,
+                    head: env_var("BUILDKITE_COMMIT").unwrap_or_default(),
+                    base: env_var("BUILDKITE_PULL_REQUEST_BASE_BRANCH")
+                        .map(|b| remote_branch(&b))
+                        .unwrap_or_default(),
+                    draft: doctavious_std::env::as_boolean("BUILDKITE_PULL_REQUEST_DRAFT"),
+                    author: None,
+                    repository: remote.as_ref().map(slug),
+                    branch: env_var("BUILDKITE_BRANCH"),
+                    commit: env_var("BUILDKITE_COMMIT"),
+                    is_pull_request: pull_request.is_some(),
+                    pull_request,
+                    scm_platform: remote.map(|r| r.platform),
+                    metadata: Default::default(),
+                }
+            }
+            ContinuousIntegrationProvider::CircleCI => {
+                let build_directory = match env_var("CIRCLE_WORKING_DIRECTORY") {
+                    // CircleCI doesn't expand the default of `~/project`
+                    Some(dir) => match (dir.strip_prefix("~/"), env_var("HOME")) {
+                        (Some(relative), Some(home)) => PathBuf::from(home).join(relative),
+                        _ => PathBuf::from(dir),
+                    },
+                    None => std::env::current_dir()?,
+                };
+                // CIRCLE_PR_NUMBER is only set for pull requests from forks
+                let pull_request = env_var("CIRCLE_PR_NUMBER").or_else(|| {
+                    env_var("CIRCLE_PULL_REQUEST")
+                        .and_then(|url| url.rsplit('/').next().map(str::to_string))
+                });
+                let repository = env_var("CIRCLE_PROJECT_USERNAME")
+                    .zip(env_var("CIRCLE_PROJECT_REPONAME"))
+                    .map(|(owner, repo)| format!("{owner}/{repo}"));
+
                 ContinuousIntegrationContext {
-                    provider: ContinuousIntegrationProvider::Gitea,
+                    provider: ContinuousIntegrationProvider::CircleCI,
                     is_ci: self.in_ci(),
                     build_directory,
-                    base: event.pull_request.base.sha,
-                    head: event.pull_request.head.sha,
-                    draft: event.pull_request.draft.unwrap_or(false),
-                    author: event
-                        .pull_request
-                        .user
-                        .and_then(|u| Some(format!("@{}", u.login).to_string())),
-                    // TODO: confirm
-                    repository: std::env::var("GITHUB_REPOSITORY").ok(),
-                    branch: std::env::var("GITHUB_REF_NAME").ok(),
-                    commit: std::env::var("GITHUB_SHA").ok(),
-                    is_pull_request: std::env::var("GITHUB_EVENT_NAME").ok()
-                        == Some("pull_request".to_string()),
-                    pull_request: Some(event.pull_request.id.to_string()),
-                    scm_platform: Some(ScmPlatform::Gitea),
+                    head: env_var("CIRCLE_SHA1").unwrap_or_default(),
+                    // CircleCI doesn't expose the pull request's base outside of pipeline values
+                    base: "".to_string(),
+                    draft: false,
+                    author: env_var("CIRCLE_USERNAME").map(|u| format!("@{u}")),
+                    repository,
+                    branch: env_var("CIRCLE_BRANCH"),
+                    commit: env_var("CIRCLE_SHA1"),
+                    is_pull_request: pull_request.is_some(),
+                    pull_request,
+                    scm_platform: env_var("CIRCLE_REPOSITORY_URL")
+                        .and_then(|url| ScmRemote::parse(&url))
+                        .map(|r| r.platform),
                     metadata: Default::default(),
                 }
             }
-            // TODO: finish...
-            ContinuousIntegrationProvider::Buildkite => ContinuousIntegrationContext {
-                provider: ContinuousIntegrationProvider::Buildkite,
-                is_ci: self.in_ci(),
-                build_directory: Default::default(),
-                head: "".to_string(),
-                base: "".to_string(),
-                draft: false,
-                author: None,
-                repository: std::env::var("BUILDKITE_PIPELINE_SLUG").ok(),
-                branch: std::env::var("BUILDKITE_BRANCH").ok(),
-                commit: std::env::var("BUILDKITE_COMMIT").ok(),
-                is_pull_request: std::env::var("BUILDKITE_PULL_REQUEST").is_ok(),
-                pull_request: None,
-                scm_platform: Self::detect_scm_from_repo_url(std::env::var("BUILDKIT_REPO").ok()),
-                metadata: Default::default(),
-            },
-            // TODO: finish...
-            ContinuousIntegrationProvider::CircleCI => ContinuousIntegrationContext {
-                provider: ContinuousIntegrationProvider::CircleCI,
-                is_ci: self.in_ci(),
-                build_directory: Default::default(),
-                head: "".to_string(),
-                base: "".to_string(),
-                draft: false,
-                author: None,
-                repository: std::env::var("CIRCLECPROJECT_REPONAME").ok(),
-                branch: std::env::var("CIRCLECI_BRANCH").ok(),
-                commit: std::env::var("CIRCLECI_SHA1").ok(),
-                is_pull_request: std::env::var("CIRCLECI_PULL_REQUEST").is_ok(),
-                pull_request: None,
-                scm_platform: Self::detect_scm_from_repo_url(
-                    std::env::var("CIRCLECI_REPOSITORY_URL").ok(),
-                ),
-                metadata: Default::default(),
-            },
-            _ => todo!(),
-        })
-    }
+            ContinuousIntegrationProvider::Jenkins => {
+                // Multibranch pipelines set CHANGE_* for pull requests while the GitHub Pull
+                // Request Builder plugin sets ghprb*
+                let remote = env_var("GIT_URL").and_then(|url| ScmRemote::parse(&url));
+                let pull_request = env_var("CHANGE_ID").or_else(|| env_var("ghprbPullId"));
+                ContinuousIntegrationContext {
+                    provider: ContinuousIntegrationProvider::Jenkins,
+                    is_ci: self.in_ci(),
+                    build_directory: build_directory("WORKSPACE")?,
+                    head: env_var("GIT_COMMIT")
+                        .or_else(|| env_var("ghprbActualCommit"))
+                        .unwrap_or_default(),
+                    base: env_var("CHANGE_TARGET")
+                        .or_else(|| env_var("ghprbTargetBranch"))
+                        .map(|b| remote_branch(&b))
+                        .unwrap_or_default(),
+                    draft: false,
+                    author: env_var("CHANGE_AUTHOR")
+                        .or_else(|| env_var("ghprbPullAuthorLogin"))
+                        .map(|a| format!("@{a}")),
+                    repository: remote
+                        .as_ref()
+                        .map(slug)
+                        .or_else(|| env_var("ghprbGhRepository")),
+                    branch: env_var("BRANCH_NAME").or_else(|| env_var("GIT_BRANCH")),
+                    commit: env_var("GIT_COMMIT"),
+                    is_pull_request: pull_request.is_some(),
+                    pull_request,
+                    scm_platform: remote
+                        .map(|r| r.platform)
+                        .or_else(|| env_var("ghprbGhRepository").map(|_| ScmPlatform::GitHub)),
+                    metadata: Default::default(),
+                }
+            }
+            ContinuousIntegrationProvider::TeamCity => {
+                // TeamCity only exposes a few environment variables. Everything else, including
+                // pull request details, is in the configuration properties file referenced by the
+                // build properties file
+                let build_properties = match env_var("TEAMCITY_BUILD_PROPERTIES_FILE") {
+                    Some(path) => read_properties(path)?,
+                    None => HashMap::new(),
+                };
+                let config = match build_properties.get("teamcity.configuration.properties.file") {
+                    Some(path) => read_properties(path)?,
+                    None => HashMap::new(),
+                };
+                let property = |key: &str| config.get(key).filter(|v| !v.is_empty()).cloned();
+
+                let remote = property("vcsroot.url").and_then(|url| ScmRemote::parse(&url));
+                let commit = env_var("BUILD_VCS_NUMBER").or_else(|| property("build.vcs.number"));
+                let pull_request = property("teamcity.pullRequest.number");
+                ContinuousIntegrationContext {
+                    provider: ContinuousIntegrationProvider::TeamCity,
+                    is_ci: self.in_ci(),
+                    build_directory: match property("teamcity.build.checkoutDir") {
+                        Some(dir) => PathBuf::from(dir),
+                        None => std::env::current_dir()?,
+                    },
+                    head: commit.clone().unwrap_or_default(),
+                    base: property("teamcity.pullRequest.target.branch")
+                        .map(|b| remote_branch(&branch_name(&b)))
+                        .unwrap_or_default(),
+                    draft: false,
+                    author: None,
+                    repository: remote.as_ref().map(slug),
+                    branch: property("teamcity.build.branch"),
+                    commit,
+                    is_pull_request: pull_request.is_some(),
+                    pull_request,
+                    scm_platform: remote.map(|r| r.platform),
+                    metadata: Default::default(),
+                }
+            }
+            ContinuousIntegrationProvider::Travis => {
+                let pull_request = env_var("TRAVIS_PULL_REQUEST").filter(|pr| pr != "false");
+                // TRAVIS_COMMIT_RANGE is `base...head`
+                let base = env_var("TRAVIS_COMMIT_RANGE")
+                    .and_then(|range| range.split_once("...").map(|(b, _)| b.to_string()))
+                    .or_else(|| {
+                        pull_request
+                            .as_ref()
+                            .and_then(|_| env_var("TRAVIS_BRANCH"))
+                            .map(|b| remote_branch(&b))
+                    });
 
-    fn detect_scm_from_repo_url(repo_url: Option<String>) -> Option<ScmPlatform> {
-        repo_url.and_then(|url| {
-            if url.contains("github.com") {
-                Some(ScmPlatform::GitHub)
-            } else if url.contains("gitlab.com") {
-                Some(ScmPlatform::GitLab)
-            } else if url.contains("bitbucket.org") {
-                Some(ScmPlatform::BitBucket)
-            } else if url.contains("dev.azure.com") {
-                Some(ScmPlatform::Azure)
-            } else if url.contains("gitea.com") {
-                Some(ScmPlatform::Gitea)
-            } else if url.contains("gogs.io") {
-                Some(ScmPlatform::Gogs)
-            } else {
-                None
+                ContinuousIntegrationContext {
+                    provider: ContinuousIntegrationProvider::Travis,
+                    is_ci: self.in_ci(),
+                    build_directory: build_directory("TRAVIS_BUILD_DIR")?,
+                    head: env_var("TRAVIS_PULL_REQUEST_SHA")
+                        .or_else(|| env_var("TRAVIS_COMMIT"))
+                        .unwrap_or_default(),
+                    base: base.unwrap_or_default(),
+                    draft: false,
+                    author: None,
+                    repository: env_var("TRAVIS_REPO_SLUG"),
+                    branch: env_var("TRAVIS_PULL_REQUEST_BRANCH")
+                        .or_else(|| env_var("TRAVIS_BRANCH")),
+                    commit: env_var("TRAVIS_COMMIT"),
+                    is_pull_request: pull_request.is_some(),
+                    pull_request,
+                    // Travis CI's support for SCMs other than GitHub is in beta and not exposed in
+                    // the build environment
+                    scm_platform: Some(ScmPlatform::GitHub),
+                    metadata: Default::default(),
+                }
             }
         })
     }
 
-    // TODO: is this useful here or is it mainly an SCMProvider concern?
-    // GitHub Actions does reference a webhook payload path in their CI env which does contain
-    // details
-    pub fn context_from_webhook(&self, _data: &str) -> ContinuousIntegrationContext {
-        todo!()
+    /// Builds a context from a webhook payload of the CI provider's SCM platform, e.g., a GitHub
+    /// pull request event for GitHub Actions, so changes can be processed outside of CI.
+    /// There is no checkout so `build_directory` is empty until the caller checks out `head`.
+    pub fn context_from_webhook(
+        &self,
+        data: &str,
+    ) -> ContinuousIntegrationResult<ContinuousIntegrationContext> {
+        match self {
+            ContinuousIntegrationProvider::Gitea => {
+                actions::context_from_webhook(self.clone(), ScmPlatform::Gitea, data)
+            }
+            ContinuousIntegrationProvider::GitHubActions => {
+                actions::context_from_webhook(self.clone(), ScmPlatform::GitHub, data)
+            }
+            ContinuousIntegrationProvider::GitLab => gitlab::context_from_webhook(data),
+            _ => Err(ContinuousIntegrationError::UnsupportedWebhook(self.clone())),
+        }
     }
 
     // TODO: does this make sense?
     // if we can determine which scm provider we can do that here or context?
     // even if we did determine we wouldnt know the authentication key to use and
     // would need users to pass details into the CLI. we can check for a well known env var
+    /// SCM platform the CI provider is part of.
+    /// Returns `None` for standalone CI providers which build repositories from any platform.
     pub fn associated_scm_platform(&self) -> Option<ScmPlatform> {
         match self {
+            ContinuousIntegrationProvider::AzureDevOpsPipelines => Some(ScmPlatform::Azure),
+            ContinuousIntegrationProvider::BitBucket => Some(ScmPlatform::BitBucket),
             ContinuousIntegrationProvider::Gitea => Some(ScmPlatform::Gitea),
             ContinuousIntegrationProvider::GitHubActions => Some(ScmPlatform::GitHub),
             ContinuousIntegrationProvider::GitLab => Some(ScmPlatform::GitLab),
-            _ => todo!(),
+            ContinuousIntegrationProvider::Buildkite
+            | ContinuousIntegrationProvider::CircleCI
+            | ContinuousIntegrationProvider::Jenkins
+            | ContinuousIntegrationProvider::TeamCity
+            | ContinuousIntegrationProvider::Travis => None,
         }
     }
 
-    // TODO (sean): Not sure if this is the best way to handle this but trying to make progress on
-    // something. Will revisit later in particular when we get to the point of how to integrate this
-    // when handling customer webhooks.
+    /// Creates an SCM platform client bound to the repository being built with credentials from
+    /// environment variables. See [`ScmPlatform::get_client_from_env`].
+    /// Returns `None` when the platform or repository can't be determined from the context.
     pub fn associated_bound_scm_client(
         &self,
         context: &ContinuousIntegrationContext,
     ) -> ContinuousIntegrationResult<Option<Box<dyn ScmPlatformRepositoryBoundedClient>>> {
-        match self {
-            ContinuousIntegrationProvider::GitHubActions => {
-                if let Some((owner, repo_name)) =
-                    context.repository.as_ref().unwrap().split_once('/')
-                {
-                    let token = std::env::var("GITHUB_TOKEN")?;
-                    let p = GithubRepositoryBoundedProvider::new(
-                        owner.to_string(),
-                        repo_name.to_string(),
-                        &token,
-                        std::env::var("GITHUB_API_URL").ok().as_deref(),
-                    )?;
-                    Ok(Some(Box::new(p)))
-                } else {
-                    Ok(None)
-                }
+        self.associated_bound_scm_client_with_github_app(context, None)
+    }
+
+    /// Creates an SCM platform client like [`associated_bound_scm_client`] with GitHub clients
+    /// authenticating as `github_app` rather than the app of the `GITHUB_APP_*` variables.
+    ///
+    /// [`associated_bound_scm_client`]: ContinuousIntegrationProvider::associated_bound_scm_client
+    pub fn associated_bound_scm_client_with_github_app(
+        &self,
+        context: &ContinuousIntegrationContext,
+        github_app: Option<GitHubAppCredentials>,
+    ) -> ContinuousIntegrationResult<Option<Box<dyn ScmPlatformRepositoryBoundedClient>>> {
+        let (Some(platform), Some((owner, repository))) = (
+            context
+                .scm_platform
+                .clone()
+                .or_else(|| self.associated_scm_platform()),
+            context
+                .repository
+                .as_deref()
+                .and_then(|r| r.rsplit_once('/')),
+        ) else {
+            return Ok(None);
+        };
+
+        let credentials = platform.credentials_from_env_with_github_app(None, github_app)?;
+        Ok(Some(platform.get_client(
+            owner,
+            repository,
+            &credentials,
+        )?))
+    }
+}
+
+/// `before` of push events which created a branch
+const NULL_SHA: &str = "0000000000000000000000000000000000000000";
+
+/// Value of an environment variable, treating empty values as unset
+fn env_var(key: &str) -> Option<String> {
+    std::env::var(key).ok().filter(|v| !v.trim().is_empty())
+}
+
+/// Directory from an environment variable, falling back to the current directory
+fn build_directory(key: &str) -> ContinuousIntegrationResult<PathBuf> {
+    Ok(match env_var(key) {
+        Some(dir) => PathBuf::from(dir),
+        None => std::env::current_dir()?,
+    })
+}
+
+/// Strips the `refs/heads/` prefix some CI providers include in branch names
+fn branch_name(reference: &str) -> String {
+    reference
+        .strip_prefix("refs/heads/")
+        .unwrap_or(reference)
+        .to_string()
+}
+
+/// Remote tracking branch of a pull request's target branch. CI checkouts usually only fetch the
+/// pull request, so there is no local branch of the target to compare against
+fn remote_branch(branch: &str) -> String {
+    format!("origin/{branch}")
+}
+
+fn slug(remote: &ScmRemote) -> String {
+    format!("{}/{}", remote.owner, remote.repo)
+}
+
+/// Reads a Java properties file, as written by TeamCity, ignoring multi-line values
+fn read_properties<P: AsRef<Path>>(path: P) -> io::Result<HashMap<String, String>> {
+    let unescape = |value: &str| {
+        let mut unescaped = String::with_capacity(value.len());
+        let mut chars = value.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => unescaped.extend(chars.next()),
+                _ => unescaped.push(c),
             }
-            _ => todo!(),
         }
-    }
+        unescaped
+    };
+
+    Ok(std::fs::read_to_string(path)?
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#') && !line.starts_with('!'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (unescape(key.trim()), unescape(value.trim())))
+        .collect())
 }
 
 pub trait ContinuousIntegrationOperations: Send + Sync {
@@ -355,7 +579,7 @@ impl ContinuousIntegrationOperations for CircleCiProvider {
 
     fn env_vars(&self) -> HashMap<String, String> {
         std::env::vars()
-            .filter(|(key, _)| key.starts_with("CIRCLECI_"))
+            .filter(|(key, _)| key.starts_with("CIRCLE"))
             .collect()
     }
 
@@ -374,19 +598,346 @@ impl ContinuousIntegrationOperations for CircleCiProvider {
 /// TODO: Think through a more robust solution that would potentially allow us to get at any CI
 /// information that might be exposed via environment variables, webhook payloads, etc across the
 /// different CI providers.
+#[derive(Clone, Debug, PartialEq)]
 pub struct ContinuousIntegrationContext {
     pub provider: ContinuousIntegrationProvider,
     pub is_ci: bool,
     pub build_directory: PathBuf,
+    /// Revision being built, i.e., the pull request's head
     pub head: String,
+    /// Revision the pull request is compared against. Some providers only expose the target
+    /// branch's name, in which case this is its remote tracking branch, e.g., `origin/main`.
+    /// CircleCI doesn't expose it at all, in which case this is empty
     pub base: String,
     pub draft: bool,
+    /// Mention of the user who opened the pull request or triggered the build, e.g., `@octocat`
     pub author: Option<String>,
+    /// Repository slug, `owner/name`, including any groups
     pub repository: Option<String>,
     pub branch: Option<String>,
     pub commit: Option<String>,
     pub is_pull_request: bool,
+    /// Number, or ID when the platform has no numbers, of the pull request being built
     pub pull_request: Option<String>,
     pub scm_platform: Option<ScmPlatform>,
     pub metadata: HashMap<String, String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use scm::platforms::ScmPlatform;
+    use test_case::test_case;
+
+    use crate::{ContinuousIntegrationError, ContinuousIntegrationProvider};
+
+    struct Expected {
+        build_directory: &'static str,
+        head: &'static str,
+        base: &'static str,
+        pull_request: Option<&'static str>,
+        draft: bool,
+        author: Option<&'static str>,
+        repository: Option<&'static str>,
+        scm_platform: Option<ScmPlatform>,
+    }
+
+    /// Loads a recorded environment snapshot of `KEY=VALUE` lines
+    fn snapshot(name: &str) -> Vec<(String, Option<String>)> {
+        fs::read_to_string(format!("tests/fixtures/env/{name}.env"))
+            .unwrap()
+            .lines()
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| line.split_once('='))
+            .map(|(key, value)| (key.to_string(), Some(value.to_string())))
+            .collect()
+    }
+
+    #[test_case(ContinuousIntegrationProvider::AzureDevOpsPipelines, "azure_github", Expected {
+        build_directory: "/home/vsts/work/1/s",
+        head: "9f8e7d6c5b4a39281706f5e4d3c2b1a098765432",
+        base: "origin/main",
+        pull_request: Some("42"),
+        draft: true,
+        author: None,
+        repository: Some("doctavious/doctavious"),
+        scm_platform: Some(ScmPlatform::GitHub),
+    }; "azure pipelines github")]
+    #[test_case(ContinuousIntegrationProvider::AzureDevOpsPipelines, "azure_repos", Expected {
+        build_directory: "/home/vsts/work/1/s",
+        head: "3c2b1a098765432f9f8e7d6c5b4a39281706f5e4",
+        base: "origin/main",
+        pull_request: Some("17"),
+        draft: false,
+        author: None,
+        repository: Some("doctavious/platform/doctavious"),
+        scm_platform: Some(ScmPlatform::Azure),
+    }; "azure pipelines azure repos")]
+    #[test_case(ContinuousIntegrationProvider::BitBucket, "bitbucket", Expected {
+        build_directory: "/opt/atlassian/pipelines/agent/build",
+        head: "5b4a39281706f5e4d3c2b1a0987654329f8e7d6c",
+        base: "a1b2c3d4e5f6",
+        pull_request: Some("8"),
+        draft: false,
+        author: None,
+        repository: Some("doctavious/doctavious"),
+        scm_platform: Some(ScmPlatform::BitBucket),
+    }; "bitbucket pipelines")]
+    #[test_case(ContinuousIntegrationProvider::Buildkite, "buildkite", Expected {
+        build_directory: "/var/lib/buildkite-agent/builds/agent-1/doctavious/doctavious",
+        head: "0f1e2d3c4b5a69788796a5b4c3d2e1f00f1e2d3c",
+        base: "origin/main",
+        pull_request: Some("27"),
+        draft: true,
+        author: None,
+        repository: Some("doctavious/doctavious"),
+        scm_platform: Some(ScmPlatform::GitHub),
+    }; "buildkite")]
+    #[test_case(ContinuousIntegrationProvider::CircleCI, "circleci", Expected {
+        build_directory: "/home/circleci/project",
+        head: "4d3c2b1a0987654329f8e7d6c5b4a39281706f5e",
+        base: "",
+        pull_request: Some("51"),
+        draft: false,
+        author: Some("@jane"),
+        repository: Some("doctavious/doctavious"),
+        scm_platform: Some(ScmPlatform::GitHub),
+    }; "circleci")]
+    #[test_case(ContinuousIntegrationProvider::Gitea, "gitea_pull_request", Expected {
+        build_directory: "/workspace/doctavious/doctavious",
+        head: "3e2f1a0b9a8b7c6d5e4f3a2b1c0d9e8f7a6b5c4d",
+        base: "9a8b7c6d5e4f3a2b1c0d9e8f7a6b5c4d3e2f1a0b",
+        pull_request: Some("14"),
+        draft: false,
+        author: Some("@jane"),
+        repository: Some("doctavious/doctavious"),
+        scm_platform: Some(ScmPlatform::Gitea),
+    }; "gitea actions pull request")]
+    #[test_case(ContinuousIntegrationProvider::GitHubActions, "github_merge_group", Expected {
+        build_directory: "/home/runner/work/doctavious/doctavious",
+        head: "e7f8a9b0c1d2e3f4a5b6c7d8e9f0a1b2c3d4e5f6",
+        base: "1f2e3d4c5b6a79880f1e2d3c4b5a69788796a5b4",
+        pull_request: None,
+        draft: false,
+        author: Some("@github-merge-queue[bot]"),
+        repository: Some("doctavious/doctavious"),
+        scm_platform: Some(ScmPlatform::GitHub),
+    }; "github actions merge group")]
+    #[test_case(ContinuousIntegrationProvider::GitHubActions, "github_pull_request", Expected {
+        build_directory: "/home/runner/work/doctavious/doctavious",
+        head: "7a6b5c4d3e2f1a0b9c8d7e6f5a4b3c2d1e0f9a8b",
+        base: "1f2e3d4c5b6a79880f1e2d3c4b5a69788796a5b4",
+        pull_request: Some("128"),
+        draft: true,
+        author: Some("@octocat"),
+        repository: Some("doctavious/doctavious"),
+        scm_platform: Some(ScmPlatform::GitHub),
+    }; "github actions pull request")]
+    #[test_case(ContinuousIntegrationProvider::GitHubActions, "github_pull_request_target", Expected {
+        build_directory: "/home/runner/work/doctavious/doctavious",
+        head: "b1c2d3e4f5a6b7c8d9e0f1a2b3c4d5e6f7a8b9c0",
+        base: "0a9b8c7d6e5f4a3b2c1d0e9f8a7b6c5d4e3f2a1b",
+        pull_request: Some("131"),
+        draft: false,
+        author: Some("@hubot"),
+        repository: Some("doctavious/doctavious"),
+        scm_platform: Some(ScmPlatform::GitHub),
+    }; "github actions pull request target")]
+    #[test_case(ContinuousIntegrationProvider::GitHubActions, "github_push", Expected {
+        build_directory: "/home/runner/work/doctavious/doctavious",
+        head: "c0ffee254729296a45a3885639ac7e10f9d54979",
+        base: "5e4d3c2b1a0f9e8d7c6b5a4f3e2d1c0b9a8f7e6d",
+        pull_request: None,
+        draft: false,
+        author: Some("@octocat"),
+        repository: Some("doctavious/doctavious"),
+        scm_platform: Some(ScmPlatform::GitHub),
+    }; "github actions push")]
+    #[test_case(ContinuousIntegrationProvider::GitHubActions, "github_push_new_branch", Expected {
+        build_directory: "/home/runner/work/doctavious/doctavious",
+        head: "d4e5f6a7b8c9d0e1f2a3b4c5d6e7f8a9b0c1d2e3",
+        base: "",
+        pull_request: None,
+        draft: false,
+        author: Some("@octocat"),
+        repository: Some("doctavious/doctavious"),
+        scm_platform: Some(ScmPlatform::GitHub),
+    }; "github actions push creating branch")]
+    #[test_case(ContinuousIntegrationProvider::GitHubActions, "github_workflow_dispatch", Expected {
+        build_directory: "/home/runner/work/doctavious/doctavious",
+        head: "f0e1d2c3b4a5968778695a4b3c2d1e0f9a8b7c6d",
+        base: "",
+        pull_request: None,
+        draft: false,
+        author: Some("@octocat"),
+        repository: Some("doctavious/doctavious"),
+        scm_platform: Some(ScmPlatform::GitHub),
+    }; "github actions workflow dispatch")]
+    #[test_case(ContinuousIntegrationProvider::GitLab, "gitlab", Expected {
+        build_directory: "/builds",
+        head: "96a5b4c3",
+        base: "1e2d3c4b5a69788796a5b4c3d2e1f00f1e2d3c0f",
+        pull_request: Some("12"),
+        draft: false,
+        author: Some("@jane"),
+        repository: Some("doctavious/tools/doctavious"),
+        scm_platform: Some(ScmPlatform::GitLab),
+    }; "gitlab")]
+    #[test_case(ContinuousIntegrationProvider::Jenkins, "jenkins", Expected {
+        build_directory: "/var/jenkins_home/workspace/doctavious_PR-63",
+        head: "c5b4a39281706f5e4d3c2b1a0987654329f8e7d6",
+        base: "origin/main",
+        pull_request: Some("63"),
+        draft: false,
+        author: Some("@jane"),
+        repository: Some("doctavious/doctavious"),
+        scm_platform: Some(ScmPlatform::GitLab),
+    }; "jenkins multibranch")]
+    #[test_case(ContinuousIntegrationProvider::Jenkins, "jenkins_ghprb", Expected {
+        build_directory: "/var/jenkins_home/workspace/doctavious-pr",
+        head: "81706f5e4d3c2b1a0987654329f8e7d6c5b4a392",
+        base: "origin/main",
+        pull_request: Some("64"),
+        draft: false,
+        author: Some("@jane"),
+        repository: Some("doctavious/doctavious"),
+        scm_platform: Some(ScmPlatform::GitHub),
+    }; "jenkins github pull request builder")]
+    #[test_case(ContinuousIntegrationProvider::TeamCity, "teamcity", Expected {
+        build_directory: "/opt/buildagent/work/5d1b2c3a",
+        head: "e4d3c2b1a0987654329f8e7d6c5b4a39281706f5",
+        base: "origin/main",
+        pull_request: Some("91"),
+        draft: false,
+        author: None,
+        repository: Some("doctavious/doctavious"),
+        scm_platform: Some(ScmPlatform::GitHub),
+    }; "teamcity")]
+    #[test_case(ContinuousIntegrationProvider::Travis, "travis", Expected {
+        build_directory: "/home/travis/build/doctavious/doctavious",
+        head: "2d3c4b5a69788796a5b4c3d2e1f00f1e2d3c0f1e",
+        base: "6c5b4a39281706f5e4d3c2b1a0987654329f8e7d",
+        pull_request: Some("105"),
+        draft: false,
+        author: None,
+        repository: Some("doctavious/doctavious"),
+        scm_platform: Some(ScmPlatform::GitHub),
+    }; "travis")]
+    fn context_from_env(provider: ContinuousIntegrationProvider, name: &str, expected: Expected) {
+        temp_env::with_vars(snapshot(name), || {
+            assert!(provider.in_ci());
+
+            let context = provider.context_from_env().unwrap();
+            assert_eq!(provider, context.provider);
+            assert_eq!(
+                PathBuf::from(expected.build_directory),
+                context.build_directory
+            );
+            assert_eq!(expected.head, context.head);
+            assert_eq!(expected.base, context.base);
+            assert_eq!(expected.pull_request.is_some(), context.is_pull_request);
+            assert_eq!(expected.pull_request, context.pull_request.as_deref());
+            assert_eq!(expected.draft, context.draft);
+            assert_eq!(expected.author, context.author.as_deref());
+            assert_eq!(expected.repository, context.repository.as_deref());
+            assert_eq!(expected.scm_platform, context.scm_platform);
+        });
+    }
+
+    #[test_case(ContinuousIntegrationProvider::Gitea, "gitea/pull_request", Expected {
+        build_directory: "",
+        head: "3e2f1a0b9a8b7c6d5e4f3a2b1c0d9e8f7a6b5c4d",
+        base: "9a8b7c6d5e4f3a2b1c0d9e8f7a6b5c4d3e2f1a0b",
+        pull_request: Some("14"),
+        draft: false,
+        author: Some("@jane"),
+        repository: Some("doctavious/doctavious"),
+        scm_platform: Some(ScmPlatform::Gitea),
+    }; "gitea pull request")]
+    #[test_case(ContinuousIntegrationProvider::GitHubActions, "github/merge_group", Expected {
+        build_directory: "",
+        head: "e7f8a9b0c1d2e3f4a5b6c7d8e9f0a1b2c3d4e5f6",
+        base: "1f2e3d4c5b6a79880f1e2d3c4b5a69788796a5b4",
+        pull_request: None,
+        draft: false,
+        author: Some("@github-merge-queue[bot]"),
+        repository: Some("doctavious/doctavious"),
+        scm_platform: Some(ScmPlatform::GitHub),
+    }; "github merge group")]
+    #[test_case(ContinuousIntegrationProvider::GitHubActions, "github/pull_request", Expected {
+        build_directory: "",
+        head: "7a6b5c4d3e2f1a0b9c8d7e6f5a4b3c2d1e0f9a8b",
+        base: "1f2e3d4c5b6a79880f1e2d3c4b5a69788796a5b4",
+        pull_request: Some("128"),
+        draft: true,
+        author: Some("@octocat"),
+        repository: Some("doctavious/doctavious"),
+        scm_platform: Some(ScmPlatform::GitHub),
+    }; "github pull request")]
+    #[test_case(ContinuousIntegrationProvider::GitHubActions, "github/push", Expected {
+        build_directory: "",
+        head: "c0ffee254729296a45a3885639ac7e10f9d54979",
+        base: "5e4d3c2b1a0f9e8d7c6b5a4f3e2d1c0b9a8f7e6d",
+        pull_request: None,
+        draft: false,
+        author: Some("@octocat"),
+        repository: Some("doctavious/doctavious"),
+        scm_platform: Some(ScmPlatform::GitHub),
+    }; "github push")]
+    #[test_case(ContinuousIntegrationProvider::GitLab, "gitlab/merge_request", Expected {
+        build_directory: "",
+        head: "96a5b4c3d2e1f00f1e2d3c4b5a6978877a6b5c4d",
+        base: "origin/main",
+        pull_request: Some("12"),
+        draft: true,
+        author: Some("@jane"),
+        repository: Some("doctavious/tools/doctavious"),
+        scm_platform: Some(ScmPlatform::GitLab),
+    }; "gitlab merge request")]
+    #[test_case(ContinuousIntegrationProvider::GitLab, "gitlab/push", Expected {
+        build_directory: "",
+        head: "5a69788796a5b4c3d2e1f00f1e2d3c0f1e2d3c4b",
+        base: "1e2d3c4b5a69788796a5b4c3d2e1f00f1e2d3c0f",
+        pull_request: None,
+        draft: false,
+        author: Some("@jane"),
+        repository: Some("doctavious/tools/doctavious"),
+        scm_platform: Some(ScmPlatform::GitLab),
+    }; "gitlab push")]
+    fn context_from_webhook(
+        provider: ContinuousIntegrationProvider,
+        fixture: &str,
+        expected: Expected,
+    ) {
+        let data = fs::read_to_string(format!("tests/fixtures/events/{fixture}.json")).unwrap();
+        let context = provider.context_from_webhook(&data).unwrap();
+
+        assert_eq!(provider, context.provider);
+        assert!(!context.is_ci);
+        assert_eq!(
+            PathBuf::from(expected.build_directory),
+            context.build_directory
+        );
+        assert_eq!(expected.head, context.head);
+        assert_eq!(Some(expected.head), context.commit.as_deref());
+        assert_eq!(expected.base, context.base);
+        assert_eq!(expected.pull_request.is_some(), context.is_pull_request);
+        assert_eq!(expected.pull_request, context.pull_request.as_deref());
+        assert_eq!(expected.draft, context.draft);
+        assert_eq!(expected.author, context.author.as_deref());
+        assert_eq!(expected.repository, context.repository.as_deref());
+        assert_eq!(expected.scm_platform, context.scm_platform);
+    }
+
+    #[test]
+    fn should_not_support_webhooks_of_standalone_providers() {
+        assert!(matches!(
+            ContinuousIntegrationProvider::Jenkins.context_from_webhook("{}"),
+            Err(ContinuousIntegrationError::UnsupportedWebhook(
+                ContinuousIntegrationProvider::Jenkins
+            ))
+        ));
+    }
+}
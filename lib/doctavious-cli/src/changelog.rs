@@ -1,2 +1,4 @@
 pub mod init;
+pub mod migrate;
+pub mod preview;
 pub mod release;
@@ -1,7 +1,60 @@
 use std::path::Path;
 
-use crate::errors::CliResult;
+use changelog::settings::ChangelogSettings;
+use changelog::templates::ChangelogTemplate;
 
-pub fn init(cwd: &Path) -> CliResult<()> {
-    Ok(())
+use crate::errors::{CliResult, DoctaviousCliError};
+use crate::settings::{SettingErrors, load_settings, persist_settings};
+
+/// Init changelog settings using one of the built-in templates
+pub fn init(cwd: &Path, template: ChangelogTemplate) -> CliResult<()> {
+    init_with_settings(cwd, template.settings())
+}
+
+pub(crate) fn init_with_settings(cwd: &Path, changelog: ChangelogSettings) -> CliResult<()> {
+    let mut settings = load_settings(cwd)?;
+    if settings.changelog.is_some() {
+        return Err(DoctaviousCliError::SettingError(
+            SettingErrors::AlreadyInitialized("changelog".to_string()),
+        ));
+    }
+
+    settings.changelog = Some(changelog);
+    persist_settings(cwd, &settings)
+}
+
+#[cfg(test)]
+mod tests {
+    use changelog::templates::ChangelogTemplate;
+    use strum::IntoEnumIterator;
+    use tempfile::TempDir;
+
+    use crate::changelog::init::init;
+    use crate::settings::load_settings;
+
+    #[test]
+    fn should_init_valid_settings_for_all_templates() {
+        for template in ChangelogTemplate::iter() {
+            let dir = TempDir::new().unwrap();
+            init(dir.path(), template).unwrap();
+
+            let settings = load_settings(dir.path()).unwrap().changelog.unwrap();
+            assert_eq!(
+                template.template_settings().body,
+                settings.template.body,
+                "{template} body should be embedded in settings"
+            );
+            assert_eq!(
+                template.settings().commit.group_parsers.unwrap().len(),
+                settings.commit.group_parsers.unwrap().len()
+            );
+        }
+    }
+
+    #[test]
+    fn should_not_init_twice() {
+        let dir = TempDir::new().unwrap();
+        init(dir.path(), ChangelogTemplate::default()).unwrap();
+        assert!(init(dir.path(), ChangelogTemplate::Github).is_err());
+    }
 }
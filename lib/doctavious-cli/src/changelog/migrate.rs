@@ -0,0 +1,54 @@
+use std::fs;
+use std::path::Path;
+
+use changelog::cliff;
+
+use crate::changelog::init::init_with_settings;
+use crate::errors::CliResult;
+
+/// Init changelog settings from an existing git-cliff configuration file.
+/// Returns warnings for any settings that could not be migrated as-is.
+pub fn migrate(cwd: &Path, cliff_config: &Path) -> CliResult<Vec<String>> {
+    let content = fs::read_to_string(cwd.join(cliff_config))?;
+    let migration = cliff::migrate(&content)?;
+    init_with_settings(cwd, migration.settings)?;
+    Ok(migration.warnings)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use tempfile::TempDir;
+
+    use crate::changelog::migrate::migrate;
+    use crate::settings::load_settings;
+
+    #[test]
+    fn should_migrate_cliff_config() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("cliff.toml"),
+            r#"
+[changelog]
+body = "{{ version }}"
+
+[git]
+commit_parsers = [{ message = "^feat", group = "Features" }]
+tag_pattern = "v[0-9]*"
+"#,
+        )
+        .unwrap();
+
+        let warnings = migrate(dir.path(), Path::new("cliff.toml")).unwrap();
+        assert!(!warnings.is_empty());
+
+        let settings = load_settings(dir.path()).unwrap().changelog.unwrap();
+        assert_eq!("{{ version }}", settings.template.body);
+        assert_eq!(
+            Some(vec!["v[0-9]*".to_string()]),
+            settings.release.tag_patterns
+        );
+    }
+}
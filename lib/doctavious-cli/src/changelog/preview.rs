@@ -0,0 +1,120 @@
+use std::path::Path;
+
+use changelog::changelog::Changelog;
+use changelog::commits::ScmTaggedCommits;
+use changelog::remote::RemoteRepository;
+use scm::commit::ScmCommitRange;
+use scm::drivers::{Scm, ScmRepository};
+use tracing::warn;
+
+use crate::errors::CliResult;
+use crate::settings::load_settings;
+
+/// Renders the unreleased changelog entries for the commits within `range`, such as the commits
+/// of a pull request, without writing any files.
+///
+/// Returns `None` when the repository doesn't have changelog settings or there are no commits.
+pub fn preview(cwd: &Path, range: &ScmCommitRange) -> CliResult<Option<String>> {
+    let settings = load_settings(cwd)?;
+    let Some(changelog_settings) = settings.changelog else {
+        return Ok(None);
+    };
+
+    let scm = Scm::discover_with_settings(cwd, &settings.scm.unwrap_or_default())?;
+    let commits = scm.commits(
+        Some(range),
+        None,
+        None,
+        changelog_settings.commit.limit_commits,
+    )?;
+    if commits.is_empty() {
+        return Ok(None);
+    }
+
+    let tagged_commits = ScmTaggedCommits {
+        repository: cwd
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string(),
+        tag: None,
+        commits,
+        timestamp: None,
+    };
+
+    let mut changelog = Changelog::new(vec![tagged_commits], changelog_settings)?;
+    if changelog.remote().is_none() {
+        let remote = scm
+            .remote_url()?
+            .as_deref()
+            .and_then(RemoteRepository::from_url);
+        changelog = changelog.with_remote(remote);
+    }
+
+    for diagnostic in changelog.diagnostics() {
+        warn!("{diagnostic}");
+    }
+
+    let mut out = Vec::new();
+    changelog.generate(&mut out)?;
+    Ok(Some(String::from_utf8_lossy(&out).to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use changelog::templates::ChangelogTemplate;
+    use git2::{Repository, Signature};
+    use scm::commit::ScmCommitRange;
+    use tempfile::TempDir;
+
+    use crate::changelog::init::init;
+    use crate::changelog::preview::preview;
+
+    fn commit(repository: &Repository, path: &str, message: &str) -> git2::Oid {
+        let workdir = repository.workdir().unwrap();
+        fs::write(workdir.join(path), message).unwrap();
+
+        let mut index = repository.index().unwrap();
+        index.add_path(Path::new(path)).unwrap();
+        index.write().unwrap();
+        let tree = repository.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = Signature::now("test", "test@doctavious.com").unwrap();
+        let parents = repository
+            .head()
+            .ok()
+            .and_then(|h| h.peel_to_commit().ok())
+            .into_iter()
+            .collect::<Vec<_>>();
+        repository
+            .commit(
+                Some("HEAD"),
+                &signature,
+                &signature,
+                message,
+                &tree,
+                &parents.iter().collect::<Vec<_>>(),
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn should_preview_commits_within_range() {
+        let dir = TempDir::new().unwrap();
+        let repository = Repository::init(dir.path()).unwrap();
+        let base = commit(&repository, "README.md", "feat: initial commit");
+        commit(&repository, "a.rs", "feat: add a");
+        let head = commit(&repository, "b.rs", "fix: handle b");
+
+        let range: ScmCommitRange = format!("{base}..{head}").parse().unwrap();
+        assert_eq!(None, preview(dir.path(), &range).unwrap());
+
+        init(dir.path(), ChangelogTemplate::KeepAChangelog).unwrap();
+        let rendered = preview(dir.path(), &range).unwrap().unwrap();
+        assert!(rendered.contains("add a"));
+        assert!(rendered.contains("handle b"));
+        assert!(!rendered.contains("initial commit"));
+    }
+}
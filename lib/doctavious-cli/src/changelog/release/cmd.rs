@@ -6,12 +6,14 @@ use changelog::changelog::Changelog;
 use changelog::commits::ScmTaggedCommits;
 use changelog::entries::ChangelogEntry;
 use changelog::errors::ChangelogErrors::ChangelogError;
+use changelog::remote::RemoteRepository;
 use changelog::settings::{ChangelogCommitSort, ChangelogSettings, CommitParser};
 use doctavious_std::regex::convert_to_regex;
 use indexmap::IndexMap;
 use regex::Regex;
-use scm::commit::{ScmCommit, ScmCommitRange, ScmTag};
+use scm::commit::{ScmCommit, ScmCommitRange, ScmRevision, ScmTag};
 use scm::drivers::{Scm, ScmRepository};
+use scm::settings::ScmSettings;
 use somever::Somever;
 use tracing::warn;
 
@@ -25,14 +27,16 @@ pub fn release(mut options: ChangelogReleaseOptions) -> CliResult<()> {
     let settings_path = options.config_path.unwrap_or(options.cwd);
     let settings: Settings = load_settings(settings_path)?;
     let changelog_settings = settings.changelog.unwrap_or_default();
+    let scm_settings = settings.scm.unwrap_or_default();
 
-    release_with_settings(options, changelog_settings)
+    release_with_settings(options, changelog_settings, &scm_settings)
 }
 
 // TODO: where to handle multiple changelog files
 fn release_with_settings(
     mut options: ChangelogReleaseOptions,
     mut changelog_settings: ChangelogSettings,
+    scm_settings: &ScmSettings,
 ) -> CliResult<()> {
     if let Some(prepend) = options.prepend {
         options.prepend = Some(options.cwd.join(prepend));
@@ -61,8 +65,15 @@ fn release_with_settings(
     }
 
     let mut tagged_commits = Vec::<ScmTaggedCommits>::new();
+    let mut remote = None;
     for repository in options.repositories.as_ref().unwrap() {
-        let scm = Scm::get(&repository)?;
+        let scm = Scm::discover_with_settings(repository, scm_settings)?;
+        if remote.is_none() {
+            remote = scm
+                .remote_url()?
+                .as_deref()
+                .and_then(RemoteRepository::from_url);
+        }
 
         // load ignore_files (new line commits to skip)
         let mut ignore_commits = Vec::new();
@@ -138,7 +149,13 @@ fn release_with_settings(
             // if tag is provided use as latest tag
             let mut timestamp = None;
             let tag = if let Some(ref tag) = options.tag {
-                let mut scm_tag = scm.get_tag(tag);
+                // the tag typically doesn't exist yet as it's for the upcoming release
+                let mut scm_tag = scm.get_tag(tag)?.unwrap_or_else(|| ScmTag {
+                    id: None,
+                    name: tag.to_string(),
+                    message: None,
+                    timestamp: 0,
+                });
                 let now = SystemTime::now()
                     .duration_since(UNIX_EPOCH)?
                     .as_secs()
@@ -172,6 +189,14 @@ fn release_with_settings(
 
     // Process commits and releases for the changelog.
     let mut changelog = Changelog::new(tagged_commits, changelog_settings)?;
+    // configured remote settings take precedence over the repository's remote
+    if changelog.remote().is_none() {
+        changelog = changelog.with_remote(remote);
+    }
+
+    for diagnostic in changelog.diagnostics() {
+        warn!("{diagnostic}");
+    }
 
     if let Some(path) = &options.prepend {
         let previous_changelog = fs::read_to_string(&path)?;
@@ -181,12 +206,60 @@ fn release_with_settings(
 
     if let Some(path) = &options.output {
         println!("{:?}", path);
-        let mut output = File::create(path)?;
-        changelog.generate(&mut output)?;
+        if options.update {
+            let existing = path
+                .is_file()
+                .then(|| fs::read_to_string(path))
+                .transpose()?;
+            let mut updated = Vec::new();
+            for conflict in changelog.update(existing.as_deref(), &mut updated)? {
+                warn!("{conflict}");
+            }
+            fs::write(path, updated)?;
+        } else {
+            let mut output = File::create(path)?;
+            changelog.generate(&mut output)?;
+        }
     } else if options.prepend.is_none() {
         changelog.generate(&mut io::stdout())?;
     }
 
+    if options.tag_commit {
+        tag_release(&options, &changelog, scm_settings)?;
+    }
+
+    Ok(())
+}
+
+/// Commits the changelog file and creates an annotated tag for the release, with the rendered
+/// release as its message, and pushes the tag.
+fn tag_release(
+    options: &ChangelogReleaseOptions,
+    changelog: &Changelog,
+    scm_settings: &ScmSettings,
+) -> CliResult<()> {
+    let Some(tag) = options.tag.as_deref() else {
+        return Err(DoctaviousCliError::ChangelogError(ChangelogError(
+            String::from("A tag is required to tag the release"),
+        )));
+    };
+
+    let Some(path) = options.output.as_ref().or(options.prepend.as_ref()) else {
+        return Err(DoctaviousCliError::ChangelogError(ChangelogError(
+            String::from("The changelog must be written to a file to tag the release"),
+        )));
+    };
+
+    let path = path.canonicalize()?;
+    let scm = Scm::discover_with_settings(path.parent().unwrap_or(options.cwd), scm_settings)?;
+    scm.write(&path, &format!("chore(release): {tag}"), None)?;
+
+    let message = changelog
+        .render_release(tag)?
+        .unwrap_or_else(|| format!("Release {tag}"));
+    scm.create_tag(tag, None, Some(message.trim()), None)?;
+    scm.push_tag(tag, None)?;
+
     Ok(())
 }
 
@@ -236,7 +309,10 @@ fn determine_commit_range(
                         scm.last_commit()?.and_then(|c| Some(c.id)),
                         tags.get_index(0).map(|(k, _)| k),
                     ) {
-                        commit_range = Some(ScmCommitRange(tag1, Some(tag2.to_string())));
+                        commit_range = Some(ScmCommitRange::new(
+                            ScmRevision::Ref(tag1),
+                            Some(ScmRevision::Ref(tag2.to_string())),
+                        ));
                     }
                 } else {
                     let mut tag_index = tags.len() - 2;
@@ -267,27 +343,22 @@ fn determine_commit_range(
                         tags.get_index(tag_index).map(|(k, _)| k),
                         tags.get_index(tag_index + 1).map(|(k, _)| k),
                     ) {
-                        commit_range =
-                            Some(ScmCommitRange(tag1.to_string(), Some(tag2.to_string())));
+                        commit_range = Some(ScmCommitRange::new(
+                            ScmRevision::Ref(tag1.to_string()),
+                            Some(ScmRevision::Ref(tag2.to_string())),
+                        ));
                     }
                 }
             }
             ChangelogRange::Unreleased => {
                 if let Some(last_tag) = tags.last().map(|(k, _)| k) {
-                    commit_range = Some(ScmCommitRange(last_tag.to_string(), None));
-                }
-            }
-            ChangelogRange::Range(r) => {
-                if let Some(parts) = r.to_string().split_once("..") {
-                    commit_range = Some(ScmCommitRange(
-                        parts.0.to_string(),
-                        Some(parts.1.to_string()),
-                    ))
-                } else {
-                    // TODO: this should probably be an error
-                    warn!("{}", format!("Unable to parse changelog range {r}"))
+                    commit_range = Some(ScmCommitRange::new(
+                        ScmRevision::Ref(last_tag.to_string()),
+                        None,
+                    ));
                 }
             }
+            ChangelogRange::Range(r) => commit_range = Some(r.clone()),
         }
     };
 
@@ -366,7 +437,9 @@ mod tests {
         ChangelogCommitSettings, ChangelogCommitSort, ChangelogReleaseConfiguration,
         ChangelogSettings, CommitStyleSettings, TemplateSettings,
     };
+    use git2::Repository;
     use scm::drivers::git::{GitScmRepository, TagSort};
+    use scm::settings::ScmSettings;
     use somever::VersioningScheme;
     use tempfile::TempDir;
 
@@ -385,6 +458,7 @@ mod tests {
                 output: Some(PathBuf::from("./test_changelog.md")),
                 output_type: ChangelogOutputType::Single,
                 prepend: None,
+                update: false,
                 range: None,
                 include_paths: None,
                 exclude_paths: None,
@@ -395,6 +469,7 @@ mod tests {
                 skip_tag_patterns: None,
                 ignore_tag_patterns: None,
                 tag: None,
+                tag_commit: false,
                 strip: None,
             },
             ChangelogSettings {
@@ -440,6 +515,7 @@ mod tests {
                 version_scheme: VersioningScheme::Semver,
                 version_suffixes: None,
             },
+            &ScmSettings::default(),
         )
         .unwrap();
     }
@@ -469,6 +545,7 @@ mod tests {
             repositories: None,
             output_type: Default::default(),
             prepend: None,
+            update: false,
             range: None,
             include_paths: None,
             exclude_paths: None,
@@ -478,10 +555,11 @@ mod tests {
             skip_tag_patterns: None,
             ignore_tag_patterns: None,
             tag: None,
+            tag_commit: false,
             tag_sort: None,
         };
 
-        release_with_settings(options, settings).unwrap();
+        release_with_settings(options, settings, &ScmSettings::default()).unwrap();
 
         let changelog = fs::read_to_string(dir.path().join("changelog.md")).unwrap();
         assert!(!changelog.contains("Changelog Header"));
@@ -512,6 +590,7 @@ mod tests {
             output: Some(dir.path().join("changelog.md")),
             output_type: Default::default(),
             prepend: None,
+            update: false,
             range: None,
             include_paths: None,
             exclude_paths: None,
@@ -521,11 +600,12 @@ mod tests {
             skip_tag_patterns: None,
             ignore_tag_patterns: None,
             tag: None,
+            tag_commit: false,
             tag_sort: None,
             strip: Some(StrippableChangelogSection::Footer),
         };
 
-        release_with_settings(options, settings).unwrap();
+        release_with_settings(options, settings, &ScmSettings::default()).unwrap();
         let changelog = fs::read_to_string(dir.path().join("changelog.md")).unwrap();
         assert!(!changelog.contains("Generated by Doctavious"));
         assert!(changelog.contains("Changelog Header"));
@@ -546,6 +626,7 @@ mod tests {
                 output: Some(dir.path().join("changelog.md")),
                 output_type: Default::default(),
                 prepend: None,
+                update: false,
                 range: None,
                 include_paths: None,
                 exclude_paths: None,
@@ -555,6 +636,7 @@ mod tests {
                 skip_tag_patterns: None,
                 ignore_tag_patterns: None,
                 tag: None,
+                tag_commit: false,
                 tag_sort: None,
                 strip: Some(StrippableChangelogSection::All),
             },
@@ -568,6 +650,7 @@ mod tests {
                 },
                 ..Default::default()
             },
+            &ScmSettings::default(),
         )
         .unwrap();
 
@@ -576,6 +659,75 @@ mod tests {
         assert!(!changelog.contains("Generated by Doctavious"));
     }
 
+    #[test]
+    fn should_commit_and_tag_release() {
+        let dir = TempDir::new().unwrap();
+        let remote_path = dir.path().join("remote.git");
+        Repository::init_bare(&remote_path).unwrap();
+
+        let cwd = dir.path().join("repo");
+        let repository = Repository::init(&cwd).unwrap();
+        let mut config = repository.config().unwrap();
+        config.set_str("user.name", "test").unwrap();
+        config.set_str("user.email", "test@doctavious.com").unwrap();
+        repository
+            .remote("origin", &format!("file://{}", remote_path.display()))
+            .unwrap();
+
+        fs::write(cwd.join("README.md"), "readme").unwrap();
+        let scm = GitScmRepository::new(&cwd).unwrap();
+        scm.am("feat: add readme", None).unwrap();
+
+        let options = ChangelogReleaseOptions {
+            cwd: &cwd,
+            config_path: None,
+            repositories: None,
+            output: Some(cwd.join("CHANGELOG.md")),
+            output_type: Default::default(),
+            prepend: None,
+            update: false,
+            range: None,
+            include_paths: None,
+            exclude_paths: None,
+            commit_sort: Default::default(),
+            ignore_commits: None,
+            tag_patterns: None,
+            skip_tag_patterns: None,
+            ignore_tag_patterns: None,
+            tag: Some("v1.0.0".to_string()),
+            tag_commit: true,
+            tag_sort: None,
+            strip: None,
+        };
+
+        let settings = ChangelogSettings {
+            template: TemplateSettings {
+                header: Some("Changelog Header".to_string()),
+                body: "{% for release in releases %}{% for commit in release.commits %}\
+                    - {{ commit.message }}\n\
+                    {% endfor %}{% endfor %}"
+                    .to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        release_with_settings(options, settings, &ScmSettings::default()).unwrap();
+
+        let head = repository.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(Some("chore(release): v1.0.0"), head.message());
+        assert!(head.tree().unwrap().get_name("CHANGELOG.md").is_some());
+
+        let remote = Repository::open_bare(&remote_path).unwrap();
+        let tag = remote
+            .revparse_single("refs/tags/v1.0.0")
+            .unwrap()
+            .into_tag()
+            .unwrap();
+        assert_eq!(head.id(), tag.target_id());
+        assert_eq!(Some("- feat: add readme"), tag.message().map(str::trim));
+    }
+
     // TODO: test multiple repositories - verify name is included
 
     // TODO: test output individual files
@@ -4,15 +4,10 @@ use std::str::FromStr;
 use changelog::changelog::ChangelogOutputType;
 use changelog::settings::ChangelogCommitSort;
 use glob::Pattern;
-use lazy_static::lazy_static;
-use regex::Regex;
+use scm::commit::ScmCommitRange;
 use scm::drivers::git::TagSort;
 use serde::{Deserialize, Serialize};
-use strum::{Display, EnumIter, EnumString, VariantNames};
-
-lazy_static! {
-    static ref CHANGELOG_RANGE_REGEX: Regex = Regex::new(r"^.+\.\..+$").unwrap();
-}
+use strum::{Display, EnumString, VariantNames};
 
 // TODO: replace cwd with repositories
 // TODO: ignore commits will live in .doctavious/changelog/.commitsignore
@@ -27,6 +22,10 @@ pub struct ChangelogReleaseOptions<'a> {
     pub output: Option<PathBuf>,
     pub output_type: ChangelogOutputType,
     pub prepend: Option<PathBuf>,
+
+    /// Update the output changelog in place, preserving release sections edited by hand
+    pub update: bool,
+
     // does range? I feel like you could make a case either way
     pub range: Option<ChangelogRange>,
     pub include_paths: Option<Vec<Pattern>>,
@@ -47,6 +46,10 @@ pub struct ChangelogReleaseOptions<'a> {
     // this doesnt below in the configuration file
     pub tag: Option<String>,
 
+    /// Commit the changelog and create an annotated tag, with the release as the message, for
+    /// `tag` then push both
+    pub tag_commit: bool,
+
     // TODO: this needs to fit into Somever sorting
     pub tag_sort: Option<TagSort>,
 
@@ -85,12 +88,12 @@ pub enum BumpType {
     Patch,
 }
 
-#[derive(Clone, Debug, Display, EnumIter, VariantNames, PartialEq)]
+#[derive(Clone, Debug, Display, VariantNames, PartialEq)]
 pub enum ChangelogRange {
     Current,
     Latest,
     Unreleased,
-    Range(String),
+    Range(ScmCommitRange),
 }
 
 impl FromStr for ChangelogRange {
@@ -101,17 +104,12 @@ impl FromStr for ChangelogRange {
             "current" => Ok(ChangelogRange::Current),
             "latest" => Ok(ChangelogRange::Latest),
             "unreleased" => Ok(ChangelogRange::Unreleased),
-            _ => {
-                if CHANGELOG_RANGE_REGEX.is_match(s) {
-                    Ok(ChangelogRange::Range(s.to_string()))
-                } else {
-                    Err(
-                        "Invalid changelog range. Value should be current, latest, unreleased, or \
-                        in the format of <START>..<END>"
-                            .to_string(),
-                    )
-                }
-            }
+            _ => s.parse::<ScmCommitRange>().map(ChangelogRange::Range).map_err(|e| {
+                format!(
+                    "{e}. Value should be current, latest, unreleased, or a commit range such as \
+                    <START>..<END>"
+                )
+            }),
         }
     }
 }
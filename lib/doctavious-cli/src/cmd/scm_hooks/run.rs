@@ -1,5 +1,6 @@
 use std::path::{Path, PathBuf};
 
+use scm::commit::ScmCommitRange;
 use scm::drivers::{Scm, ScmRepository};
 use scm::errors::ScmError;
 use thiserror::Error;
@@ -22,6 +23,10 @@ pub enum DoctaviousScmHookRunError {
 #[remain::sorted]
 pub enum ScmHookRunFiles {
     All,
+
+    /// Files changed within the commit range
+    Range(ScmCommitRange),
+
     Specific(Vec<PathBuf>),
 }
 
@@ -68,6 +73,7 @@ pub fn run(
         None => vec![],
         Some(f) => match f {
             ScmHookRunFiles::All => scm.all_files()?,
+            ScmHookRunFiles::Range(range) => scm.diff_paths(Some(&range))?,
             ScmHookRunFiles::Specific(files) => files,
         },
     };
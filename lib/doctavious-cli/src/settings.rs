@@ -9,7 +9,9 @@ use changelog::settings::{
 use directories::ProjectDirs;
 use indexmap::IndexMap;
 use markup::MarkupFormat;
+use scm::discovery::ScmRoot;
 use scm::hooks::ScmHook;
+use scm::settings::ScmSettings;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use tracing::warn;
@@ -146,6 +148,9 @@ pub struct Settings {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub changelog: Option<ChangelogSettings>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scm: Option<ScmSettings>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename(serialize = "scmhook"))]
     #[serde(alias = "scmhook")]
@@ -397,8 +402,31 @@ impl Settings {
     // }
 }
 
+/// Finds the settings file for `cwd`.
+///
+/// Walks up from `cwd` to the root of the repository it is within and returns the first existing
+/// settings file. When there is none the settings file is placed at the repository root, or at
+/// `cwd` if it's not within a repository.
 pub(crate) fn get_settings_file(cwd: &Path) -> PathBuf {
-    cwd.join(DEFAULT_CONFIG_DIR).join(DEFAULT_CONFIG_NAME)
+    let config_file_path = Config::config_file_path();
+    let scm_root = match ScmRoot::find(cwd) {
+        Ok(scm_root) if !scm_root.bare => scm_root,
+        _ => return cwd.join(config_file_path),
+    };
+
+    let mut dir = scm_root.root.join(&scm_root.relative_cwd);
+    loop {
+        let settings_file = dir.join(&config_file_path);
+        if settings_file.is_file() {
+            return settings_file;
+        }
+
+        if dir == scm_root.root || !dir.pop() {
+            break;
+        }
+    }
+
+    scm_root.root.join(config_file_path)
 }
 
 pub fn load_settings(path: &Path) -> CliResult<Settings> {
@@ -477,3 +505,42 @@ pub fn init_dir(dir: &Path) -> CliResult<()> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use scm::drivers::Scm;
+    use tempfile::TempDir;
+
+    use crate::settings::{Config, get_settings_file, load_settings};
+
+    #[test]
+    fn should_load_settings_from_repository_subdirectory() {
+        let dir = TempDir::new().unwrap();
+        git2::Repository::init(dir.path()).unwrap();
+        let settings_file = dir.path().join(Config::config_file_path());
+        fs::create_dir_all(settings_file.parent().unwrap()).unwrap();
+        fs::write(&settings_file, "[adr]\ndir = \"decisions\"\n").unwrap();
+
+        let nested = dir.path().join("docs").join("adr");
+        fs::create_dir_all(&nested).unwrap();
+        assert!(Scm::get(&nested).is_ok());
+
+        let settings = load_settings(&nested).unwrap();
+        assert_eq!("decisions", settings.adr_settings.unwrap().dir.unwrap());
+        assert_eq!(
+            fs::canonicalize(&settings_file).unwrap(),
+            get_settings_file(&nested)
+        );
+    }
+
+    #[test]
+    fn should_default_settings_file_to_cwd_outside_of_repository() {
+        let dir = TempDir::new().unwrap();
+        assert_eq!(
+            dir.path().join(Config::config_file_path()),
+            get_settings_file(dir.path())
+        );
+    }
+}
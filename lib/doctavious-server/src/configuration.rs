@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use figment::Figment;
 use figment::providers::{Env, Format, Json, Toml};
 use opendal::Operator;
@@ -78,6 +80,40 @@ pub enum Compression {
     None,
 }
 
+/// Secret used to verify GitHub webhook signatures
+pub const GITHUB_WEBHOOK_SECRET: &str = "DOCTAVIOUS_GITHUB_WEBHOOK_SECRET";
+
+/// Token GitLab webhooks are expected to send
+pub const GITLAB_WEBHOOK_TOKEN: &str = "DOCTAVIOUS_GITLAB_WEBHOOK_TOKEN";
+
+/// Directory repositories are checked out to for jobs triggered by webhooks
+pub const WEBHOOK_WORKSPACE: &str = "DOCTAVIOUS_WEBHOOK_WORKSPACE";
+
+// TODO: move into Configuration once it's loaded via figment
+pub struct WebhookConfiguration {
+    /// Webhooks of a platform without a secret are rejected
+    pub github_secret: Option<String>,
+    pub gitlab_token: Option<String>,
+    pub workspace: PathBuf,
+}
+
+impl WebhookConfiguration {
+    pub fn from_env() -> Self {
+        let var = |key| {
+            std::env::var(key)
+                .ok()
+                .filter(|v: &String| !v.trim().is_empty())
+        };
+        Self {
+            github_secret: var(GITHUB_WEBHOOK_SECRET),
+            gitlab_token: var(GITLAB_WEBHOOK_TOKEN),
+            workspace: var(WEBHOOK_WORKSPACE)
+                .map(PathBuf::from)
+                .unwrap_or_else(|| std::env::temp_dir().join("doctavious")),
+        }
+    }
+}
+
 // TODO: honeycomb config: https://github.com/vectordotdev/vector/blob/fa8a55385dd391aa2429c3f2e9821198c364c6a0/src/sinks/honeycomb.rs
 
 // https://github.com/vectordotdev/vector/blob/fa8a55385dd391aa2429c3f2e9821198c364c6a0/src/sinks/opendal_common.rs
@@ -1,5 +1,8 @@
 pub mod changelog;
 pub mod deployment;
 pub mod design_decisions;
+pub mod github;
+pub mod gitlab;
 pub mod openapi;
 pub mod projects;
+pub mod webhooks;
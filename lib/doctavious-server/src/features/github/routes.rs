@@ -1,14 +1,140 @@
-use axum::extract::Path;
-use axum::http::StatusCode;
-use axum::routing::{get, post};
-use axum::{Json, Router};
+use axum::Router;
+use axum::body::Bytes;
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode};
+use axum::routing::post;
+use continuous_integration::ContinuousIntegrationProvider;
+use tracing::{debug, warn};
 
-// TODO: What should these routes look like?
+use crate::features::webhooks::jobs::PullRequestJob;
+use crate::features::webhooks::{WebhookState, jobs, verify_signature};
 
-pub fn get_routes() -> Router {
+/// Pull request actions which change its commits or make it ready for review
+const PULL_REQUEST_ACTIONS: [&str; 4] = ["opened", "ready_for_review", "reopened", "synchronize"];
+
+pub fn get_routes<S>(state: WebhookState) -> Router<S> {
     Router::new()
         .route("/external/github", post(webhook))
+        .with_state(state)
 }
 
-async fn webhook() {}
+// https://docs.github.com/en/webhooks/webhook-events-and-payloads
+async fn webhook(State(state): State<WebhookState>, headers: HeaderMap, body: Bytes) -> StatusCode {
+    let Some(secret) = state.configuration.github_secret.as_deref() else {
+        warn!("received GitHub webhook but no secret is configured");
+        return StatusCode::UNAUTHORIZED;
+    };
+
+    let header = |name| headers.get(name).and_then(|v| v.to_str().ok());
+    if !verify_signature(
+        secret,
+        &body,
+        header("X-Hub-Signature-256").unwrap_or_default(),
+    ) {
+        return StatusCode::UNAUTHORIZED;
+    }
+
+    match header("X-GitHub-Event") {
+        Some("pull_request") => {}
+        Some(event) => {
+            debug!("ignoring GitHub {event} event");
+            return StatusCode::OK;
+        }
+        None => return StatusCode::BAD_REQUEST,
+    }
+
+    let Ok(payload) = serde_json::from_slice::<serde_json::Value>(&body) else {
+        return StatusCode::BAD_REQUEST;
+    };
+
+    let action = payload["action"].as_str().unwrap_or_default();
+    if !PULL_REQUEST_ACTIONS.contains(&action) {
+        debug!("ignoring GitHub pull request {action} action");
+        return StatusCode::OK;
+    }
+
+    let (Some(clone_url), Ok(context)) = (
+        payload
+            .pointer("/repository/clone_url")
+            .and_then(|u| u.as_str()),
+        ContinuousIntegrationProvider::GitHubActions
+            .context_from_webhook(&String::from_utf8_lossy(&body)),
+    ) else {
+        return StatusCode::BAD_REQUEST;
+    };
+
+    jobs::spawn(
+        state,
+        PullRequestJob {
+            context,
+            clone_url: clone_url.to_string(),
+        },
+    );
+
+    StatusCode::ACCEPTED
+}
 
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use hmac::{Hmac, Mac};
+    use sha2::Sha256;
+    use tower::ServiceExt;
+
+    use crate::configuration::WebhookConfiguration;
+    use crate::features::github::get_routes;
+    use crate::features::webhooks::WebhookState;
+
+    fn sign(body: &str) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"secret").unwrap();
+        mac.update(body.as_bytes());
+        format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+    }
+
+    async fn send(event: &str, body: &str, signature: &str) -> StatusCode {
+        let state = WebhookState::new(WebhookConfiguration {
+            github_secret: Some("secret".to_string()),
+            gitlab_token: None,
+            workspace: std::env::temp_dir(),
+        });
+
+        get_routes::<()>(state)
+            .oneshot(
+                Request::post("/external/github")
+                    .header("X-GitHub-Event", event)
+                    .header("X-Hub-Signature-256", signature)
+                    .body(Body::from(body.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap()
+            .status()
+    }
+
+    #[tokio::test]
+    async fn should_reject_invalid_signatures() {
+        let body = r#"{"zen":"Keep it logically awesome."}"#;
+        assert_eq!(StatusCode::OK, send("ping", body, &sign(body)).await);
+        assert_eq!(
+            StatusCode::UNAUTHORIZED,
+            send("ping", body, &sign("{}")).await
+        );
+        assert_eq!(StatusCode::UNAUTHORIZED, send("ping", body, "").await);
+    }
+
+    #[tokio::test]
+    async fn should_ignore_pull_request_actions_without_changes() {
+        let body = r#"{"action":"labeled","number":1}"#;
+        assert_eq!(
+            StatusCode::OK,
+            send("pull_request", body, &sign(body)).await
+        );
+
+        let body = r#"{"action":"opened","number":1}"#;
+        assert_eq!(
+            StatusCode::BAD_REQUEST,
+            send("pull_request", body, &sign(body)).await
+        );
+    }
+}
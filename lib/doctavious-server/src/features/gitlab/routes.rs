@@ -0,0 +1,72 @@
+use axum::Router;
+use axum::body::Bytes;
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode};
+use axum::routing::post;
+use continuous_integration::ContinuousIntegrationProvider;
+use tracing::{debug, warn};
+
+use crate::features::webhooks::jobs::PullRequestJob;
+use crate::features::webhooks::{WebhookState, jobs, verify_token};
+
+pub fn get_routes<S>(state: WebhookState) -> Router<S> {
+    Router::new()
+        .route("/external/gitlab", post(webhook))
+        .with_state(state)
+}
+
+// https://docs.gitlab.com/user/project/integrations/webhook_events/
+async fn webhook(State(state): State<WebhookState>, headers: HeaderMap, body: Bytes) -> StatusCode {
+    let Some(token) = state.configuration.gitlab_token.as_deref() else {
+        warn!("received GitLab webhook but no token is configured");
+        return StatusCode::UNAUTHORIZED;
+    };
+
+    let header = |name| headers.get(name).and_then(|v| v.to_str().ok());
+    if !verify_token(token, header("X-Gitlab-Token").unwrap_or_default()) {
+        return StatusCode::UNAUTHORIZED;
+    }
+
+    match header("X-Gitlab-Event") {
+        Some("Merge Request Hook") => {}
+        Some(event) => {
+            debug!("ignoring GitLab {event}");
+            return StatusCode::OK;
+        }
+        None => return StatusCode::BAD_REQUEST,
+    }
+
+    let Ok(payload) = serde_json::from_slice::<serde_json::Value>(&body) else {
+        return StatusCode::BAD_REQUEST;
+    };
+
+    // updates without `oldrev` only change details such as the title or labels
+    let action = payload
+        .pointer("/object_attributes/action")
+        .and_then(|a| a.as_str())
+        .unwrap_or_default();
+    let pushed = payload.pointer("/object_attributes/oldrev").is_some();
+    if !(matches!(action, "open" | "reopen") || (action == "update" && pushed)) {
+        debug!("ignoring GitLab merge request {action} action");
+        return StatusCode::OK;
+    }
+
+    let (Some(clone_url), Ok(context)) = (
+        payload
+            .pointer("/project/git_http_url")
+            .and_then(|u| u.as_str()),
+        ContinuousIntegrationProvider::GitLab.context_from_webhook(&String::from_utf8_lossy(&body)),
+    ) else {
+        return StatusCode::BAD_REQUEST;
+    };
+
+    jobs::spawn(
+        state,
+        PullRequestJob {
+            context,
+            clone_url: clone_url.to_string(),
+        },
+    );
+
+    StatusCode::ACCEPTED
+}
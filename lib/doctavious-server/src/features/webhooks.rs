@@ -0,0 +1,86 @@
+pub mod jobs;
+
+use std::sync::Arc;
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use subtle::ConstantTimeEq;
+use tokio::sync::Mutex;
+
+use crate::configuration::WebhookConfiguration;
+
+#[derive(Clone)]
+pub struct WebhookState {
+    pub configuration: Arc<WebhookConfiguration>,
+    /// Jobs share checkouts so only one runs at a time
+    pub jobs: Arc<Mutex<()>>,
+}
+
+impl WebhookState {
+    pub fn new(configuration: WebhookConfiguration) -> Self {
+        Self {
+            configuration: Arc::new(configuration),
+            jobs: Arc::new(Mutex::new(())),
+        }
+    }
+}
+
+/// Verifies an `X-Hub-Signature-256` header, `sha256=` followed by the hex encoded HMAC of the
+/// body, which GitHub signs webhook payloads with.
+/// https://docs.github.com/en/webhooks/using-webhooks/validating-webhook-deliveries
+pub fn verify_signature(secret: &str, body: &[u8], signature: &str) -> bool {
+    let Some(signature) = signature
+        .strip_prefix("sha256=")
+        .and_then(|s| hex::decode(s).ok())
+    else {
+        return false;
+    };
+
+    let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(secret.as_bytes()) else {
+        return false;
+    };
+
+    mac.update(body);
+    mac.verify_slice(&signature).is_ok()
+}
+
+/// Verifies an `X-Gitlab-Token` header, which GitLab sends as is, in constant time
+pub fn verify_token(expected: &str, token: &str) -> bool {
+    expected.as_bytes().ct_eq(token.as_bytes()).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::features::webhooks::{verify_signature, verify_token};
+
+    #[test]
+    fn should_verify_signature() {
+        // example from GitHub's documentation
+        let signature = "sha256=757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17";
+
+        assert!(verify_signature(
+            "It's a Secret to Everybody",
+            b"Hello, World!",
+            signature
+        ));
+        assert!(!verify_signature(
+            "It's a Secret to Everybody",
+            b"Hello, World?",
+            signature
+        ));
+        assert!(!verify_signature("secret", b"Hello, World!", signature));
+        assert!(!verify_signature(
+            "It's a Secret to Everybody",
+            b"Hello, World!",
+            signature.trim_start_matches("sha256=")
+        ));
+        assert!(!verify_signature("secret", b"", "sha256=not-hex"));
+    }
+
+    #[test]
+    fn should_verify_token() {
+        assert!(verify_token("token", "token"));
+        assert!(!verify_token("token", "tokem"));
+        assert!(!verify_token("token", ""));
+    }
+}
@@ -0,0 +1,165 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use code_ownify::notify::CodeNotify;
+use continuous_integration::ContinuousIntegrationContext;
+use scm::commit::ScmCommitRange;
+use scm::drivers::ScmRepository;
+use scm::drivers::git::GitScmRepository;
+use scm::platforms::{ScmPlatform, ScmPlatformRepositoryBoundedClient};
+use tracing::{debug, error};
+
+use crate::features::webhooks::WebhookState;
+
+/// Marks the changelog preview comment so later runs update it
+const CHANGELOG_PREVIEW_TITLE: &str = "<!-- doctavious:changelog-preview -->";
+
+/// Pull request to run jobs, i.e. codenotify and changelog preview comments, against
+pub struct PullRequestJob {
+    pub context: ContinuousIntegrationContext,
+    /// URL the repository is cloned from
+    pub clone_url: String,
+}
+
+struct JobOutput {
+    codenotify_title: String,
+    codenotify_message: String,
+    has_subscribers: bool,
+    changelog_preview: Option<String>,
+}
+
+/// Runs the job in the background logging any failure as the webhook has already been answered
+pub fn spawn(state: WebhookState, job: PullRequestJob) {
+    tokio::spawn(async move {
+        let repository = job.context.repository.clone().unwrap_or_default();
+        let pull_request = job.context.pull_request.clone().unwrap_or_default();
+        if let Err(e) = run(state, job).await {
+            error!("pull request job for {repository}#{pull_request} failed: {e:#}");
+        }
+    });
+}
+
+async fn run(state: WebhookState, job: PullRequestJob) -> anyhow::Result<()> {
+    let context = &job.context;
+    if context.draft {
+        debug!("Not running jobs for draft pull request.");
+        return Ok(());
+    }
+
+    let pull_request: u64 = context
+        .pull_request
+        .as_deref()
+        .context("pull request cannot be none")?
+        .parse()?;
+
+    let scm = context
+        .provider
+        .associated_bound_scm_client(context)?
+        .context("unable to determine SCM platform client")?;
+
+    let _guard = state.jobs.lock().await;
+    let workspace = state.configuration.workspace.clone();
+    let output = tokio::task::spawn_blocking(move || execute(&workspace, &job)).await??;
+
+    upsert_note(
+        scm.as_ref(),
+        pull_request,
+        &output.codenotify_title,
+        output.codenotify_message,
+        output.has_subscribers,
+    )
+    .await?;
+
+    if let Some(preview) = output.changelog_preview {
+        upsert_note(
+            scm.as_ref(),
+            pull_request,
+            CHANGELOG_PREVIEW_TITLE,
+            format!("{CHANGELOG_PREVIEW_TITLE}\n## Changelog preview\n\n{preview}"),
+            true,
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+fn execute(workspace: &Path, job: &PullRequestJob) -> anyhow::Result<JobOutput> {
+    let context = &job.context;
+    let cwd = checkout(workspace, job)?;
+    let commit_range = ScmCommitRange::new(context.base.parse()?, Some(context.head.parse()?));
+
+    let code_notify = CodeNotify {
+        cwd: cwd.clone(),
+        format: "markdown".to_string(),
+        // TODO: read from settings
+        file_name: "CODENOTIFY".to_string(),
+        subscriber_threshold: 0,
+        commit_range: commit_range.clone(),
+        author: context.author.clone(),
+    };
+    let code_notify_result = code_notify.notify()?;
+
+    Ok(JobOutput {
+        codenotify_title: code_notify.markdown_comment_title(),
+        codenotify_message: code_notify_result.message,
+        has_subscribers: !code_notify_result.notify.is_empty(),
+        changelog_preview: doctavious_cli::changelog::preview::preview(&cwd, &commit_range)?,
+    })
+}
+
+/// Clones the repository, or fetches when it was cloned by a previous job, and checks out the
+/// pull request's head which is fetched from the platform's pull request ref to include forks
+fn checkout(workspace: &Path, job: &PullRequestJob) -> anyhow::Result<PathBuf> {
+    let context = &job.context;
+    let repository = context
+        .repository
+        .as_deref()
+        .context("repository cannot be none")?;
+    let pull_request = context
+        .pull_request
+        .as_deref()
+        .context("pull request cannot be none")?;
+
+    let cwd = workspace.join(repository);
+    let scm = if cwd.join(".git").is_dir() {
+        let scm = GitScmRepository::new(&cwd)?;
+        scm.fetch(None)?;
+        scm
+    } else {
+        GitScmRepository::clone_remote(&job.clone_url, &cwd)?
+    };
+
+    let refspec = match context.scm_platform {
+        Some(ScmPlatform::GitLab) => format!(
+            "+refs/merge-requests/{pull_request}/head:refs/remotes/origin/merge-requests/{pull_request}"
+        ),
+        _ => format!("+refs/pull/{pull_request}/head:refs/remotes/origin/pull/{pull_request}"),
+    };
+    scm.fetch_refspecs(&[&refspec], None)?;
+    scm.checkout(&context.head)?;
+
+    Ok(cwd)
+}
+
+/// Updates the note starting with `title`, which was written by a previous job, otherwise creates
+/// one when `create` is true
+async fn upsert_note(
+    scm: &dyn ScmPlatformRepositoryBoundedClient,
+    pull_request: u64,
+    title: &str,
+    body: String,
+    create: bool,
+) -> anyhow::Result<()> {
+    let notes = scm.list_all_merge_requests_notes(pull_request).await?;
+    match notes.into_iter().find(|note| note.body.starts_with(title)) {
+        Some(note) => {
+            scm.update_merge_request_note(pull_request, note.id, body)
+                .await?
+        }
+        None if create => scm.create_merge_request_note(pull_request, body).await?,
+        None => debug!("not adding a comment because there is nothing to report"),
+    }
+
+    Ok(())
+}
@@ -20,8 +20,9 @@ use tower_http::trace::TraceLayer;
 use tower_service::Service;
 use tracing::debug;
 
-use crate::configuration::get_configuration;
-use crate::features::deployment;
+use crate::configuration::{WebhookConfiguration, get_configuration};
+use crate::features::webhooks::WebhookState;
+use crate::features::{deployment, github, gitlab};
 
 // State is global within the router
 // The state passed to this method will be used for all requests this router receives.
@@ -59,12 +60,15 @@ async fn main() {
     get_configuration();
 
     let storage = get_storage().expect("unable to create storage");
+    let webhooks = WebhookState::new(WebhookConfiguration::from_env());
 
     // build our application with a single route
     let app = Router::new()
         .route("/", get(|| async { "Hello, World!" }))
         .route("/health", get(health))
         .merge(deployment::get_routes(storage))
+        .merge(github::get_routes(webhooks.clone()))
+        .merge(gitlab::get_routes(webhooks))
         //.with_state(storage)
         .fallback(not_found)
         .layer((
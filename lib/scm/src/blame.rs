@@ -0,0 +1,181 @@
+use std::cmp::Reverse;
+use std::collections::HashSet;
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+
+use indexmap::IndexMap;
+use serde_derive::{Deserialize, Serialize};
+
+use crate::commit::ScmSignature;
+
+/// Consecutive lines of a file last changed by the same commit
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ScmBlameHunk {
+    /// ID of the commit that last changed the lines
+    pub commit_id: String,
+
+    /// Author of the commit
+    pub author: ScmSignature,
+
+    /// First line of the hunk, starting at 1
+    pub start_line: usize,
+
+    /// Number of lines in the hunk
+    pub lines: usize,
+}
+
+impl ScmBlameHunk {
+    /// Lines of the file the hunk covers, starting at 1
+    pub fn line_range(&self) -> RangeInclusive<usize> {
+        self.start_line..=self.start_line + self.lines.saturating_sub(1)
+    }
+}
+
+/// Lines changed by an author within a blamed file
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ScmBlameAuthor {
+    pub name: Option<String>,
+
+    pub email: Option<String>,
+
+    /// Number of lines last changed by the author
+    pub lines: usize,
+
+    /// Number of distinct commits the author's lines come from
+    pub commits: usize,
+
+    /// Time of the author's most recent change to the file
+    pub last_timestamp: i64,
+}
+
+/// Attribution of each line of a file to the commit that last changed it
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ScmBlame {
+    /// Path of the file relative to the repository root
+    pub path: PathBuf,
+
+    /// Hunks ordered by line
+    pub hunks: Vec<ScmBlameHunk>,
+}
+
+impl ScmBlame {
+    /// Groups per line attribution, in line order, into hunks of consecutive lines from the same
+    /// commit. This is for SCMs that only blame line by line.
+    pub fn from_lines<I>(path: PathBuf, lines: I) -> Self
+    where
+        I: IntoIterator<Item = (String, ScmSignature)>,
+    {
+        let mut hunks: Vec<ScmBlameHunk> = vec![];
+        for (commit_id, author) in lines {
+            match hunks.last_mut() {
+                Some(hunk) if hunk.commit_id == commit_id => hunk.lines += 1,
+                _ => {
+                    let start_line = hunks.last().map_or(1, |h| h.start_line + h.lines);
+                    hunks.push(ScmBlameHunk {
+                        commit_id,
+                        author,
+                        start_line,
+                        lines: 1,
+                    });
+                }
+            }
+        }
+
+        Self { path, hunks }
+    }
+
+    /// Hunk containing the line, starting at 1
+    pub fn hunk_for_line(&self, line: usize) -> Option<&ScmBlameHunk> {
+        self.hunks.iter().find(|h| h.line_range().contains(&line))
+    }
+
+    /// Most recently changed hunk, i.e., who last touched the file and when
+    pub fn last_changed(&self) -> Option<&ScmBlameHunk> {
+        self.hunks.iter().max_by_key(|h| h.author.timestamp)
+    }
+
+    /// Summary of the lines each author last changed, ordered by most lines.
+    ///
+    /// Authors are identified by email, falling back to name for SCMs such as Subversion that
+    /// only record a username.
+    pub fn authors(&self) -> Vec<ScmBlameAuthor> {
+        let mut authors: IndexMap<Option<&str>, ScmBlameAuthor> = IndexMap::new();
+        let mut author_commits = HashSet::new();
+        for hunk in &self.hunks {
+            let key = hunk.author.email.as_deref().or(hunk.author.name.as_deref());
+            let author = authors.entry(key).or_insert_with(|| ScmBlameAuthor {
+                name: hunk.author.name.clone(),
+                email: hunk.author.email.clone(),
+                lines: 0,
+                commits: 0,
+                last_timestamp: hunk.author.timestamp,
+            });
+
+            author.lines += hunk.lines;
+            author.last_timestamp = author.last_timestamp.max(hunk.author.timestamp);
+            if author_commits.insert((key, hunk.commit_id.as_str())) {
+                author.commits += 1;
+            }
+        }
+
+        let mut authors: Vec<ScmBlameAuthor> = authors.into_values().collect();
+        authors.sort_by_key(|a| Reverse(a.lines));
+        authors
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::blame::ScmBlame;
+    use crate::commit::ScmSignature;
+
+    fn signature(name: &str, email: Option<&str>, timestamp: i64) -> ScmSignature {
+        ScmSignature {
+            name: Some(name.to_string()),
+            email: email.map(String::from),
+            timestamp,
+        }
+    }
+
+    #[test]
+    fn should_group_lines_into_hunks() {
+        let jane = signature("jane", Some("jane@doctavious.com"), 1);
+        let john = signature("john", None, 3);
+        let blame = ScmBlame::from_lines(
+            PathBuf::from("README.md"),
+            [
+                ("a".to_string(), jane.clone()),
+                ("a".to_string(), jane.clone()),
+                ("b".to_string(), john.clone()),
+                (
+                    "c".to_string(),
+                    signature("jane", Some("jane@doctavious.com"), 2),
+                ),
+                ("a".to_string(), jane),
+            ],
+        );
+
+        assert_eq!(
+            vec![1..=2, 3..=3, 4..=4, 5..=5],
+            blame
+                .hunks
+                .iter()
+                .map(|h| h.line_range())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!("b", blame.hunk_for_line(3).unwrap().commit_id);
+        assert!(blame.hunk_for_line(6).is_none());
+        assert_eq!("b", blame.last_changed().unwrap().commit_id);
+
+        let authors = blame.authors();
+        assert_eq!(2, authors.len());
+        assert_eq!(Some("jane@doctavious.com".to_string()), authors[0].email);
+        assert_eq!(4, authors[0].lines);
+        assert_eq!(2, authors[0].commits);
+        assert_eq!(2, authors[0].last_timestamp);
+        assert_eq!(Some("john".to_string()), authors[1].name);
+        assert_eq!(1, authors[1].lines);
+    }
+}
@@ -1,7 +1,15 @@
+use std::fmt;
+use std::fmt::{Display, Formatter};
 use std::hash::{Hash, Hasher};
+use std::str::FromStr;
 
+use chrono::{NaiveDate, NaiveTime};
 use serde_derive::{Deserialize, Serialize};
 
+use crate::errors::{ScmError, ScmResult};
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
 // TODO: could possibly make this an enum with an associated trait
 // TODO: should we have a CommitId type?
 // TODO: not sure this will work generically across SCM providers but will use for now
@@ -65,17 +73,253 @@ impl Hash for ScmTag {
     }
 }
 
-// TODO: inclusive/exclusive
-#[derive(Debug)]
-pub struct ScmCommitRange(pub String, pub Option<String>);
+/// Revision bounding a commit range
+#[remain::sorted]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ScmRevision {
+    /// Latest commit of a branch, `branch:<name>`
+    Branch(String),
+
+    /// Most recent commit before the start of the day (UTC), `date:<YYYY-MM-DD>`
+    Date(NaiveDate),
+
+    /// Current revision of the working directory, `HEAD`
+    Head,
+
+    /// Common ancestor of a branch and the other end of the range, `merge-base:<branch>`
+    MergeBase(String),
+
+    /// Any revision the SCM understands such as a commit ID or tag name
+    Ref(String),
+
+    /// Tagged commit, `tag:<name>`
+    Tag(String),
+}
+
+impl ScmRevision {
+    /// Timestamp the revision of a date bound must be before
+    pub fn date_timestamp(date: &NaiveDate) -> i64 {
+        date.and_time(NaiveTime::MIN).and_utc().timestamp()
+    }
+}
+
+impl FromStr for ScmRevision {
+    type Err = ScmError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ScmError::InvalidRange(s.to_string());
+        if s.is_empty() || s.starts_with('.') {
+            return Err(invalid());
+        }
+
+        Ok(match s.split_once(':') {
+            Some(("branch", name)) if !name.is_empty() => ScmRevision::Branch(name.to_string()),
+            Some(("date", date)) => ScmRevision::Date(parse_date(date).ok_or_else(invalid)?),
+            Some(("merge-base", name)) if !name.is_empty() => {
+                ScmRevision::MergeBase(name.to_string())
+            }
+            Some(("tag", name)) if !name.is_empty() => ScmRevision::Tag(name.to_string()),
+            _ if s == "HEAD" => ScmRevision::Head,
+            _ => ScmRevision::Ref(s.to_string()),
+        })
+    }
+}
+
+impl Display for ScmRevision {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ScmRevision::Branch(name) => write!(f, "branch:{name}"),
+            ScmRevision::Date(date) => write!(f, "date:{}", date.format(DATE_FORMAT)),
+            ScmRevision::Head => write!(f, "HEAD"),
+            ScmRevision::MergeBase(name) => write!(f, "merge-base:{name}"),
+            ScmRevision::Ref(revision) => write!(f, "{revision}"),
+            ScmRevision::Tag(name) => write!(f, "tag:{name}"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScmRangeBound {
+    pub revision: ScmRevision,
+
+    /// Whether the commit the revision resolves to is part of the range
+    pub inclusive: bool,
+}
+
+/// Range of commits.
+///
+/// Parsed from `<start>..<end>` where, like git, the start is exclusive and the end is inclusive.
+/// Bounds can be made explicit by wrapping the range in brackets, `[` / `]` for inclusive and
+/// `(` / `)` for exclusive, e.g., `[v1.0.0..v2.0.0)`. The end defaults to `HEAD` when omitted.
+/// Revisions are described by [`ScmRevision`]. Additionally supports `since:<YYYY-MM-DD>` for
+/// commits made on or after a date and `last:<n>` for the most recent `n` commits.
+#[remain::sorted]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ScmCommitRange {
+    Between {
+        start: ScmRangeBound,
+        end: Option<ScmRangeBound>,
+    },
 
-pub enum ScmCommitRangeBound {
-    Inclusive,
-    Exclusive,
+    /// Most recent commits following first parents from `HEAD`
+    Last(usize),
 }
 
-// pub struct ScmCommitRange<'a> {
-//     start: &'a str,
-//     end: &'a str,
-//     bound: ScmCommitRangeBound
-// }
+impl ScmCommitRange {
+    /// Range of commits after `start` up to and including `end`, similar to git's `start..end`
+    pub fn new(start: ScmRevision, end: Option<ScmRevision>) -> Self {
+        ScmCommitRange::Between {
+            start: ScmRangeBound {
+                revision: start,
+                inclusive: false,
+            },
+            end: end.map(|revision| ScmRangeBound {
+                revision,
+                inclusive: true,
+            }),
+        }
+    }
+}
+
+impl FromStr for ScmCommitRange {
+    type Err = ScmError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let invalid = || ScmError::InvalidRange(s.to_string());
+
+        if let Some(count) = s.strip_prefix("last:") {
+            return count
+                .parse()
+                .map(ScmCommitRange::Last)
+                .map_err(|_| invalid());
+        }
+
+        if let Some(date) = s.strip_prefix("since:") {
+            return Ok(ScmCommitRange::Between {
+                start: ScmRangeBound {
+                    revision: ScmRevision::Date(parse_date(date).ok_or_else(invalid)?),
+                    inclusive: false,
+                },
+                end: None,
+            });
+        }
+
+        let (start_inclusive, range) = match s.strip_prefix('[') {
+            Some(range) => (true, range),
+            None => (false, s.strip_prefix('(').unwrap_or(s)),
+        };
+        let (end_inclusive, range) = match range.strip_suffix(')') {
+            Some(range) => (false, range),
+            None => (true, range.strip_suffix(']').unwrap_or(range)),
+        };
+
+        let (start, end) = match range.split_once("..") {
+            Some((start, "")) => (start, None),
+            Some((start, end)) => (start, Some(end)),
+            None => (range, None),
+        };
+
+        Ok(ScmCommitRange::Between {
+            start: ScmRangeBound {
+                revision: start.parse().map_err(|_| invalid())?,
+                inclusive: start_inclusive,
+            },
+            end: end
+                .map(|end| -> ScmResult<ScmRangeBound> {
+                    Ok(ScmRangeBound {
+                        revision: end.parse().map_err(|_| invalid())?,
+                        inclusive: end_inclusive,
+                    })
+                })
+                .transpose()?,
+        })
+    }
+}
+
+impl Display for ScmCommitRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ScmCommitRange::Between { start, end } => {
+                let end_inclusive = end.as_ref().is_none_or(|e| e.inclusive);
+                write!(
+                    f,
+                    "{}{}..{}{}",
+                    if start.inclusive { "[" } else { "(" },
+                    start.revision,
+                    end.as_ref()
+                        .map(|e| e.revision.to_string())
+                        .unwrap_or_default(),
+                    if end_inclusive { "]" } else { ")" }
+                )
+            }
+            ScmCommitRange::Last(count) => write!(f, "last:{count}"),
+        }
+    }
+}
+
+/// Commit range resolved to commit IDs
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScmResolvedRange {
+    /// Commit the range starts after. `None` when the range starts at the first commit
+    pub start: Option<String>,
+
+    /// Last commit of the range
+    pub end: String,
+}
+
+fn parse_date(date: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(date, DATE_FORMAT).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use test_case::test_case;
+
+    use crate::commit::{ScmCommitRange, ScmRangeBound, ScmRevision};
+
+    fn bound(revision: ScmRevision, inclusive: bool) -> ScmRangeBound {
+        ScmRangeBound {
+            revision,
+            inclusive,
+        }
+    }
+
+    #[test_case("v1..v2", bound(ScmRevision::Ref("v1".into()), false), Some(bound(ScmRevision::Ref("v2".into()), true)) ; "git style")]
+    #[test_case("[tag:v1..branch:main)", bound(ScmRevision::Tag("v1".into()), true), Some(bound(ScmRevision::Branch("main".into()), false)) ; "explicit bounds")]
+    #[test_case("merge-base:main..HEAD", bound(ScmRevision::MergeBase("main".into()), false), Some(bound(ScmRevision::Head, true)) ; "merge base")]
+    #[test_case("abc123", bound(ScmRevision::Ref("abc123".into()), false), None ; "start only")]
+    #[test_case("date:2024-01-01..", bound(ScmRevision::Date(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()), false), None ; "date")]
+    #[test_case("since:2024-01-01", bound(ScmRevision::Date(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()), false), None ; "since")]
+    fn should_parse_range(value: &str, start: ScmRangeBound, end: Option<ScmRangeBound>) {
+        assert_eq!(
+            ScmCommitRange::Between { start, end },
+            value.parse::<ScmCommitRange>().unwrap()
+        );
+    }
+
+    #[test]
+    fn should_parse_last() {
+        assert_eq!(
+            ScmCommitRange::Last(5),
+            "last:5".parse::<ScmCommitRange>().unwrap()
+        );
+    }
+
+    #[test_case("" ; "empty")]
+    #[test_case("..HEAD" ; "missing start")]
+    #[test_case("a...b" ; "symmetric difference")]
+    #[test_case("last:many" ; "invalid count")]
+    #[test_case("since:yesterday" ; "invalid date")]
+    fn should_reject_invalid_range(value: &str) {
+        assert!(value.parse::<ScmCommitRange>().is_err());
+    }
+
+    #[test]
+    fn should_display_range() {
+        let range: ScmCommitRange = "tag:v1..".parse().unwrap();
+        assert_eq!("(tag:v1..]", range.to_string());
+        assert_eq!(range, range.to_string().parse().unwrap());
+    }
+}
@@ -0,0 +1,226 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use git2::Repository as Git2Repository;
+use strum::{Display, EnumString, VariantNames};
+
+use crate::drivers::Scm;
+use crate::drivers::git::GitScmRepository;
+use crate::drivers::hg::HgScmRepository;
+use crate::drivers::svn::SvnScmRepository;
+use crate::errors::{ScmError, ScmResult};
+use crate::settings::ScmSettings;
+
+#[remain::sorted]
+#[derive(Clone, Copy, Debug, Display, EnumString, Eq, PartialEq, VariantNames)]
+#[strum(serialize_all = "lowercase")]
+pub enum ScmKind {
+    Git,
+    Hg,
+    Svn,
+}
+
+/// Repository found by walking up from a directory within it
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScmRoot {
+    pub kind: ScmKind,
+
+    /// Root of the working tree. For bare repositories this is the repository directory
+    pub root: PathBuf,
+
+    /// Directory discovery started from relative to `root`
+    pub relative_cwd: PathBuf,
+
+    /// Whether the repository is a bare git repository, i.e., has no working tree
+    pub bare: bool,
+}
+
+impl ScmRoot {
+    /// Walks up from `cwd` to the root of the closest repository.
+    ///
+    /// Git worktrees and submodules, where `.git` is a file, resolve to their own working tree
+    /// rather than the repository they belong to. Directories within a git directory, such as
+    /// `.git/hooks`, resolve to the working tree of that repository.
+    pub fn find(cwd: &Path) -> ScmResult<Self> {
+        let cwd = fs::canonicalize(cwd)?;
+        for dir in cwd.ancestors() {
+            if let Some((kind, root, bare)) = Self::detect(dir)? {
+                let relative_cwd = cwd
+                    .strip_prefix(&root)
+                    .map(Path::to_path_buf)
+                    .unwrap_or_default();
+
+                return Ok(Self {
+                    kind,
+                    root,
+                    relative_cwd,
+                    bare,
+                });
+            }
+        }
+
+        Err(ScmError::Unsupported)
+    }
+
+    fn detect(dir: &Path) -> ScmResult<Option<(ScmKind, PathBuf, bool)>> {
+        // `.git` is a file for worktrees and submodules which git2 follows when opening
+        if dir.join(".git").exists() || is_git_dir(dir) {
+            let repository = Git2Repository::open(dir)?;
+            let root = match repository.workdir() {
+                Some(workdir) => fs::canonicalize(workdir)?,
+                None => fs::canonicalize(repository.path())?,
+            };
+
+            return Ok(Some((ScmKind::Git, root, repository.is_bare())));
+        }
+
+        if dir.join(".hg").is_dir() {
+            return Ok(Some((ScmKind::Hg, dir.to_path_buf(), false)));
+        }
+
+        // only the root of an SVN 1.7+ working copy contains `.svn`
+        if dir.join(".svn").is_dir() {
+            return Ok(Some((ScmKind::Svn, dir.to_path_buf(), false)));
+        }
+
+        Ok(None)
+    }
+
+    /// Opens the repository with the driver for its SCM
+    pub fn open(&self) -> ScmResult<Scm> {
+        self.open_with_settings(&ScmSettings::default())
+    }
+
+    /// Opens the repository with the driver for its SCM configured by `settings`
+    pub fn open_with_settings(&self, settings: &ScmSettings) -> ScmResult<Scm> {
+        Ok(match self.kind {
+            ScmKind::Git => Scm::Git(GitScmRepository::new(&self.root)?),
+            ScmKind::Hg => Scm::Hg(HgScmRepository::new(&self.root)?),
+            ScmKind::Svn => {
+                let mut repository = SvnScmRepository::new(&self.root)?;
+                if let Some(tags_path) = &settings.svn_tags_path {
+                    repository = repository.with_tags_path(tags_path);
+                }
+                Scm::Svn(repository)
+            }
+        })
+    }
+}
+
+/// Determines if the directory is a git directory such as a bare repository or `.git`
+fn is_git_dir(dir: &Path) -> bool {
+    dir.join("HEAD").is_file() && dir.join("objects").is_dir() && dir.join("refs").is_dir()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    use git2::{Repository, RepositoryInitOptions, Signature, WorktreeAddOptions};
+    use tempfile::TempDir;
+
+    use crate::discovery::{ScmKind, ScmRoot};
+
+    fn commit(repository: &Repository) {
+        let signature = Signature::now("test", "test@doctavious.com").unwrap();
+        let tree_id = repository.index().unwrap().write_tree().unwrap();
+        let tree = repository.find_tree(tree_id).unwrap();
+        repository
+            .commit(Some("HEAD"), &signature, &signature, "init", &tree, &[])
+            .unwrap();
+    }
+
+    fn canonical(path: &Path) -> PathBuf {
+        fs::canonicalize(path).unwrap()
+    }
+
+    #[test]
+    fn should_find_root_from_subdirectory() {
+        let dir = TempDir::new().unwrap();
+        Repository::init(dir.path()).unwrap();
+        let nested = dir.path().join("docs").join("adr");
+        fs::create_dir_all(&nested).unwrap();
+
+        let root = ScmRoot::find(&nested).unwrap();
+        assert_eq!(ScmKind::Git, root.kind);
+        assert_eq!(canonical(dir.path()), root.root);
+        assert_eq!(PathBuf::from("docs/adr"), root.relative_cwd);
+        assert!(!root.bare);
+
+        let root = ScmRoot::find(&dir.path().join(".git").join("hooks")).unwrap();
+        assert_eq!(canonical(dir.path()), root.root);
+
+        assert!(root.open().is_ok());
+    }
+
+    #[test]
+    fn should_find_nested_hg_and_svn_roots() {
+        let dir = TempDir::new().unwrap();
+        Repository::init(dir.path()).unwrap();
+        fs::create_dir_all(dir.path().join("hg").join(".hg")).unwrap();
+        fs::create_dir_all(dir.path().join("svn").join(".svn")).unwrap();
+        fs::create_dir_all(dir.path().join("svn").join("src")).unwrap();
+
+        let root = ScmRoot::find(&dir.path().join("hg")).unwrap();
+        assert_eq!(ScmKind::Hg, root.kind);
+        assert_eq!(PathBuf::new(), root.relative_cwd);
+
+        let root = ScmRoot::find(&dir.path().join("svn").join("src")).unwrap();
+        assert_eq!(ScmKind::Svn, root.kind);
+        assert_eq!(canonical(&dir.path().join("svn")), root.root);
+        assert_eq!(PathBuf::from("src"), root.relative_cwd);
+    }
+
+    #[test]
+    fn should_find_worktree_root() {
+        let dir = TempDir::new().unwrap();
+        let repository = Repository::init(dir.path().join("main")).unwrap();
+        commit(&repository);
+
+        let worktree_path = dir.path().join("feature");
+        repository
+            .worktree("feature", &worktree_path, Some(&WorktreeAddOptions::new()))
+            .unwrap();
+        assert!(worktree_path.join(".git").is_file());
+        fs::create_dir_all(worktree_path.join("src")).unwrap();
+
+        let root = ScmRoot::find(&worktree_path.join("src")).unwrap();
+        assert_eq!(ScmKind::Git, root.kind);
+        assert_eq!(canonical(&worktree_path), root.root);
+        assert_eq!(PathBuf::from("src"), root.relative_cwd);
+    }
+
+    #[test]
+    fn should_find_submodule_root() {
+        let dir = TempDir::new().unwrap();
+        let repository = Repository::init(dir.path()).unwrap();
+        repository
+            .submodule("https://example.com/lib.git", Path::new("lib"), true)
+            .unwrap();
+        let submodule_path = dir.path().join("lib");
+        assert!(submodule_path.join(".git").is_file());
+
+        let root = ScmRoot::find(&submodule_path).unwrap();
+        assert_eq!(canonical(&submodule_path), root.root);
+        assert_eq!(PathBuf::new(), root.relative_cwd);
+    }
+
+    #[test]
+    fn should_find_bare_repository() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("repo.git");
+        Repository::init_opts(&path, RepositoryInitOptions::new().bare(true)).unwrap();
+
+        let root = ScmRoot::find(&path.join("refs")).unwrap();
+        assert!(root.bare);
+        assert_eq!(canonical(&path), root.root);
+        assert_eq!(PathBuf::from("refs"), root.relative_cwd);
+    }
+
+    #[test]
+    fn should_error_outside_of_repository() {
+        let dir = TempDir::new().unwrap();
+        assert!(ScmRoot::find(dir.path()).is_err());
+    }
+}
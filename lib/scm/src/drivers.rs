@@ -1,16 +1,22 @@
+use std::cmp::Ordering;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
 
 use git2::Signature;
 use glob::Pattern;
 use indexmap::IndexMap;
 use regex::Regex;
 
-use crate::commit::{ScmCommit, ScmCommitRange, ScmTag};
+use crate::blame::ScmBlame;
+use crate::commit::{ScmCommit, ScmCommitRange, ScmResolvedRange, ScmRevision, ScmTag};
+use crate::discovery::ScmRoot;
 use crate::drivers::git::{GitScmRepository, TagSort};
 use crate::drivers::hg::HgScmRepository;
 use crate::drivers::svn::SvnScmRepository;
 use crate::errors::{ScmError, ScmResult};
+use crate::remote::ScmRemote;
+use crate::settings::ScmSettings;
 
 pub mod git;
 pub mod hg;
@@ -48,6 +54,29 @@ pub trait ScmRepository {
         limit_commits: Option<usize>,
     ) -> ScmResult<Vec<ScmCommit>>;
 
+    /// Commits that changed the file, or anything within the directory, newest first.
+    ///
+    /// `path` is relative to the repository root. Renames aren't followed.
+    fn file_history(
+        &self,
+        path: &Path,
+        range: Option<&ScmCommitRange>,
+    ) -> ScmResult<Vec<ScmCommit>> {
+        // escape the path so it's matched literally
+        let include_paths = vec![Pattern::new(&Pattern::escape(&path.to_string_lossy()))?];
+        self.commits(range, Some(&include_paths), None, None)
+    }
+
+    /// Commit that added the file. Returns `None` when the file hasn't been committed
+    fn first_commit_for(&self, path: &Path) -> ScmResult<Option<ScmCommit>> {
+        Ok(self.file_history(path, None)?.pop())
+    }
+
+    /// Attributes each line of the committed file to the commit that last changed it.
+    ///
+    /// `path` is relative to the repository root. Uncommitted changes aren't included.
+    fn blame(&self, path: &Path) -> ScmResult<ScmBlame>;
+
     // fn tagged_commits(&self) -> ScmResult<Vec<TaggedCommits>>;
 
     // TODO: I wonder if we would be ok with include / exclude being globs rather than regex?
@@ -63,11 +92,44 @@ pub trait ScmRepository {
 
     fn latest_tag(&self) -> ScmResult<Option<ScmTag>>;
 
-    fn get_tag(&self, name: &str) -> ScmTag;
+    /// Returns `None` when the tag doesn't exist
+    fn get_tag(&self, name: &str) -> ScmResult<Option<ScmTag>>;
+
+    /// Creates a tag for the revision, or the current revision when `None`.
+    ///
+    /// Tags are annotated with the message where the SCM supports it and signed when the SCM is
+    /// configured to sign tags.
+    fn create_tag(
+        &self,
+        name: &str,
+        revision: Option<&str>,
+        message: Option<&str>,
+        signature: Option<&Signature>,
+    ) -> ScmResult<ScmTag>;
 
     /// Determines if the working directory has changes
     fn is_dirty(&self) -> ScmResult<bool>;
 
+    /// URL of the repository's default remote, if one is configured
+    fn remote_url(&self) -> ScmResult<Option<String>>;
+
+    /// Repository on an SCM platform the default remote points to
+    fn remote_repository(&self) -> ScmResult<Option<ScmRemote>> {
+        Ok(self.remote_url()?.as_deref().and_then(ScmRemote::parse))
+    }
+
+    /// Fetches branches and tags from the remote, or the default remote when `None`
+    fn fetch(&self, remote: Option<&str>) -> ScmResult<()>;
+
+    /// Pushes the branch to the remote, or the default remote when `None`
+    fn push_branch(&self, branch: &str, remote: Option<&str>) -> ScmResult<()>;
+
+    /// Pushes the tag to the remote, or the default remote when `None`
+    fn push_tag(&self, tag: &str, remote: Option<&str>) -> ScmResult<()>;
+
+    /// Names of the branches of the remote, or the default remote when `None`
+    fn remote_branches(&self, remote: Option<&str>) -> ScmResult<Vec<String>>;
+
     // head return commit/revision
 
     fn supported_hooks(&self) -> Vec<&'static str>;
@@ -97,38 +159,85 @@ pub trait ScmRepository {
     fn diff_paths(&self, range: Option<&ScmCommitRange>) -> ScmResult<Vec<PathBuf>>;
 
     fn commit(&self, message: &str, signature: Option<&Signature>) -> ScmResult<()>;
+
+    /// Commit ID the revision refers to.
+    ///
+    /// Merge bases and dates are relative to `base`, or the current revision when `None`.
+    /// Returns `None` when the revision precedes the first commit, e.g., a date before any commits.
+    fn resolve_revision(
+        &self,
+        revision: &ScmRevision,
+        base: Option<&str>,
+    ) -> ScmResult<Option<String>>;
+
+    /// Commit ID of the first parent of the commit
+    fn parent(&self, id: &str) -> ScmResult<Option<String>>;
+
+    /// Resolves the range to commit IDs. Returns `None` when the range can't contain any commits.
+    fn resolve_range(&self, range: &ScmCommitRange) -> ScmResult<Option<ScmResolvedRange>> {
+        match range {
+            ScmCommitRange::Between { start, end } => {
+                let end_revision = end.as_ref().map_or(&ScmRevision::Head, |e| &e.revision);
+                let mut end_id = self.resolve_revision(end_revision, None)?;
+                if let (Some(id), Some(end)) = (&end_id, end)
+                    && !end.inclusive
+                {
+                    end_id = self.parent(id)?;
+                }
+
+                let Some(end_id) = end_id else {
+                    return Ok(None);
+                };
+
+                let mut start_id = self.resolve_revision(&start.revision, Some(&end_id))?;
+                if let Some(id) = &start_id
+                    && start.inclusive
+                {
+                    start_id = self.parent(id)?;
+                }
+
+                Ok(Some(ScmResolvedRange {
+                    start: start_id,
+                    end: end_id,
+                }))
+            }
+            ScmCommitRange::Last(count) => {
+                let Some(end_id) = self.resolve_revision(&ScmRevision::Head, None)? else {
+                    return Ok(None);
+                };
+
+                let mut start_id = Some(end_id.clone());
+                for _ in 0..*count {
+                    match start_id {
+                        Some(id) => start_id = self.parent(&id)?,
+                        None => break,
+                    }
+                }
+
+                Ok(Some(ScmResolvedRange {
+                    start: start_id,
+                    end: end_id,
+                }))
+            }
+        }
+    }
 }
 
 impl Scm {
-    // TODO: separate get and search? I saw an example of a VCS/SCM project that did that but dont recall which
+    /// Opens the repository `cwd` is within. See [`Scm::discover`]
     pub fn get(cwd: &Path) -> ScmResult<Self> {
-        // TODO: it might be better to try and discover directory such as
-        // `git rev-parse --show-toplevel` and `git rev-parse --git-dir`
-
-        if fs::metadata(cwd.join(".git")).is_ok() {
-            return Ok(Scm::Git(GitScmRepository::new(cwd)?));
-        }
-
-        if fs::metadata(cwd.join(".svn")).is_ok() {
-            unimplemented!()
-        }
-
-        if fs::metadata(cwd.join(".hg")).is_ok() {
-            unimplemented!()
-        }
-
-        Err(ScmError::Unsupported)
+        Self::discover(cwd)
     }
 
+    /// Walks up from `cwd` to the closest repository root and opens it.
+    /// Use [`ScmRoot::find`] to also get the root and `cwd` relative to it.
     pub fn discover(cwd: &Path) -> ScmResult<Self> {
-        if let Ok(repo) = GitScmRepository::discover(cwd) {
-            return Ok(Scm::Git(repo));
-        }
-
-        // TODO: SVN
-        // TODO: Mecurial
+        ScmRoot::find(cwd)?.open()
+    }
 
-        Err(ScmError::Unsupported)
+    /// Same as [`Scm::discover`] but configures the driver with `settings`
+    pub fn discover_with_settings(cwd: &Path, settings: &ScmSettings) -> ScmResult<Self> {
+        ScmRoot::find(cwd)?.open_with_settings(settings)
     }
 
     pub fn ensure_hooks_directory(&self) -> ScmResult<PathBuf> {
@@ -192,6 +301,34 @@ impl ScmRepository for Scm {
         }
     }
 
+    fn file_history(
+        &self,
+        path: &Path,
+        range: Option<&ScmCommitRange>,
+    ) -> ScmResult<Vec<ScmCommit>> {
+        match self {
+            Scm::Git(r) => r.file_history(path, range),
+            Scm::Hg(r) => r.file_history(path, range),
+            Scm::Svn(r) => r.file_history(path, range),
+        }
+    }
+
+    fn first_commit_for(&self, path: &Path) -> ScmResult<Option<ScmCommit>> {
+        match self {
+            Scm::Git(r) => r.first_commit_for(path),
+            Scm::Hg(r) => r.first_commit_for(path),
+            Scm::Svn(r) => r.first_commit_for(path),
+        }
+    }
+
+    fn blame(&self, path: &Path) -> ScmResult<ScmBlame> {
+        match self {
+            Scm::Git(r) => r.blame(path),
+            Scm::Hg(r) => r.blame(path),
+            Scm::Svn(r) => r.blame(path),
+        }
+    }
+
     fn tags(
         &self,
         includes: Option<&Vec<Regex>>,
@@ -222,7 +359,7 @@ impl ScmRepository for Scm {
         }
     }
 
-    fn get_tag(&self, name: &str) -> ScmTag {
+    fn get_tag(&self, name: &str) -> ScmResult<Option<ScmTag>> {
         match self {
             Scm::Git(r) => r.get_tag(name),
             Scm::Hg(r) => r.get_tag(name),
@@ -230,6 +367,20 @@ impl ScmRepository for Scm {
         }
     }
 
+    fn create_tag(
+        &self,
+        name: &str,
+        revision: Option<&str>,
+        message: Option<&str>,
+        signature: Option<&Signature>,
+    ) -> ScmResult<ScmTag> {
+        match self {
+            Scm::Git(r) => r.create_tag(name, revision, message, signature),
+            Scm::Hg(r) => r.create_tag(name, revision, message, signature),
+            Scm::Svn(r) => r.create_tag(name, revision, message, signature),
+        }
+    }
+
     fn is_dirty(&self) -> ScmResult<bool> {
         match self {
             Scm::Git(r) => r.is_dirty(),
@@ -238,6 +389,46 @@ impl ScmRepository for Scm {
         }
     }
 
+    fn remote_url(&self) -> ScmResult<Option<String>> {
+        match self {
+            Scm::Git(r) => r.remote_url(),
+            Scm::Hg(r) => r.remote_url(),
+            Scm::Svn(r) => r.remote_url(),
+        }
+    }
+
+    fn fetch(&self, remote: Option<&str>) -> ScmResult<()> {
+        match self {
+            Scm::Git(r) => r.fetch(remote),
+            Scm::Hg(r) => r.fetch(remote),
+            Scm::Svn(r) => r.fetch(remote),
+        }
+    }
+
+    fn push_branch(&self, branch: &str, remote: Option<&str>) -> ScmResult<()> {
+        match self {
+            Scm::Git(r) => r.push_branch(branch, remote),
+            Scm::Hg(r) => r.push_branch(branch, remote),
+            Scm::Svn(r) => r.push_branch(branch, remote),
+        }
+    }
+
+    fn push_tag(&self, tag: &str, remote: Option<&str>) -> ScmResult<()> {
+        match self {
+            Scm::Git(r) => r.push_tag(tag, remote),
+            Scm::Hg(r) => r.push_tag(tag, remote),
+            Scm::Svn(r) => r.push_tag(tag, remote),
+        }
+    }
+
+    fn remote_branches(&self, remote: Option<&str>) -> ScmResult<Vec<String>> {
+        match self {
+            Scm::Git(r) => r.remote_branches(remote),
+            Scm::Hg(r) => r.remote_branches(remote),
+            Scm::Svn(r) => r.remote_branches(remote),
+        }
+    }
+
     fn supported_hooks(&self) -> Vec<&'static str> {
         match self {
             Scm::Git(r) => r.supported_hooks(),
@@ -321,8 +512,8 @@ impl ScmRepository for Scm {
     fn diff_paths(&self, range: Option<&ScmCommitRange>) -> ScmResult<Vec<PathBuf>> {
         match self {
             Scm::Git(r) => r.diff_paths(range),
-            Scm::Hg(_) => unimplemented!(),
-            Scm::Svn(_) => unimplemented!(),
+            Scm::Hg(r) => r.diff_paths(range),
+            Scm::Svn(r) => r.diff_paths(range),
         }
     }
 
@@ -333,4 +524,151 @@ impl ScmRepository for Scm {
             Scm::Svn(r) => r.commit(message, signature),
         }
     }
+
+    fn resolve_revision(
+        &self,
+        revision: &ScmRevision,
+        base: Option<&str>,
+    ) -> ScmResult<Option<String>> {
+        match self {
+            Scm::Git(r) => r.resolve_revision(revision, base),
+            Scm::Hg(r) => r.resolve_revision(revision, base),
+            Scm::Svn(r) => r.resolve_revision(revision, base),
+        }
+    }
+
+    fn parent(&self, id: &str) -> ScmResult<Option<String>> {
+        match self {
+            Scm::Git(r) => r.parent(id),
+            Scm::Hg(r) => r.parent(id),
+            Scm::Svn(r) => r.parent(id),
+        }
+    }
+}
+
+/// Compares tag names by their numeric components, similar to git's `v:refname` sort
+pub(crate) fn version_cmp(a: &str, b: &str) -> Ordering {
+    let parts = |s: &str| -> Vec<(bool, String)> {
+        let mut parts: Vec<(bool, String)> = vec![];
+        for c in s.chars() {
+            let is_digit = c.is_ascii_digit();
+            match parts.last_mut() {
+                Some((digits, part)) if *digits == is_digit => part.push(c),
+                _ => parts.push((is_digit, c.to_string())),
+            }
+        }
+        parts
+    };
+
+    let (a_parts, b_parts) = (parts(a), parts(b));
+    for (a_part, b_part) in a_parts.iter().zip(b_parts.iter()) {
+        let ordering = match (a_part, b_part) {
+            ((true, a), (true, b)) => a
+                .parse::<u64>()
+                .unwrap_or_default()
+                .cmp(&b.parse::<u64>().unwrap_or_default()),
+            ((_, a), (_, b)) => a.cmp(b),
+        };
+
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+
+    a_parts.len().cmp(&b_parts.len())
+}
+
+/// Returns the stdout of a CLI driver command or its stderr as a [`ScmError::CommandFailed`]
+pub(crate) fn check_output(command: &Command, output: Output) -> ScmResult<Vec<u8>> {
+    if output.status.success() {
+        return Ok(output.stdout);
+    }
+
+    let args = command
+        .get_args()
+        .map(|a| a.to_string_lossy())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    Err(ScmError::CommandFailed(
+        format!("{} {args}", command.get_program().to_string_lossy()),
+        String::from_utf8_lossy(&output.stderr).trim().to_string(),
+    ))
+}
+
+/// Include and exclude path patterns, normalised once so that filtering the paths of many commits
+/// doesn't parse the patterns again for every path
+#[derive(Debug, Default)]
+pub(crate) struct PathFilter {
+    include: Vec<Pattern>,
+    exclude: Vec<Pattern>,
+}
+
+impl PathFilter {
+    pub(crate) fn new(include: Option<&Vec<Pattern>>, exclude: Option<&Vec<Pattern>>) -> Self {
+        let normalize = |patterns: Option<&Vec<Pattern>>| -> Vec<Pattern> {
+            patterns
+                .into_iter()
+                .flatten()
+                .map(|pattern| {
+                    Pattern::new(pattern.as_str().trim_end_matches('/'))
+                        .unwrap_or_else(|_| pattern.clone())
+                })
+                .collect()
+        };
+
+        Self {
+            include: normalize(include),
+            exclude: normalize(exclude),
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty()
+    }
+
+    /// Normalised include patterns, i.e., without a trailing `/`
+    pub(crate) fn include(&self) -> &[Pattern] {
+        &self.include
+    }
+
+    /// Determines if the path matches any of the include patterns, when there are any, and none
+    /// of the exclude patterns
+    pub(crate) fn matches(&self, path: &str) -> bool {
+        (self.include.is_empty() || self.include.iter().any(|p| path_matches(p, path)))
+            && !self.exclude.iter().any(|p| path_matches(p, path))
+    }
+}
+
+/// Determines if the pattern matches the path or any of its parent directories
+fn path_matches(pattern: &Pattern, path: &str) -> bool {
+    Path::new(path).ancestors().any(|p| pattern.matches_path(p))
+}
+
+#[cfg(test)]
+mod tests {
+    use glob::Pattern;
+
+    use crate::drivers::{PathFilter, version_cmp};
+
+    #[test]
+    fn should_match_paths() {
+        let matches = |include: &str, path: &str| {
+            PathFilter::new(Some(&vec![Pattern::new(include).unwrap()]), None).matches(path)
+        };
+        assert!(matches("lib/", "lib/a.rs"));
+        assert!(matches("lib/*.rs", "lib/a.rs"));
+        assert!(!matches("bin", "lib/a.rs"));
+
+        let filter = PathFilter::new(None, Some(&vec![Pattern::new("docs/").unwrap()]));
+        assert!(filter.matches("lib/a.rs"));
+        assert!(!filter.matches("docs/a.md"));
+    }
+
+    #[test]
+    fn should_sort_versions() {
+        let mut tags = vec!["v1.10.0", "v1.2.0", "v1.2.0-rc.1", "v0.9"];
+        tags.sort_by(|a, b| version_cmp(a, b));
+        assert_eq!(vec!["v0.9", "v1.2.0", "v1.2.0-rc.1", "v1.10.0"], tags);
+    }
 }
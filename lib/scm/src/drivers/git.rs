@@ -1,12 +1,17 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::ffi::OsStr;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::rc::Rc;
 
+use git2::build::RepoBuilder;
 use git2::{
-    BranchType, Commit as Git2Commit, Config, DescribeFormatOptions, DescribeOptions, Direction,
-    IndexAddOption, Oid as Git2Oid, Repository as Git2Repository, Signature as Git2Signature,
-    Signature, StatusOptions,
+    AutotagOption, BranchType, Commit as Git2Commit, Config, DescribeFormatOptions,
+    DescribeOptions, DiffOptions, Direction, ErrorCode, FetchOptions, IndexAddOption,
+    Oid as Git2Oid, PushOptions, RemoteCallbacks, Repository as Git2Repository, Revwalk,
+    Signature as Git2Signature, Signature, Sort, StatusOptions, Tree,
 };
 use glob::Pattern;
 use indexmap::IndexMap;
@@ -16,9 +21,12 @@ use serde_derive::{Deserialize, Serialize};
 use strum::{Display, EnumIter, EnumString, VariantNames};
 
 use crate::GIT;
-use crate::commit::{ScmCommit, ScmCommitRange, ScmSignature, ScmTag};
-use crate::drivers::ScmRepository;
-use crate::errors::ScmResult;
+use crate::blame::{ScmBlame, ScmBlameHunk};
+use crate::commit::{ScmCommit, ScmCommitRange, ScmRevision, ScmSignature, ScmTag};
+use crate::drivers::{PathFilter, ScmRepository};
+use crate::errors::{ScmError, ScmResult};
+
+mod credentials;
 
 // TODO: Oid strut
 
@@ -31,6 +39,9 @@ use crate::errors::ScmResult;
 // TODO: find_last_commit
 // TODO: tags
 
+/// Maximum number of commits kept by [`GitScmRepository::find_commit`]
+const COMMIT_CACHE_CAPACITY: usize = 10_000;
+
 // TODO: should we make this an enum?
 const HOOK_NAMES: [&str; 21] = [
     "applypatch-msg",
@@ -56,10 +67,13 @@ const HOOK_NAMES: [&str; 21] = [
     "post-index-change",
 ];
 
+const DEFAULT_REMOTE: &str = "origin";
+
 lazy_static! {
     // TODO: probably doesnt need to be an owned type
     static ref TAG_SIGNATURE_REGEX: Regex = Regex::new(
-        r"(?s)-----BEGIN PGP SIGNATURE-----(.*?)-----END PGP SIGNATURE-----"
+        // gpg.format openpgp, ssh and x509 respectively
+        r"(?s)-----BEGIN (?:PGP SIGNATURE|SSH SIGNATURE|SIGNED MESSAGE)-----(.*?)-----END (?:PGP SIGNATURE|SSH SIGNATURE|SIGNED MESSAGE)-----"
     ).unwrap();
 }
 
@@ -277,8 +291,127 @@ pub enum TagSort {
     Version,
 }
 
+/// Options for walking the history of a git repository
+#[derive(Debug, Default)]
+pub struct GitWalkOptions<'a> {
+    pub range: Option<&'a ScmCommitRange>,
+
+    /// Only include commits which touch paths matching at least one of these patterns
+    pub include_paths: Option<&'a Vec<Pattern>>,
+
+    /// Skip commits which only touch paths matching these patterns
+    pub exclude_paths: Option<&'a Vec<Pattern>>,
+
+    /// Only follow the first parent of merge commits, similar to `git log --first-parent`
+    pub first_parent: bool,
+}
+
+/// Commits of a history walk, newest first.
+///
+/// Commits are filtered by the paths they touch by diffing their tree against their parents'.
+/// Similar to `git log`'s default history simplification, merge commits are skipped unless they
+/// touch matching paths compared to every parent.
+pub struct GitCommits<'r> {
+    repository: &'r GitScmRepository,
+    revwalk: Revwalk<'r>,
+    path_filter: PathFilter,
+    first_parent: bool,
+}
+
+impl<'r> GitCommits<'r> {
+    fn new(
+        repository: &'r GitScmRepository,
+        revwalk: Revwalk<'r>,
+        options: &GitWalkOptions,
+    ) -> Self {
+        Self {
+            repository,
+            revwalk,
+            path_filter: PathFilter::new(options.include_paths, options.exclude_paths),
+            first_parent: options.first_parent,
+        }
+    }
+
+    fn touches_paths(&self, oid: Git2Oid) -> ScmResult<bool> {
+        if self.path_filter.is_empty() {
+            return Ok(true);
+        }
+
+        let repository = &self.repository.inner;
+        let commit = repository.find_commit(oid)?;
+        let tree = commit.tree()?;
+        let parents: Vec<Git2Oid> = if self.first_parent {
+            commit.parent_ids().take(1).collect()
+        } else {
+            commit.parent_ids().collect()
+        };
+
+        if parents.is_empty() {
+            return self.diff_touches_paths(None, &tree);
+        }
+
+        for parent in parents {
+            let parent_tree = repository.find_commit(parent)?.tree()?;
+            if !self.diff_touches_paths(Some(&parent_tree), &tree)? {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    fn diff_touches_paths(&self, old_tree: Option<&Tree>, new_tree: &Tree) -> ScmResult<bool> {
+        // the pathspec lets libgit2 skip subtrees that can't match. A pattern also matches the
+        // files within the directories it matches, which `/*` covers as pathspec wildcards match
+        // across `/`. Exclude patterns are only applied to the resulting deltas
+        let mut options = DiffOptions::new();
+        for pattern in self.path_filter.include() {
+            options
+                .pathspec(pattern.as_str())
+                .pathspec(format!("{}/*", pattern.as_str()));
+        }
+
+        let diff = self.repository.inner.diff_tree_to_tree(
+            old_tree,
+            Some(new_tree),
+            Some(&mut options),
+        )?;
+
+        Ok(diff.deltas().any(|delta| {
+            [delta.old_file().path(), delta.new_file().path()]
+                .into_iter()
+                .flatten()
+                .filter_map(Path::to_str)
+                .any(|path| self.path_filter.matches(path))
+        }))
+    }
+}
+
+impl Iterator for GitCommits<'_> {
+    type Item = ScmResult<Rc<ScmCommit>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let oid = match self.revwalk.next()? {
+                Ok(oid) => oid,
+                Err(e) => return Some(Err(e.into())),
+            };
+
+            match self.touches_paths(oid) {
+                Ok(true) => return Some(self.repository.find_commit(oid)),
+                Ok(false) => continue,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
 pub struct GitScmRepository {
     inner: Git2Repository,
+
+    /// Commits already read from the repository keyed by their oid.
+    /// Cleared once it holds [`COMMIT_CACHE_CAPACITY`] commits to bound memory on large histories
+    commit_cache: RefCell<HashMap<Git2Oid, Rc<ScmCommit>>>,
 }
 
 impl GitScmRepository {
@@ -288,15 +421,90 @@ impl GitScmRepository {
     }
 
     pub fn discover<P: AsRef<Path>>(path: P) -> ScmResult<Self> {
-        Ok(Self {
-            inner: Git2Repository::discover(path)?,
-        })
+        Ok(Self::from_repository(Git2Repository::discover(path)?))
     }
 
     pub fn new<P: AsRef<Path>>(path: P) -> ScmResult<Self> {
-        Ok(Self {
-            inner: Git2Repository::open(&path)?,
-        })
+        Ok(Self::from_repository(Git2Repository::open(&path)?))
+    }
+
+    /// Clones the remote at `url` into `path` using the same credentials as fetching and pushing
+    pub fn clone_remote<P: AsRef<Path>>(url: &str, path: P) -> ScmResult<Self> {
+        let mut options = FetchOptions::new();
+        options
+            .remote_callbacks(credentials::remote_callbacks(Config::open_default()?))
+            .download_tags(AutotagOption::All);
+
+        let inner = RepoBuilder::new()
+            .fetch_options(options)
+            .clone(url, path.as_ref())?;
+        Ok(Self::from_repository(inner))
+    }
+
+    /// Fetches `refspecs` from the remote, such as pull request refs which aren't fetched by
+    /// default. No refspecs fetches the remote's configured refspecs.
+    pub fn fetch_refspecs(&self, refspecs: &[&str], remote: Option<&str>) -> ScmResult<()> {
+        let mut remote = self.inner.find_remote(remote.unwrap_or(DEFAULT_REMOTE))?;
+        let mut options = FetchOptions::new();
+        options
+            .remote_callbacks(self.remote_callbacks()?)
+            .download_tags(AutotagOption::All);
+        remote.fetch(refspecs, Some(&mut options), None)?;
+        Ok(())
+    }
+
+    fn from_repository(inner: Git2Repository) -> Self {
+        Self {
+            inner,
+            commit_cache: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the commit with the given oid, reading it from the repository on first use
+    pub fn find_commit(&self, oid: Git2Oid) -> ScmResult<Rc<ScmCommit>> {
+        if let Some(commit) = self.commit_cache.borrow().get(&oid) {
+            return Ok(Rc::clone(commit));
+        }
+
+        let commit = Rc::new(ScmCommit::from(self.inner.find_commit(oid)?));
+        let mut cache = self.commit_cache.borrow_mut();
+        if cache.len() >= COMMIT_CACHE_CAPACITY {
+            cache.clear();
+        }
+        cache.insert(oid, Rc::clone(&commit));
+        Ok(commit)
+    }
+
+    /// Walks the history of the repository, newest commit first.
+    ///
+    /// Walks from `HEAD` when no range is given. Commits are read lazily so callers only interested
+    /// in the most recent commits don't pay for walking the entire history.
+    pub fn walk_commits(&self, options: &GitWalkOptions) -> ScmResult<GitCommits<'_>> {
+        let mut revwalk = self.inner.revwalk()?;
+        revwalk.set_sorting(Sort::TIME | Sort::TOPOLOGICAL)?;
+        if options.first_parent {
+            revwalk.simplify_first_parent()?;
+        }
+
+        match options.range {
+            Some(range) => {
+                // ranges without commits leave the revwalk empty
+                if let Some(resolved) = self.resolve_range(range)? {
+                    revwalk.push(Git2Oid::from_str(&resolved.end)?)?;
+                    if let Some(start) = resolved.start {
+                        revwalk.hide(Git2Oid::from_str(&start)?)?;
+                    }
+                }
+            }
+            None => {
+                // an unborn HEAD, i.e., a repository without commits, has no history to walk
+                if let Some(head) = self.find_last_commit()? {
+                    revwalk.push(head.id())?;
+                }
+            }
+        }
+
+        Ok(GitCommits::new(self, revwalk, options))
     }
 
     fn find_last_commit(&self) -> ScmResult<Option<Git2Commit>> {
@@ -356,11 +564,77 @@ impl GitScmRepository {
         Ok(())
     }
 
-    fn push(&self) -> ScmResult<()> {
-        let mut remote = self.inner.find_remote("origin")?;
-        remote.connect(Direction::Push)?;
-        // TODO: what should this be?
-        Ok(remote.push(&["refs/heads/master:refs/heads/master"], None)?)
+    fn remote_callbacks(&self) -> ScmResult<RemoteCallbacks<'static>> {
+        Ok(credentials::remote_callbacks(self.inner.config()?))
+    }
+
+    fn push_refspec(&self, refspec: &str, remote: Option<&str>) -> ScmResult<()> {
+        let mut remote = self.inner.find_remote(remote.unwrap_or(DEFAULT_REMOTE))?;
+        let mut callbacks = self.remote_callbacks()?;
+        // rejected references, e.g., non-fast-forwards, are only reported through this callback
+        callbacks.push_update_reference(|reference, status| match status {
+            Some(status) => Err(git2::Error::from_str(&format!(
+                "Failed to push {reference}: {status}"
+            ))),
+            None => Ok(()),
+        });
+
+        let mut options = PushOptions::new();
+        options.remote_callbacks(callbacks);
+        remote.push(&[refspec], Some(&mut options))?;
+        Ok(())
+    }
+
+    /// Creates a signed annotated tag with git which handles GPG, SSH and X.509 signing formats
+    fn create_signed_tag(
+        &self,
+        name: &str,
+        target: &str,
+        message: &str,
+        signature: Option<&Signature>,
+    ) -> ScmResult<()> {
+        let mut command = Command::new(GIT);
+        command.current_dir(self.inner.workdir().unwrap_or(self.inner.path()));
+
+        // git uses the committer identity as the tagger
+        if let Some(signature) = signature {
+            if let Some(name) = signature.name() {
+                command.env("GIT_COMMITTER_NAME", name);
+            }
+            if let Some(email) = signature.email() {
+                command.env("GIT_COMMITTER_EMAIL", email);
+            }
+        }
+
+        let output = command
+            .args([
+                "tag",
+                "--sign",
+                "--cleanup=verbatim",
+                "-m",
+                message,
+                name,
+                target,
+            ])
+            .output()?;
+
+        if !output.status.success() {
+            return Err(ScmError::CommandFailed(
+                format!("{GIT} tag --sign {name} {target}"),
+                String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Name of the checked out branch
+    fn current_branch(&self) -> ScmResult<String> {
+        let head = self.inner.head()?;
+        match head.shorthand() {
+            Some(name) if head.is_branch() => Ok(name.to_string()),
+            _ => Err(ScmError::DetachedHead),
+        }
     }
 
     /// accepts git command args and returns its result as a list of filepaths.
@@ -471,11 +745,33 @@ impl ScmRepository for GitScmRepository {
     //     Ok(self.commit(message)?)
     // }
 
+    /// Commits the file and pushes the current branch.
+    ///
+    /// `path` is either relative to the working directory or an absolute path within it.
     fn write(&self, path: &Path, message: &str, signature: Option<&Signature>) -> ScmResult<()> {
+        let path = if path.is_absolute() {
+            let workdir = self
+                .inner
+                .workdir()
+                .ok_or(git2::Error::from_str("Cant write to a bare repository"))?;
+            path.canonicalize()?
+                .strip_prefix(workdir.canonicalize()?)
+                .map_err(|_| {
+                    git2::Error::from_str(&format!(
+                        "{} is outside of the repository",
+                        path.display()
+                    ))
+                })?
+                .to_path_buf()
+        } else {
+            path.to_path_buf()
+        };
+
         let mut index = self.inner.index()?;
-        index.add_path(path)?;
+        index.add_path(&path)?;
+        index.write()?;
         self.commit(message, signature)?;
-        self.push()
+        self.push_branch(&self.current_branch()?, None)
     }
 
     fn commit(&self, message: &str, signature: Option<&Signature>) -> ScmResult<()> {
@@ -498,57 +794,34 @@ impl ScmRepository for GitScmRepository {
         exclude_paths: Option<&Vec<Pattern>>,
         limit_commits: Option<usize>,
     ) -> ScmResult<Vec<ScmCommit>> {
-        // libgit2 and as a result git2's revwalk doesnt support filtering by paths touched by commits
-        // see https://github.com/libgit2/libgit2/issues/3041
-        let mut command = Command::new("git");
-        if let Some(git_workdir) = self.inner.workdir() {
-            command.current_dir(git_workdir);
-        }
-
-        // only output commit hash for now as we re-fetch commits to avoid parsing git log
-        let mut args = vec!["log".to_string(), "--pretty=%H".to_string()];
-
-        if let Some(num) = limit_commits {
-            args.extend(["-n".to_string(), num.to_string()])
-        }
-
-        if let Some(range) = range {
-            let start = &range.0;
-            let end = match &range.1 {
-                None => "HEAD",
-                Some(e) => e,
-            };
-
-            args.push(format!("{start}..{end}"));
-        }
-
-        let mut path_specs = vec![];
-        if let Some(include_paths) = include_paths {
-            for include_path in include_paths {
-                path_specs.push(include_path.to_string());
-            }
-        }
-
-        if let Some(exclude_paths) = exclude_paths {
-            for exclude_path in exclude_paths {
-                path_specs.push(format!(":(exclude){}", exclude_path));
-            }
-        }
-
-        if !path_specs.is_empty() {
-            args.push("-- .".to_string());
-            args.extend(path_specs);
-        }
-
-        let output = command.args(args).output()?.stdout;
-        let commits: Vec<ScmCommit> = String::from_utf8(output)?
-            .lines()
-            .filter_map(|id| Git2Oid::from_str(id).ok())
-            .filter_map(|oid| self.inner.find_commit(oid).ok())
-            .map(|c| c.into())
-            .collect();
+        let commits = self.walk_commits(&GitWalkOptions {
+            range,
+            include_paths,
+            exclude_paths,
+            first_parent: false,
+        })?;
+
+        commits
+            .take(limit_commits.unwrap_or(usize::MAX))
+            .map(|commit| commit.map(Rc::unwrap_or_clone))
+            .collect()
+    }
 
-        Ok(commits)
+    /// Blames the file as of `HEAD`
+    fn blame(&self, path: &Path) -> ScmResult<ScmBlame> {
+        let blame = self.inner.blame_file(path, None)?;
+        Ok(ScmBlame {
+            path: path.to_path_buf(),
+            hunks: blame
+                .iter()
+                .map(|hunk| ScmBlameHunk {
+                    commit_id: hunk.final_commit_id().to_string(),
+                    author: hunk.final_signature().into(),
+                    start_line: hunk.final_start_line(),
+                    lines: hunk.lines_in_hunk(),
+                })
+                .collect(),
+        })
     }
 
     /// Parses and returns a commit-tag map.
@@ -668,7 +941,7 @@ impl ScmRepository for GitScmRepository {
                 describe
                     .format(Some(DescribeFormatOptions::new().abbreviated_size(0)))
                     .ok()
-                    .map(|name| self.get_tag(&name))
+                    .and_then(|name| self.get_tag(&name).ok().flatten())
             })
     }
 
@@ -690,39 +963,80 @@ impl ScmRepository for GitScmRepository {
             if tag.is_empty() {
                 Ok(None)
             } else {
-                Ok(Some(self.get_tag(&tag)))
+                self.get_tag(&tag)
             }
         } else {
             Ok(None)
         }
     }
 
-    fn get_tag(&self, name: &str) -> ScmTag {
-        match self
-            .inner
-            .resolve_reference_from_short_name(name)
-            .and_then(|r| r.peel_to_tag())
-        {
+    /// Returns the tag with the time of the commit it points to
+    fn get_tag(&self, name: &str) -> ScmResult<Option<ScmTag>> {
+        let reference = match self.inner.find_reference(&format!("refs/tags/{name}")) {
+            Ok(reference) => reference,
+            Err(e) if e.code() == ErrorCode::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+
+        let commit = reference.peel_to_commit()?;
+        let timestamp = commit.time().seconds();
+        let tag = match reference.peel_to_tag() {
             Ok(tag) => ScmTag {
                 id: Some(tag.id().to_string()),
                 name: tag.name().unwrap_or(name).to_string(),
                 message: tag
                     .message()
                     .map(|msg| TAG_SIGNATURE_REGEX.replace(msg, "").trim().to_owned()),
-                timestamp: self
-                    .inner
-                    .find_commit(tag.id())
-                    .map(|c| c.time().seconds())
-                    .unwrap_or_default(),
+                timestamp,
             },
-            // TODO: should probably return None
-            _ => ScmTag {
-                id: None,
-                name: name.to_owned(),
+            // lightweight tags point directly to the commit
+            Err(_) => ScmTag {
+                id: Some(commit.id().to_string()),
+                name: name.to_string(),
                 message: None,
-                timestamp: 0,
+                timestamp,
             },
+        };
+
+        Ok(Some(tag))
+    }
+
+    /// Creates a lightweight tag, or an annotated tag when there is a message.
+    ///
+    /// Annotated tags are signed when `tag.gpgSign` is enabled. Signing is delegated to git so
+    /// that `gpg.format`, `gpg.program` and `user.signingKey` are honored.
+    fn create_tag(
+        &self,
+        name: &str,
+        revision: Option<&str>,
+        message: Option<&str>,
+        signature: Option<&Signature>,
+    ) -> ScmResult<ScmTag> {
+        let target = self.inner.revparse_single(revision.unwrap_or("HEAD"))?;
+        let sign = self
+            .inner
+            .config()?
+            .get_bool("tag.gpgSign")
+            .unwrap_or_default();
+
+        match message {
+            Some(message) if sign => {
+                self.create_signed_tag(name, &target.id().to_string(), message, signature)?
+            }
+            Some(message) => {
+                let tagger = match signature {
+                    Some(signature) => signature.to_owned(),
+                    None => self.inner.signature()?,
+                };
+                self.inner.tag(name, &target, &tagger, message, false)?;
+            }
+            None => {
+                self.inner.tag_lightweight(name, &target, false)?;
+            }
         }
+
+        self.get_tag(name)?
+            .ok_or_else(|| ScmError::TagNotFound(name.to_string()))
     }
 
     /// Determines if there are any current changes in the working directory / staging area
@@ -735,6 +1049,39 @@ impl ScmRepository for GitScmRepository {
         Ok(!statuses.is_empty())
     }
 
+    fn remote_url(&self) -> ScmResult<Option<String>> {
+        match self.inner.find_remote("origin") {
+            Ok(remote) => Ok(remote.url().map(|url| url.to_string())),
+            Err(e) if e.code() == ErrorCode::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn fetch(&self, remote: Option<&str>) -> ScmResult<()> {
+        self.fetch_refspecs(&[], remote)
+    }
+
+    fn push_branch(&self, branch: &str, remote: Option<&str>) -> ScmResult<()> {
+        self.push_refspec(&format!("refs/heads/{branch}:refs/heads/{branch}"), remote)
+    }
+
+    fn push_tag(&self, tag: &str, remote: Option<&str>) -> ScmResult<()> {
+        self.push_refspec(&format!("refs/tags/{tag}:refs/tags/{tag}"), remote)
+    }
+
+    fn remote_branches(&self, remote: Option<&str>) -> ScmResult<Vec<String>> {
+        let mut remote = self.inner.find_remote(remote.unwrap_or(DEFAULT_REMOTE))?;
+        let connection =
+            remote.connect_auth(Direction::Fetch, Some(self.remote_callbacks()?), None)?;
+
+        Ok(connection
+            .list()?
+            .iter()
+            .filter_map(|head| head.name().strip_prefix("refs/heads/"))
+            .map(String::from)
+            .collect())
+    }
+
     fn supported_hooks(&self) -> Vec<&'static str> {
         HOOK_NAMES.to_vec()
     }
@@ -810,31 +1157,631 @@ impl ScmRepository for GitScmRepository {
     // generic
 
     fn diff_paths(&self, range: Option<&ScmCommitRange>) -> ScmResult<Vec<PathBuf>> {
-        let mut args = vec!["diff".to_string(), "--name-only".to_string()];
-
-        if let Some(range) = range {
-            // TODO: move logic to a common area
-            let start = &range.0;
-            let end = match &range.1 {
-                None => "HEAD",
-                Some(e) => e,
-            };
+        let diff = match range {
+            Some(range) => {
+                let Some(resolved) = self.resolve_range(range)? else {
+                    return Ok(vec![]);
+                };
+
+                let start = match resolved.start {
+                    Some(start) => {
+                        Some(self.inner.find_commit(Git2Oid::from_str(&start)?)?.tree()?)
+                    }
+                    None => None,
+                };
+                let end = self
+                    .inner
+                    .find_commit(Git2Oid::from_str(&resolved.end)?)?
+                    .tree()?;
+                self.inner
+                    .diff_tree_to_tree(start.as_ref(), Some(&end), None)?
+            }
+            None => self.inner.diff_index_to_workdir(None, None)?,
+        };
 
-            args.push(format!("{start}..{end}"));
-        }
+        Ok(diff
+            .deltas()
+            .filter_map(|delta| delta.new_file().path().map(Path::to_path_buf))
+            .collect())
+    }
+
+    fn resolve_revision(
+        &self,
+        revision: &ScmRevision,
+        base: Option<&str>,
+    ) -> ScmResult<Option<String>> {
+        let base = || -> ScmResult<Git2Oid> {
+            Ok(match base {
+                Some(base) => Git2Oid::from_str(base)?,
+                None => self.inner.head()?.peel_to_commit()?.id(),
+            })
+        };
+
+        let oid = match revision {
+            ScmRevision::Branch(name) => self
+                .inner
+                .find_branch(name, BranchType::Local)
+                .or_else(|_| {
+                    self.inner
+                        .find_branch(&format!("origin/{name}"), BranchType::Remote)
+                })?
+                .get()
+                .peel_to_commit()?
+                .id(),
+            ScmRevision::Date(date) => {
+                let timestamp = ScmRevision::date_timestamp(date);
+                let mut revwalk = self.inner.revwalk()?;
+                revwalk.set_sorting(Sort::TIME)?;
+                revwalk.push(base()?)?;
+                for oid in revwalk {
+                    let oid = oid?;
+                    if self.inner.find_commit(oid)?.time().seconds() < timestamp {
+                        return Ok(Some(oid.to_string()));
+                    }
+                }
+
+                return Ok(None);
+            }
+            ScmRevision::Head => self.inner.head()?.peel_to_commit()?.id(),
+            ScmRevision::MergeBase(branch) => {
+                let branch = self.inner.revparse_single(branch)?.peel_to_commit()?.id();
+                self.inner.merge_base(branch, base()?)?
+            }
+            ScmRevision::Ref(revision) => {
+                self.inner.revparse_single(revision)?.peel_to_commit()?.id()
+            }
+            ScmRevision::Tag(name) => self
+                .inner
+                .revparse_single(&format!("refs/tags/{name}"))?
+                .peel_to_commit()?
+                .id(),
+        };
+
+        Ok(Some(oid.to_string()))
+    }
 
-        self.get_files(args)
+    fn parent(&self, id: &str) -> ScmResult<Option<String>> {
+        let commit = self.inner.find_commit(Git2Oid::from_str(id)?)?;
+        Ok(commit.parent_id(0).ok().map(|oid| oid.to_string()))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::env;
+    use std::path::{Path, PathBuf};
+    use std::process::Command;
+    use std::{env, fs};
 
+    use git2::{Oid, Repository, Signature};
     use glob::Pattern;
+    use tempfile::TempDir;
 
+    use crate::commit::{ScmCommitRange, ScmRevision};
     use crate::drivers::ScmRepository;
-    use crate::drivers::git::{GitScmRepository, TagSort};
+    use crate::drivers::git::{GitScmRepository, GitWalkOptions};
+
+    fn commit_file(repository: &Repository, path: &str, message: &str, time: i64) -> Oid {
+        let workdir = repository.workdir().unwrap();
+        fs::create_dir_all(workdir.join(path).parent().unwrap()).unwrap();
+        fs::write(workdir.join(path), message).unwrap();
+
+        let mut index = repository.index().unwrap();
+        index.add_path(Path::new(path)).unwrap();
+        index.write().unwrap();
+        let tree = repository.find_tree(index.write_tree().unwrap()).unwrap();
+
+        let signature =
+            Signature::new("test", "test@doctavious.com", &git2::Time::new(time, 0)).unwrap();
+        let parents = match repository.head() {
+            Ok(head) => vec![head.peel_to_commit().unwrap()],
+            Err(_) => vec![],
+        };
+        let parents: Vec<_> = parents.iter().collect();
+        repository
+            .commit(
+                Some("HEAD"),
+                &signature,
+                &signature,
+                message,
+                &tree,
+                &parents,
+            )
+            .unwrap()
+    }
+
+    fn messages(scm: &GitScmRepository, options: &GitWalkOptions) -> Vec<String> {
+        scm.walk_commits(options)
+            .unwrap()
+            .map(|c| c.unwrap().message.clone())
+            .collect()
+    }
+
+    #[test]
+    fn should_match_include_patterns_against_parent_directories() {
+        let dir = TempDir::new().unwrap();
+        let repository = Repository::init(dir.path()).unwrap();
+        commit_file(&repository, "README.md", "readme", 1);
+        commit_file(&repository, "lib/nested/a.rs", "lib a", 2);
+        commit_file(&repository, "bin/main.rs", "bin", 3);
+
+        let scm = GitScmRepository::new(dir.path()).unwrap();
+        let cases = [
+            ("l?b", vec!["lib a"]),
+            ("lib/nested", vec!["lib a"]),
+            ("*.rs", vec!["bin", "lib a"]),
+            ("bin/main.rs", vec!["bin"]),
+        ];
+        for (pattern, expected) in cases {
+            let include = vec![Pattern::new(pattern).unwrap()];
+            assert_eq!(
+                expected,
+                messages(
+                    &scm,
+                    &GitWalkOptions {
+                        include_paths: Some(&include),
+                        ..Default::default()
+                    }
+                ),
+                "{pattern}"
+            );
+        }
+    }
+
+    #[test]
+    fn should_walk_commits_filtered_by_paths() {
+        let dir = TempDir::new().unwrap();
+        let repository = Repository::init(dir.path()).unwrap();
+        let first = commit_file(&repository, "README.md", "readme", 1);
+        commit_file(&repository, "lib/a.rs", "lib a", 2);
+        commit_file(&repository, "bin/main.rs", "bin", 3);
+        commit_file(&repository, "lib/b.rs", "lib b", 4);
+
+        let scm = GitScmRepository::new(dir.path()).unwrap();
+        assert_eq!(
+            vec!["lib b", "bin", "lib a", "readme"],
+            messages(&scm, &GitWalkOptions::default())
+        );
+
+        let include = vec![Pattern::new("lib/").unwrap()];
+        assert_eq!(
+            vec!["lib b", "lib a"],
+            messages(
+                &scm,
+                &GitWalkOptions {
+                    include_paths: Some(&include),
+                    ..Default::default()
+                }
+            )
+        );
+
+        let exclude = vec![
+            Pattern::new("lib/*.rs").unwrap(),
+            Pattern::new("*.md").unwrap(),
+        ];
+        assert_eq!(
+            vec!["bin"],
+            messages(
+                &scm,
+                &GitWalkOptions {
+                    exclude_paths: Some(&exclude),
+                    ..Default::default()
+                }
+            )
+        );
+
+        let range = ScmCommitRange::new(ScmRevision::Ref(first.to_string()), None);
+        assert_eq!(
+            vec!["lib b", "lib a"],
+            messages(
+                &scm,
+                &GitWalkOptions {
+                    range: Some(&range),
+                    include_paths: Some(&include),
+                    ..Default::default()
+                }
+            )
+        );
+
+        let commits = scm.commits(None, Some(&include), None, Some(1)).unwrap();
+        assert_eq!(1, commits.len());
+        assert_eq!("lib b", commits[0].message);
+    }
+
+    #[test]
+    fn should_walk_first_parent() {
+        let dir = TempDir::new().unwrap();
+        let repository = Repository::init(dir.path()).unwrap();
+        commit_file(&repository, "README.md", "readme", 1);
+        let main = repository.head().unwrap().peel_to_commit().unwrap();
+        let feature = commit_file(&repository, "lib/a.rs", "feature", 2);
+        let feature = repository.find_commit(feature).unwrap();
+        repository
+            .head()
+            .unwrap()
+            .set_target(main.id(), "reset to main")
+            .unwrap();
+
+        // merge the feature commit into a main line that doesn't contain it
+        let signature =
+            Signature::new("test", "test@doctavious.com", &git2::Time::new(3, 0)).unwrap();
+        repository
+            .commit(
+                Some("HEAD"),
+                &signature,
+                &signature,
+                "merge",
+                &feature.tree().unwrap(),
+                &[&main, &feature],
+            )
+            .unwrap();
+
+        let scm = GitScmRepository::new(dir.path()).unwrap();
+        assert_eq!(
+            vec!["merge", "feature", "readme"],
+            messages(&scm, &GitWalkOptions::default())
+        );
+        assert_eq!(
+            vec!["merge", "readme"],
+            messages(
+                &scm,
+                &GitWalkOptions {
+                    first_parent: true,
+                    ..Default::default()
+                }
+            )
+        );
+
+        // the merge matches the feature parent for these paths so only the feature commit is kept
+        let include = vec![Pattern::new("lib").unwrap()];
+        assert_eq!(
+            vec!["feature"],
+            messages(
+                &scm,
+                &GitWalkOptions {
+                    include_paths: Some(&include),
+                    ..Default::default()
+                }
+            )
+        );
+        assert_eq!(
+            vec!["merge"],
+            messages(
+                &scm,
+                &GitWalkOptions {
+                    include_paths: Some(&include),
+                    first_parent: true,
+                    ..Default::default()
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn should_diff_paths_between_commits() {
+        let dir = TempDir::new().unwrap();
+        let repository = Repository::init(dir.path()).unwrap();
+        let first = commit_file(&repository, "README.md", "readme", 1);
+        commit_file(&repository, "lib/a.rs", "lib a", 2);
+
+        let scm = GitScmRepository::new(dir.path()).unwrap();
+        let range =
+            ScmCommitRange::new(ScmRevision::Ref(first.to_string()), Some(ScmRevision::Head));
+        assert_eq!(
+            vec![PathBuf::from("lib/a.rs")],
+            scm.diff_paths(Some(&range)).unwrap()
+        );
+    }
+
+    #[test]
+    fn should_resolve_ranges() {
+        let dir = TempDir::new().unwrap();
+        let repository = Repository::init(dir.path()).unwrap();
+        // 2024-01-01T00:00:00Z is 1704067200
+        let first = commit_file(&repository, "a.md", "first", 1_704_067_200 - 60);
+        let second = commit_file(&repository, "b.md", "second", 1_704_067_200 + 60);
+        let third = commit_file(&repository, "c.md", "third", 1_704_067_200 + 120);
+        repository
+            .tag_lightweight(
+                "v1.0.0",
+                &repository.find_object(second, None).unwrap(),
+                false,
+            )
+            .unwrap();
+        repository
+            .branch("feature", &repository.find_commit(second).unwrap(), false)
+            .unwrap();
+
+        let scm = GitScmRepository::new(dir.path()).unwrap();
+        let resolve = |range: &str| {
+            let resolved = scm.resolve_range(&range.parse().unwrap()).unwrap().unwrap();
+            (resolved.start, resolved.end)
+        };
+
+        let (first, second, third) = (first.to_string(), second.to_string(), third.to_string());
+        assert_eq!(
+            (Some(second.clone()), third.clone()),
+            resolve("tag:v1.0.0..")
+        );
+        assert_eq!(
+            (Some(first.clone()), third.clone()),
+            resolve("[tag:v1.0.0..HEAD]")
+        );
+        assert_eq!(
+            (Some(first.clone()), second.clone()),
+            resolve("(date:2024-01-01..branch:feature]")
+        );
+        assert_eq!(
+            (Some(second.clone()), third.clone()),
+            resolve("merge-base:feature..")
+        );
+        assert_eq!((Some(first.clone()), third.clone()), resolve("last:2"));
+        assert_eq!((None, third.clone()), resolve("last:10"));
+        assert_eq!(
+            (Some(first.clone()), second.clone()),
+            resolve(&format!("[{second}..{third})"))
+        );
+        assert_eq!((None, third.clone()), resolve("since:2023-12-31"));
+
+        let range = "since:2024-01-01".parse().unwrap();
+        let commits = scm.commits(Some(&range), None, None, None).unwrap();
+        assert_eq!(
+            vec!["third", "second"],
+            commits
+                .iter()
+                .map(|c| c.message.as_str())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn should_blame_file_and_find_history() {
+        let dir = TempDir::new().unwrap();
+        let repository = Repository::init(dir.path()).unwrap();
+        let first = commit_file(&repository, "README.md", "readme\n", 1);
+        let second = commit_file(&repository, "lib/a.rs", "lib a", 2);
+
+        // append a line as another author
+        fs::write(dir.path().join("README.md"), "readme\nsecond line\n").unwrap();
+        let mut index = repository.index().unwrap();
+        index.add_path(Path::new("README.md")).unwrap();
+        index.write().unwrap();
+        let tree = repository.find_tree(index.write_tree().unwrap()).unwrap();
+        let jane = Signature::new("jane", "jane@doctavious.com", &git2::Time::new(3, 0)).unwrap();
+        let parent = repository.head().unwrap().peel_to_commit().unwrap();
+        let third = repository
+            .commit(
+                Some("HEAD"),
+                &jane,
+                &jane,
+                "docs: expand readme",
+                &tree,
+                &[&parent],
+            )
+            .unwrap();
+
+        let scm = GitScmRepository::new(dir.path()).unwrap();
+        let blame = scm.blame(Path::new("README.md")).unwrap();
+        assert_eq!(
+            vec![
+                (first.to_string(), 1..=1, Some("test".to_string())),
+                (third.to_string(), 2..=2, Some("jane".to_string()))
+            ],
+            blame
+                .hunks
+                .iter()
+                .map(|h| (h.commit_id.clone(), h.line_range(), h.author.name.clone()))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(third.to_string(), blame.last_changed().unwrap().commit_id);
+        assert_eq!(2, blame.authors().len());
+
+        let history = scm.file_history(Path::new("README.md"), None).unwrap();
+        assert_eq!(
+            vec![third.to_string(), first.to_string()],
+            history.into_iter().map(|c| c.id).collect::<Vec<_>>()
+        );
+
+        let range = ScmCommitRange::new(ScmRevision::Ref(second.to_string()), None);
+        let history = scm
+            .file_history(Path::new("README.md"), Some(&range))
+            .unwrap();
+        assert_eq!(
+            vec![third.to_string()],
+            history.into_iter().map(|c| c.id).collect::<Vec<_>>()
+        );
+
+        assert_eq!(
+            first.to_string(),
+            scm.first_commit_for(Path::new("README.md"))
+                .unwrap()
+                .unwrap()
+                .id
+        );
+        assert_eq!(
+            second.to_string(),
+            scm.first_commit_for(Path::new("lib")).unwrap().unwrap().id
+        );
+        assert!(
+            scm.first_commit_for(Path::new("missing.md"))
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn should_create_tags() {
+        let dir = TempDir::new().unwrap();
+        let repository = Repository::init(dir.path()).unwrap();
+        let first = commit_file(&repository, "README.md", "readme", 1);
+        let second = commit_file(&repository, "lib/a.rs", "lib a", 2);
+
+        let scm = GitScmRepository::new(dir.path()).unwrap();
+        assert!(scm.get_tag("v1.0.0").unwrap().is_none());
+
+        let tagger = Signature::new("test", "test@doctavious.com", &git2::Time::new(3, 0)).unwrap();
+        let lightweight = scm
+            .create_tag("v1.0.0", Some(&first.to_string()), None, Some(&tagger))
+            .unwrap();
+        assert_eq!(Some(first.to_string()), lightweight.id);
+        assert_eq!(None, lightweight.message);
+        assert_eq!(1, lightweight.timestamp);
+
+        let annotated = scm
+            .create_tag("v2.0.0", None, Some("Release v2.0.0"), Some(&tagger))
+            .unwrap();
+        let tag = repository
+            .find_tag(Oid::from_str(annotated.id.as_deref().unwrap()).unwrap())
+            .unwrap();
+        assert_eq!(second, tag.target_id());
+        assert_eq!(Some("test"), tag.tagger().unwrap().name());
+        assert_eq!(Some("Release v2.0.0".to_string()), annotated.message);
+        assert_eq!(2, annotated.timestamp);
+        assert_eq!(
+            annotated,
+            scm.get_tag("v2.0.0").unwrap().unwrap(),
+            "created tag should match the tag read back"
+        );
+
+        assert!(scm.create_tag("v2.0.0", None, None, None).is_err());
+    }
+
+    #[test]
+    #[ignore = "requires ssh-keygen"]
+    fn should_sign_tags_when_configured() {
+        let dir = TempDir::new().unwrap();
+        let key = dir.path().join("signing_key");
+        let status = Command::new("ssh-keygen")
+            .args(["-q", "-t", "ed25519", "-N", "", "-f"])
+            .arg(&key)
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        let repository = Repository::init(dir.path().join("repo")).unwrap();
+        commit_file(&repository, "README.md", "readme", 1);
+        let mut config = repository.config().unwrap();
+        config.set_bool("tag.gpgSign", true).unwrap();
+        config.set_str("gpg.format", "ssh").unwrap();
+        config
+            .set_str("user.signingKey", &key.to_string_lossy())
+            .unwrap();
+
+        let scm = GitScmRepository::new(dir.path().join("repo")).unwrap();
+        let tagger = Signature::now("test", "test@doctavious.com").unwrap();
+        let signed = scm
+            .create_tag("v1.0.0", None, Some("Release v1.0.0"), Some(&tagger))
+            .unwrap();
+
+        let tag = repository
+            .find_tag(Oid::from_str(signed.id.as_deref().unwrap()).unwrap())
+            .unwrap();
+        assert!(
+            tag.message()
+                .unwrap()
+                .contains("-----BEGIN SSH SIGNATURE-----")
+        );
+        assert_eq!(Some("test@doctavious.com"), tag.tagger().unwrap().email());
+        // signature is stripped from the message
+        assert_eq!(Some("Release v1.0.0".to_string()), signed.message);
+    }
+
+    #[test]
+    fn should_fetch_and_push_over_file_remote() {
+        let dir = TempDir::new().unwrap();
+        let remote_path = dir.path().join("remote.git");
+        Repository::init_bare(&remote_path).unwrap();
+        let remote_url = format!("file://{}", remote_path.display());
+
+        let repository = Repository::init(dir.path().join("local")).unwrap();
+        repository.remote("origin", &remote_url).unwrap();
+        let first = commit_file(&repository, "README.md", "readme", 1);
+        repository
+            .tag_lightweight(
+                "v1.0.0",
+                &repository.find_object(first, None).unwrap(),
+                false,
+            )
+            .unwrap();
+
+        let scm = GitScmRepository::new(dir.path().join("local")).unwrap();
+        let branch = scm.current_branch().unwrap();
+        scm.push_branch(&branch, None).unwrap();
+        scm.push_tag("v1.0.0", None).unwrap();
+        assert_eq!(vec![branch.clone()], scm.remote_branches(None).unwrap());
+
+        let remote = Repository::open_bare(&remote_path).unwrap();
+        assert_eq!(
+            first,
+            remote.revparse_single("refs/tags/v1.0.0").unwrap().id()
+        );
+
+        // push a commit from another clone and fetch it
+        let clone = Repository::clone(&remote_url, dir.path().join("clone")).unwrap();
+        let second = commit_file(&clone, "lib/a.rs", "lib a", 2);
+        GitScmRepository::new(dir.path().join("clone"))
+            .unwrap()
+            .push_branch(&branch, None)
+            .unwrap();
+
+        scm.fetch(None).unwrap();
+        assert_eq!(
+            second,
+            repository
+                .revparse_single(&format!("origin/{branch}"))
+                .unwrap()
+                .id()
+        );
+
+        // local history is now behind the remote
+        commit_file(&repository, "bin/main.rs", "bin", 3);
+        assert!(scm.push_branch(&branch, None).is_err());
+        assert!(scm.remote_repository().unwrap().is_none());
+    }
+
+    #[test]
+    fn should_clone_and_fetch_pull_request_refs() {
+        let dir = TempDir::new().unwrap();
+        let remote_path = dir.path().join("remote");
+        let remote = Repository::init(&remote_path).unwrap();
+        commit_file(&remote, "README.md", "readme", 1);
+        let head = commit_file(&remote, "lib/a.rs", "lib a", 2);
+        remote
+            .reference("refs/pull/7/head", head, false, "")
+            .unwrap();
+        remote
+            .reset(
+                &remote.revparse_single("HEAD~1").unwrap(),
+                git2::ResetType::Hard,
+                None,
+            )
+            .unwrap();
+
+        let remote_url = format!("file://{}", remote_path.display());
+        let scm = GitScmRepository::clone_remote(&remote_url, dir.path().join("clone")).unwrap();
+        let clone = Repository::open(dir.path().join("clone")).unwrap();
+        assert!(clone.find_reference("refs/remotes/origin/pull/7").is_err());
+
+        scm.fetch_refspecs(&["+refs/pull/7/head:refs/remotes/origin/pull/7"], None)
+            .unwrap();
+        assert_eq!(
+            head,
+            clone
+                .find_reference("refs/remotes/origin/pull/7")
+                .unwrap()
+                .target()
+                .unwrap()
+        );
+        scm.checkout(&head.to_string()).unwrap();
+        assert!(dir.path().join("clone/lib/a.rs").is_file());
+    }
+
+    #[test]
+    fn should_walk_empty_repository() {
+        let dir = TempDir::new().unwrap();
+        Repository::init(dir.path()).unwrap();
+        let scm = GitScmRepository::new(dir.path()).unwrap();
+        assert!(messages(&scm, &GitWalkOptions::default()).is_empty());
+    }
 
     #[test]
     fn commits() {
@@ -0,0 +1,128 @@
+use std::env;
+
+use git2::{Config, Cred, CredentialType, RemoteCallbacks};
+
+use crate::platforms::ScmPlatform;
+use crate::remote::ScmRemote;
+
+/// Token used to authenticate with any HTTPS remote, regardless of platform
+pub const DOCTAVIOUS_GIT_TOKEN: &str = "DOCTAVIOUS_GIT_TOKEN";
+
+/// Environment variables platform tokens are read from along with the username sent with them
+fn platform_tokens(platform: &ScmPlatform) -> &'static [(&'static str, &'static str)] {
+    match platform {
+        ScmPlatform::Azure => &[
+            ("AZURE_DEVOPS_EXT_PAT", "pat"),
+            ("SYSTEM_ACCESSTOKEN", "pat"),
+        ],
+        ScmPlatform::BitBucket => &[("BITBUCKET_TOKEN", "x-token-auth")],
+        ScmPlatform::Gitea => &[("GITEA_TOKEN", "oauth2")],
+        ScmPlatform::GitHub => &[
+            ("GITHUB_TOKEN", "x-access-token"),
+            ("GH_TOKEN", "x-access-token"),
+        ],
+        ScmPlatform::GitLab => &[
+            ("GITLAB_TOKEN", "oauth2"),
+            ("CI_JOB_TOKEN", "gitlab-ci-token"),
+        ],
+        ScmPlatform::Gogs => &[("GOGS_TOKEN", "git")],
+    }
+}
+
+/// Username and token for the remote URL
+fn token<F>(url: &str, var: F) -> Option<(&'static str, String)>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    if let Some(token) = var(DOCTAVIOUS_GIT_TOKEN) {
+        return Some(("git", token));
+    }
+
+    let remote = ScmRemote::parse(url)?;
+    platform_tokens(&remote.platform)
+        .iter()
+        .find_map(|(key, username)| var(key).map(|token| (*username, token)))
+}
+
+/// Callbacks authenticating with remotes.
+///
+/// Credentials are attempted in order of SSH agent, tokens from environment variables, git
+/// credential helpers and lastly default credentials such as Kerberos. Each is only attempted
+/// once as libgit2 keeps asking for credentials while authentication fails.
+pub(crate) fn remote_callbacks(config: Config) -> RemoteCallbacks<'static> {
+    let mut attempted_ssh_agent = false;
+    let mut attempted_token = false;
+    let mut attempted_credential_helper = false;
+    let mut attempted_default = false;
+
+    let mut callbacks = RemoteCallbacks::new();
+    callbacks.credentials(move |url, username_from_url, allowed| {
+        if allowed.contains(CredentialType::USERNAME) {
+            return Cred::username(username_from_url.unwrap_or("git"));
+        }
+
+        if allowed.contains(CredentialType::SSH_KEY) && !attempted_ssh_agent {
+            attempted_ssh_agent = true;
+            return Cred::ssh_key_from_agent(username_from_url.unwrap_or("git"));
+        }
+
+        if allowed.contains(CredentialType::USER_PASS_PLAINTEXT) {
+            if !attempted_token {
+                attempted_token = true;
+                if let Some((username, token)) = token(url, |key| env::var(key).ok()) {
+                    return Cred::userpass_plaintext(username_from_url.unwrap_or(username), &token);
+                }
+            }
+
+            if !attempted_credential_helper {
+                attempted_credential_helper = true;
+                if let Ok(cred) = Cred::credential_helper(&config, url, username_from_url) {
+                    return Ok(cred);
+                }
+            }
+        }
+
+        if allowed.contains(CredentialType::DEFAULT) && !attempted_default {
+            attempted_default = true;
+            return Cred::default();
+        }
+
+        Err(git2::Error::from_str(&format!(
+            "Unable to authenticate with {url}"
+        )))
+    });
+
+    callbacks
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::drivers::git::credentials::{DOCTAVIOUS_GIT_TOKEN, token};
+
+    #[test]
+    fn should_find_token_for_remote() {
+        let vars = HashMap::from([("GITHUB_TOKEN", "gh"), ("CI_JOB_TOKEN", "job")]);
+        let var = |key: &str| vars.get(key).map(|v| v.to_string());
+
+        assert_eq!(
+            Some(("x-access-token", "gh".to_string())),
+            token("https://github.com/doctavious/doctavious.git", var)
+        );
+        assert_eq!(
+            Some(("gitlab-ci-token", "job".to_string())),
+            token("https://gitlab.com/group/project.git", var)
+        );
+        assert_eq!(None, token("https://bitbucket.org/owner/repo.git", var));
+        assert_eq!(None, token("https://example.com/owner/repo.git", var));
+
+        let vars = HashMap::from([(DOCTAVIOUS_GIT_TOKEN, "any")]);
+        assert_eq!(
+            Some(("git", "any".to_string())),
+            token("https://example.com/owner/repo.git", |key| {
+                vars.get(key).map(|v| v.to_string())
+            })
+        );
+    }
+}
@@ -1,14 +1,20 @@
+use std::ffi::OsStr;
 use std::path::{Path, PathBuf};
+use std::process::Command;
 
 use git2::Signature;
 use glob::Pattern;
-use indexmap::IndexMap;
+use indexmap::{IndexMap, IndexSet};
 use regex::Regex;
 
-use crate::commit::{ScmCommit, ScmCommitRange, ScmTag};
-use crate::drivers::ScmRepository;
+use crate::HG;
+use crate::blame::ScmBlame;
+use crate::commit::{
+    ScmCommit, ScmCommitRange, ScmResolvedRange, ScmRevision, ScmSignature, ScmTag,
+};
 use crate::drivers::git::TagSort;
-use crate::errors::ScmResult;
+use crate::drivers::{ScmRepository, check_output, version_cmp};
+use crate::errors::{ScmError, ScmResult};
 
 const HOOK_NAMES: [&str; 13] = [
     "changegroup",
@@ -26,29 +32,238 @@ const HOOK_NAMES: [&str; 13] = [
     "update",
 ];
 
-pub struct HgScmRepository;
+// `hg log` templates use ASCII separators as they are unlikely to appear in commit messages
+const COMMIT_TEMPLATE: &str =
+    "{node}\x1f{author|person}\x1f{author|email}\x1f{date|hgdate}\x1f{desc}\x1e";
+const TAG_TEMPLATE: &str = "{node}\x1f{join(tags, '\x1d')}\x1f{date|hgdate}\x1e";
+const ANNOTATE_TEMPLATE: &str =
+    "{lines % '{node}\x1f{user|person}\x1f{user|email}\x1f{date|hgdate}\x1e'}";
+const FIELD_SEPARATOR: char = '\x1f';
+const RECORD_SEPARATOR: char = '\x1e';
+const LIST_SEPARATOR: char = '\x1d';
+
+// hg tracks the working directory parent as `.` and always has a `tip` tag
+const WORKING_PARENT: &str = ".";
+const TIP: &str = "tip";
+const NULL_REVISION: &str = "null";
+
+pub struct HgScmRepository {
+    root: PathBuf,
+}
+
+impl HgScmRepository {
+    pub fn init<P: AsRef<Path>>(path: P) -> ScmResult<Self> {
+        let mut command = Command::new(HG);
+        let output = command.arg("init").arg(path.as_ref()).output()?;
+        check_output(&command, output)?;
+        HgScmRepository::new(path)
+    }
+
+    /// Finds the root of the Mercurial repository containing `path`
+    pub fn discover<P: AsRef<Path>>(path: P) -> ScmResult<Self> {
+        let mut command = Command::new(HG);
+        command.current_dir(path).arg("root");
+        let output = command.output()?;
+        let root = String::from_utf8(check_output(&command, output)?)?;
+
+        Ok(Self {
+            root: PathBuf::from(root.trim_end()),
+        })
+    }
+
+    pub fn new<P: AsRef<Path>>(path: P) -> ScmResult<Self> {
+        let root = path.as_ref();
+        if !root.join(".hg").is_dir() {
+            return Err(ScmError::Unsupported);
+        }
+
+        Ok(Self {
+            root: root.to_path_buf(),
+        })
+    }
+
+    fn command(&self) -> Command {
+        let mut command = Command::new(HG);
+        // HGPLAIN disables user configuration that would change output such as aliases and color
+        command
+            .current_dir(&self.root)
+            .env("HGPLAIN", "1")
+            .env("HGENCODING", "utf-8");
+        command
+    }
+
+    /// Runs hg with the given args and returns stdout. Errors when hg exits unsuccessfully
+    fn run<I, S>(&self, args: I) -> ScmResult<String>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        let mut command = self.command();
+        command.args(args);
+        let output = command.output()?;
+        Ok(String::from_utf8(check_output(&command, output)?)?)
+    }
+
+    /// accepts hg command args and returns its result as a list of filepaths.
+    fn get_files<I, S>(&self, args: I) -> ScmResult<Vec<PathBuf>>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        // commands such as `hg files` exit with 1 when there are no matches so we dont check status
+        let output = self.command().args(args).output()?.stdout;
+        let files: Vec<_> = output
+            .split(|&b| b == b'\n')
+            .filter(|&x| !x.is_empty())
+            .filter_map(|line| std::str::from_utf8(line).ok())
+            .map(|s| PathBuf::from(s.trim_end()))
+            .collect();
+
+        Ok(files)
+    }
+
+    fn log(&self, revset: &str, file_patterns: Vec<String>) -> ScmResult<Vec<ScmCommit>> {
+        let mut args = vec![
+            "log".to_string(),
+            "-r".to_string(),
+            revset.to_string(),
+            "-T".to_string(),
+            COMMIT_TEMPLATE.to_string(),
+        ];
+        args.extend(file_patterns);
+
+        let output = self.run(args)?;
+        Ok(records(&output).filter_map(parse_commit).collect())
+    }
+
+    /// Node of the single revision the revset resolves to. The null revision is treated as none
+    fn node(&self, revset: &str) -> ScmResult<Option<String>> {
+        let output = self.run(["log", "-r", &format!("last({revset})"), "-T", "{node}"])?;
+        let node = output.trim();
+        if node.is_empty() || node.chars().all(|c| c == '0') {
+            Ok(None)
+        } else {
+            Ok(Some(node.to_string()))
+        }
+    }
+
+    /// Tagged revisions, in revision order, along with their tags.
+    fn tagged_revisions(&self) -> ScmResult<Vec<ScmTag>> {
+        let output = self.run(["log", "-r", "tag()", "-T", TAG_TEMPLATE])?;
+
+        let mut tags = vec![];
+        for record in records(&output) {
+            let mut fields = record.split(FIELD_SEPARATOR);
+            let (Some(node), Some(names), Some(date)) =
+                (fields.next(), fields.next(), fields.next())
+            else {
+                continue;
+            };
+
+            let timestamp = parse_hgdate(date);
+            for name in names
+                .split(LIST_SEPARATOR)
+                .filter(|n| !n.is_empty() && *n != TIP)
+            {
+                tags.push(ScmTag {
+                    id: Some(node.to_string()),
+                    name: name.to_string(),
+                    message: None,
+                    timestamp,
+                });
+            }
+        }
+
+        Ok(tags)
+    }
+
+    fn signature_args(signature: Option<&Signature>) -> Vec<String> {
+        let Some(signature) = signature else {
+            return vec![];
+        };
+
+        let user = match (signature.name(), signature.email()) {
+            (Some(name), Some(email)) => format!("{name} <{email}>"),
+            (Some(name), None) => name.to_string(),
+            (None, Some(email)) => email.to_string(),
+            (None, None) => return vec![],
+        };
+
+        let when = signature.when();
+        // hg offsets are seconds west of UTC whereas git offsets are minutes east of UTC
+        let offset = -(when.offset_minutes() as i64) * 60;
+
+        vec![
+            "-u".to_string(),
+            user,
+            "-d".to_string(),
+            format!("{} {offset}", when.seconds()),
+        ]
+    }
+
+    fn push(&self, args: &[&str]) -> ScmResult<()> {
+        let mut command = self.command();
+        command.arg("push").args(args);
+        let output = command.output()?;
+        // hg push exits with 1 when there is nothing to push
+        if output.status.code() == Some(1) {
+            return Ok(());
+        }
+
+        check_output(&command, output)?;
+        Ok(())
+    }
+}
 
 impl ScmRepository for HgScmRepository {
     fn checkout(&self, reference: &str) -> ScmResult<()> {
-        todo!()
+        self.run(["update", reference])?;
+        Ok(())
     }
 
     fn branch_exists(&self, branch_name: &str) -> ScmResult<bool> {
-        todo!()
+        let re = Regex::new(branch_name)?;
+
+        // bookmarks are the closest equivalent to git branches but named branches are also checked
+        let bookmarks = self.run(["bookmarks", "-T", "{bookmark}\n"])?;
+        let branches = self.run(["branches", "-T", "{branch}\n"])?;
+
+        Ok(bookmarks
+            .lines()
+            .chain(branches.lines())
+            .any(|name| re.is_match(name)))
     }
 
     fn write(&self, path: &Path, message: &str, signature: Option<&Signature>) -> ScmResult<()> {
-        todo!()
+        let mut args = vec![
+            "commit".to_string(),
+            "--addremove".to_string(),
+            "-m".to_string(),
+            message.to_string(),
+        ];
+        args.extend(Self::signature_args(signature));
+        args.push(path.to_string_lossy().to_string());
+        self.run(args)?;
+
+        // only the new commit, and its ancestors, rather than every outgoing changeset
+        self.push(&["-r", "."])
     }
 
     fn commit(&self, message: &str, signature: Option<&Signature>) -> ScmResult<()> {
-        todo!()
+        let mut args = vec!["commit".to_string(), "-m".to_string(), message.to_string()];
+        args.extend(Self::signature_args(signature));
+        self.run(args)?;
+        Ok(())
     }
 
     fn last_commit(&self) -> ScmResult<Option<ScmCommit>> {
-        todo!()
+        // the working directory parent of an empty repository is the null revision
+        Ok(self.log(". - null", vec![])?.into_iter().next())
     }
 
+    /// Parses and returns the commits.
+    ///
+    /// Commits are sorted newest to oldest
     fn commits(
         &self,
         range: Option<&ScmCommitRange>,
@@ -56,76 +271,631 @@ impl ScmRepository for HgScmRepository {
         exclude_paths: Option<&Vec<Pattern>>,
         limit_commits: Option<usize>,
     ) -> ScmResult<Vec<ScmCommit>> {
-        todo!()
+        // mirror git's `start..end` which are commits reachable from end but not from start
+        let mut revset = match range {
+            Some(range) => match self.resolve_range(range)? {
+                Some(ScmResolvedRange {
+                    start: Some(start),
+                    end,
+                }) => format!("reverse(only({end}, {start}))"),
+                Some(ScmResolvedRange { start: None, end }) => format!("reverse(::{end})"),
+                None => return Ok(vec![]),
+            },
+            None => format!("reverse(::{WORKING_PARENT})"),
+        };
+
+        if let Some(limit) = limit_commits {
+            revset = format!("limit({revset}, {limit})");
+        }
+
+        let mut file_patterns = vec![];
+        if let Some(include_paths) = include_paths {
+            for include_path in include_paths {
+                file_patterns.push(file_pattern(include_path));
+            }
+        }
+
+        if let Some(exclude_paths) = exclude_paths {
+            for exclude_path in exclude_paths {
+                file_patterns.push("-X".to_string());
+                file_patterns.push(file_pattern(exclude_path));
+            }
+        }
+
+        self.log(&revset, file_patterns)
+    }
+
+    /// Annotates the file as of the working directory parent
+    fn blame(&self, path: &Path) -> ScmResult<ScmBlame> {
+        let output = self.run([
+            "annotate".to_string(),
+            "-r".to_string(),
+            WORKING_PARENT.to_string(),
+            "-T".to_string(),
+            ANNOTATE_TEMPLATE.to_string(),
+            format!("path:{}", path.to_string_lossy()),
+        ])?;
+
+        Ok(ScmBlame::from_lines(
+            path.to_path_buf(),
+            records(&output).filter_map(parse_annotation),
+        ))
     }
 
+    /// Parses and returns a commit-tag map.
+    ///
+    /// Mercurial tags are always lightweight so tags won't have a message.
+    /// `suffix_order` is not supported.
     fn tags(
         &self,
         includes: Option<&Vec<Regex>>,
         excludes: Option<&Vec<Regex>>,
         sort: TagSort,
-        suffix_order: Option<&Vec<String>>,
+        _suffix_order: Option<&Vec<String>>,
     ) -> ScmResult<IndexMap<String, ScmTag>> {
-        todo!()
+        let mut tags: Vec<ScmTag> = self
+            .tagged_revisions()?
+            .into_iter()
+            .filter(|tag| includes.is_none_or(|i| i.iter().any(|r| r.is_match(&tag.name))))
+            .filter(|tag| excludes.is_none_or(|e| !e.iter().any(|r| r.is_match(&tag.name))))
+            .collect();
+
+        match sort {
+            TagSort::Alphabetical => tags.sort_by(|a, b| a.name.cmp(&b.name)),
+            // revision order is already chronological
+            TagSort::Chronological => {}
+            TagSort::Version => tags.sort_by(|a, b| version_cmp(&a.name, &b.name)),
+        }
+
+        Ok(tags
+            .into_iter()
+            .filter_map(|tag| Some((tag.id.clone()?, tag)))
+            .collect())
     }
 
+    /// Returns the current tag.
+    ///
+    /// It is the closest tag reachable from the working directory parent
     fn current_tag(&self) -> Option<ScmTag> {
-        todo!()
+        let latest = self
+            .run(["log", "-r", WORKING_PARENT, "-T", "{latesttag}"])
+            .ok()?;
+
+        // `latesttag` joins multiple tags on the same revision with `:` and is `null` if none
+        latest
+            .split(':')
+            .find(|t| !t.is_empty() && *t != "null")
+            .and_then(|t| self.get_tag(t).ok().flatten())
     }
 
     fn latest_tag(&self) -> ScmResult<Option<ScmTag>> {
-        todo!()
+        Ok(self.tagged_revisions()?.pop())
+    }
+
+    fn get_tag(&self, name: &str) -> ScmResult<Option<ScmTag>> {
+        Ok(self
+            .tagged_revisions()?
+            .into_iter()
+            .find(|t| t.name == name))
     }
 
-    fn get_tag(&self, name: &str) -> ScmTag {
-        todo!()
+    /// Tags are committed to `.hgtags` and the message is used as the message of that commit.
+    /// Mercurial doesn't support signing tags without the gpg extension so tags aren't signed.
+    fn create_tag(
+        &self,
+        name: &str,
+        revision: Option<&str>,
+        message: Option<&str>,
+        signature: Option<&Signature>,
+    ) -> ScmResult<ScmTag> {
+        let mut args = vec!["tag".to_string()];
+        if let Some(revision) = revision {
+            args.extend(["-r".to_string(), revision.to_string()]);
+        }
+        if let Some(message) = message {
+            args.extend(["-m".to_string(), message.to_string()]);
+        }
+        args.extend(Self::signature_args(signature));
+        args.push(name.to_string());
+        self.run(args)?;
+
+        self.get_tag(name)?
+            .ok_or_else(|| ScmError::TagNotFound(name.to_string()))
     }
 
+    /// Determines if there are any changes to tracked files in the working directory
     fn is_dirty(&self) -> ScmResult<bool> {
-        todo!()
+        let output = self.run(["status", "-mard"])?;
+        Ok(!output.trim().is_empty())
+    }
+
+    fn remote_url(&self) -> ScmResult<Option<String>> {
+        let output = self.command().args(["paths", "default"]).output()?;
+        // hg exits with 1 when the path isn't configured
+        if !output.status.success() {
+            return Ok(None);
+        }
+
+        let url = String::from_utf8(output.stdout)?.trim().to_string();
+        Ok((!url.is_empty()).then_some(url))
+    }
+
+    fn fetch(&self, remote: Option<&str>) -> ScmResult<()> {
+        self.run(["pull", "-q"].into_iter().chain(remote))?;
+        Ok(())
+    }
+
+    fn push_branch(&self, branch: &str, remote: Option<&str>) -> ScmResult<()> {
+        let mut args = vec!["-b", branch];
+        args.extend(remote);
+        self.push(&args)
+    }
+
+    /// Pushes the latest revision that changed `.hgtags` after the tagged revision, which includes
+    /// the tagged revision and the commit that added the tag.
+    fn push_tag(&self, tag: &str, remote: Option<&str>) -> ScmResult<()> {
+        let revset = format!("max(descendants(tag('{tag}')) and file('path:.hgtags'))");
+        let mut args = vec!["-r", revset.as_str()];
+        args.extend(remote);
+        self.push(&args)
+    }
+
+    /// Mercurial doesn't track remote branches so this pulls from the remote and lists branches
+    fn remote_branches(&self, remote: Option<&str>) -> ScmResult<Vec<String>> {
+        self.fetch(remote)?;
+        let output = self.run(["branches", "-c", "-T", "{branch}\n"])?;
+        Ok(output.lines().map(String::from).collect())
     }
 
     fn supported_hooks(&self) -> Vec<&'static str> {
-        todo!()
+        HOOK_NAMES.to_vec()
     }
 
     fn supports_hook(&self, hook: &str) -> bool {
-        todo!()
+        HOOK_NAMES.contains(&hook)
     }
 
+    /// Mercurial doesn't have a hooks directory. Hooks are written here and need to be registered
+    /// within the `[hooks]` section of `.hg/hgrc`
     fn hooks_path(&self) -> ScmResult<PathBuf> {
-        todo!()
+        Ok(self.root.join(".hg").join("hooks"))
     }
 
-    fn is_hook_file_sample(&self, path: &Path) -> bool {
-        todo!()
+    fn is_hook_file_sample(&self, _path: &Path) -> bool {
+        false
     }
 
     fn info_path(&self) -> ScmResult<PathBuf> {
-        todo!()
+        Ok(self.root.join(".hg"))
     }
 
     fn all_files(&self) -> ScmResult<Vec<PathBuf>> {
-        todo!()
+        self.get_files(["files"])
     }
 
+    /// Mercurial doesn't have a staging area so this returns added and modified files
     fn staged_files(&self) -> ScmResult<Vec<PathBuf>> {
-        todo!()
+        self.get_files(["status", "-amn"])
     }
 
+    /// Files changed by draft commits which are commits that haven't been pushed
     fn push_files(&self) -> ScmResult<Vec<PathBuf>> {
-        todo!()
+        let output = self.run([
+            "log",
+            "-r",
+            "draft() and ::.",
+            "-T",
+            "{join(files, '\\n')}\\n",
+        ])?;
+
+        let files: IndexSet<PathBuf> = output
+            .lines()
+            .filter(|l| !l.is_empty())
+            .map(PathBuf::from)
+            .collect();
+
+        Ok(files.into_iter().collect())
     }
 
     fn files_by_command(&self, cmd: &String) -> ScmResult<Vec<PathBuf>> {
-        todo!()
+        self.get_files(cmd.split_whitespace())
     }
 
     fn scm(&self) -> &'static str {
-        todo!()
+        HG
     }
 
     fn diff_paths(&self, range: Option<&ScmCommitRange>) -> ScmResult<Vec<PathBuf>> {
-        todo!()
+        let mut args = vec!["status".to_string(), "-n".to_string()];
+
+        if let Some(range) = range {
+            let Some(resolved) = self.resolve_range(range)? else {
+                return Ok(vec![]);
+            };
+
+            args.extend([
+                "--rev".to_string(),
+                resolved.start.unwrap_or(NULL_REVISION.to_string()),
+                "--rev".to_string(),
+                resolved.end,
+            ]);
+        } else {
+            // changes to tracked files in the working directory
+            args.push("-mard".to_string());
+        }
+
+        self.get_files(args)
+    }
+
+    fn resolve_revision(
+        &self,
+        revision: &ScmRevision,
+        base: Option<&str>,
+    ) -> ScmResult<Option<String>> {
+        let base = base.unwrap_or(WORKING_PARENT);
+        let revset = match revision {
+            ScmRevision::Branch(name) => format!("max(branch({}))", revset_symbol(name)),
+            // hg's `<` date specifier includes the given second
+            ScmRevision::Date(date) => format!(
+                "max(::{} and date('<{} 0'))",
+                revset_symbol(base),
+                ScmRevision::date_timestamp(date) - 1
+            ),
+            ScmRevision::Head => WORKING_PARENT.to_string(),
+            ScmRevision::MergeBase(branch) => format!(
+                "ancestor({}, {})",
+                revset_symbol(branch),
+                revset_symbol(base)
+            ),
+            ScmRevision::Ref(revision) => revset_symbol(revision),
+            ScmRevision::Tag(name) => format!("tag({})", revset_symbol(name)),
+        };
+
+        let node = self.node(&revset)?;
+        if node.is_none() && !matches!(revision, ScmRevision::Date(_)) {
+            return Err(ScmError::CommandFailed(
+                format!("hg log -r {revset}"),
+                format!("unknown revision `{revision}`"),
+            ));
+        }
+
+        Ok(node)
+    }
+
+    fn parent(&self, id: &str) -> ScmResult<Option<String>> {
+        self.node(&format!("p1({})", revset_symbol(id)))
+    }
+}
+
+fn records(output: &str) -> impl Iterator<Item = &str> {
+    output
+        .split(RECORD_SEPARATOR)
+        .filter(|r| !r.trim().is_empty())
+}
+
+fn parse_commit(record: &str) -> Option<ScmCommit> {
+    let mut fields = record.splitn(5, FIELD_SEPARATOR);
+    let id = fields.next()?;
+    let name = fields.next()?;
+    let email = fields.next()?;
+    let timestamp = parse_hgdate(fields.next()?);
+    let message = fields.next()?;
+
+    let (description, body) = message.split_once('\n').unwrap_or((message, ""));
+    let non_empty = |s: &str| (!s.is_empty()).then(|| s.to_string());
+    let signature = ScmSignature {
+        name: non_empty(name),
+        email: non_empty(email),
+        timestamp,
+    };
+
+    Some(ScmCommit {
+        id: id.to_string(),
+        message: message.to_string(),
+        description: description.to_string(),
+        body: body.trim_start_matches('\n').to_string(),
+        author: signature.clone(),
+        // hg doesn't distinguish between author and committer
+        committer: signature,
+        timestamp,
+    })
+}
+
+/// Parses a line of `hg annotate` output into the revision and author of the line
+fn parse_annotation(record: &str) -> Option<(String, ScmSignature)> {
+    let mut fields = record.splitn(4, FIELD_SEPARATOR);
+    let id = fields.next()?;
+    let name = fields.next()?;
+    let email = fields.next()?;
+    let timestamp = parse_hgdate(fields.next()?);
+
+    let non_empty = |s: &str| (!s.is_empty()).then(|| s.to_string());
+    Some((
+        id.to_string(),
+        ScmSignature {
+            name: non_empty(name),
+            email: non_empty(email),
+            timestamp,
+        },
+    ))
+}
+
+/// Parses the `hgdate` format which is the unix timestamp followed by the timezone offset
+fn parse_hgdate(date: &str) -> i64 {
+    date.split_whitespace()
+        .next()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or_default()
+}
+
+fn revset_symbol(revision: &str) -> String {
+    format!("'{}'", revision.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+fn file_pattern(pattern: &Pattern) -> String {
+    // patterns are relative to the repository root as hg is run from there
+    format!("glob:{}", pattern.as_str().trim_end_matches('/'))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    use git2::Signature;
+    use glob::Pattern;
+    use regex::Regex;
+    use tempfile::TempDir;
+
+    use crate::HG;
+    use crate::blame::ScmBlame;
+    use crate::commit::{ScmCommitRange, ScmRevision};
+    use crate::drivers::git::TagSort;
+    use crate::drivers::hg::{HgScmRepository, parse_annotation, parse_commit, records};
+    use crate::drivers::{Scm, ScmRepository};
+
+    // requires Mercurial, run with `cargo test -- --ignored` where it's installed
+    fn hg_repo() -> (TempDir, HgScmRepository) {
+        let dir = TempDir::new().unwrap();
+        let repo = HgScmRepository::init(dir.path()).unwrap();
+        fs::write(
+            dir.path().join(".hg").join("hgrc"),
+            "[ui]\nusername = Jane <jane@example.com>\n",
+        )
+        .unwrap();
+
+        (dir, repo)
+    }
+
+    fn commit_file(dir: &TempDir, repo: &HgScmRepository, path: &str, message: &str) {
+        let path = dir.path().join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, message).unwrap();
+        repo.run(["commit", "--addremove", "-m", message]).unwrap();
+    }
+
+    #[test]
+    #[ignore = "requires hg"]
+    fn commits() {
+        let (dir, repo) = hg_repo();
+
+        assert!(repo.last_commit().unwrap().is_none());
+
+        commit_file(&dir, &repo, "README.md", "docs: add readme");
+        commit_file(&dir, &repo, "lib/a.rs", "feat: add lib\n\nwith body");
+        commit_file(&dir, &repo, "bin/main.rs", "feat: add bin");
+
+        let commits = repo.commits(None, None, None, None).unwrap();
+        assert_eq!(
+            vec!["feat: add bin", "feat: add lib", "docs: add readme"],
+            commits
+                .iter()
+                .map(|c| c.description.as_str())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!("with body", commits[1].body);
+        assert_eq!(Some("Jane".to_string()), commits[1].author.name);
+        assert_eq!(
+            Some("jane@example.com".to_string()),
+            commits[1].author.email
+        );
+
+        let include = vec![Pattern::new("lib/").unwrap()];
+        let commits = repo.commits(None, Some(&include), None, None).unwrap();
+        assert_eq!(1, commits.len());
+        assert_eq!("feat: add lib", commits[0].description);
+
+        let exclude = vec![Pattern::new("lib/**").unwrap()];
+        let commits = repo.commits(None, None, Some(&exclude), Some(1)).unwrap();
+        assert_eq!(1, commits.len());
+        assert_eq!("feat: add bin", commits[0].description);
+
+        let first = repo.commits(None, None, None, None).unwrap().pop().unwrap();
+        let range = ScmCommitRange::new(ScmRevision::Ref(first.id.clone()), None);
+        assert_eq!(
+            2,
+            repo.commits(Some(&range), None, None, None).unwrap().len()
+        );
+        assert_eq!(
+            "feat: add bin",
+            repo.last_commit().unwrap().unwrap().description
+        );
+
+        let mut paths = repo.diff_paths(Some(&range)).unwrap();
+        paths.sort();
+        assert_eq!(
+            vec!["bin/main.rs", "lib/a.rs"],
+            paths
+                .iter()
+                .map(|p| p.to_string_lossy())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    #[ignore = "requires hg"]
+    fn tags() {
+        let (dir, repo) = hg_repo();
+
+        commit_file(&dir, &repo, "a.txt", "feat: one");
+        repo.run(["tag", "v1.2.0"]).unwrap();
+        commit_file(&dir, &repo, "b.txt", "feat: two");
+        repo.run(["tag", "v1.10.0"]).unwrap();
+        repo.run(["tag", "-r", "1", "other"]).unwrap();
+
+        let includes = vec![Regex::new("^v").unwrap()];
+        let tags = repo
+            .tags(Some(&includes), None, TagSort::Version, None)
+            .unwrap();
+        assert_eq!(
+            vec!["v1.2.0", "v1.10.0"],
+            tags.values().map(|t| t.name.as_str()).collect::<Vec<_>>()
+        );
+
+        let all = repo.tags(None, None, TagSort::Alphabetical, None).unwrap();
+        assert_eq!(3, all.len());
+        assert!(all.values().all(|t| t.name != "tip"));
+
+        assert_eq!("v1.10.0", repo.latest_tag().unwrap().unwrap().name);
+        assert_eq!("v1.10.0", repo.current_tag().unwrap().name);
+        assert!(repo.get_tag("v1.2.0").unwrap().is_some());
+        assert!(repo.get_tag("missing").unwrap().is_none());
+
+        let tag = repo
+            .create_tag("v2.0.0", Some("1"), Some("Release v2.0.0"), None)
+            .unwrap();
+        assert_eq!(repo.node("1").unwrap(), tag.id);
+    }
+
+    #[test]
+    #[ignore = "requires hg"]
+    fn blame_and_file_history() {
+        let (dir, repo) = hg_repo();
+
+        commit_file(&dir, &repo, "a.txt", "feat: one\n");
+        commit_file(&dir, &repo, "b.txt", "feat: two");
+        fs::write(dir.path().join("a.txt"), "feat: one\nsecond line\n").unwrap();
+        repo.run([
+            "commit",
+            "-u",
+            "jane <jane@doctavious.com>",
+            "-m",
+            "docs: expand a",
+        ])
+        .unwrap();
+
+        let blame = repo.blame(Path::new("a.txt")).unwrap();
+        assert_eq!(2, blame.hunks.len());
+        assert_eq!(repo.node("0").unwrap().unwrap(), blame.hunks[0].commit_id);
+        assert_eq!(
+            Some("jane@doctavious.com".to_string()),
+            blame.hunks[1].author.email
+        );
+
+        let history = repo.file_history(Path::new("a.txt"), None).unwrap();
+        assert_eq!(
+            // hg strips trailing whitespace from messages
+            vec!["docs: expand a", "feat: one"],
+            history
+                .iter()
+                .map(|c| c.message.as_str())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            repo.node("1").unwrap(),
+            repo.first_commit_for(Path::new("b.txt"))
+                .unwrap()
+                .map(|c| c.id)
+        );
+        assert!(
+            repo.first_commit_for(Path::new("missing.txt"))
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    #[ignore = "requires hg"]
+    fn working_directory_changes() {
+        let (dir, repo) = hg_repo();
+
+        let scm = Scm::get(dir.path()).unwrap();
+        assert_eq!(HG, scm.scm());
+
+        commit_file(&dir, &repo, "a.txt", "feat: one");
+        assert!(!repo.is_dirty().unwrap());
+        assert!(repo.remote_url().unwrap().is_none());
+
+        fs::write(dir.path().join("a.txt"), "changed").unwrap();
+        fs::write(dir.path().join("b.txt"), "new").unwrap();
+        assert!(repo.is_dirty().unwrap());
+        assert_eq!(
+            vec!["a.txt"],
+            repo.staged_files()
+                .unwrap()
+                .iter()
+                .map(|p| p.to_string_lossy())
+                .collect::<Vec<_>>()
+        );
+
+        repo.run(["add", "b.txt"]).unwrap();
+        let signature = Signature::now("John", "john@example.com").unwrap();
+        repo.commit("feat: two", Some(&signature)).unwrap();
+        let last = repo.last_commit().unwrap().unwrap();
+        assert_eq!("feat: two", last.description);
+        assert_eq!(Some("John".to_string()), last.author.name);
+        assert!(!repo.is_dirty().unwrap());
+
+        assert_eq!(2, repo.all_files().unwrap().len());
+        assert_eq!(
+            vec!["a.txt", "b.txt"],
+            repo.push_files()
+                .unwrap()
+                .iter()
+                .map(|p| p.to_string_lossy())
+                .collect::<Vec<_>>()
+        );
+
+        let first = repo.commits(None, None, None, None).unwrap().pop().unwrap();
+        repo.checkout(&first.id).unwrap();
+        assert_eq!(first.id, repo.last_commit().unwrap().unwrap().id);
+        assert!(!repo.branch_exists("^missing$").unwrap());
+        assert!(repo.branch_exists("default").unwrap());
+    }
+
+    #[test]
+    fn should_parse_annotations() {
+        let output = "abc\x1fJane\x1fjane@example.com\x1f1700000000 -3600\x1e\
+                      abc\x1fJane\x1fjane@example.com\x1f1700000000 -3600\x1e\
+                      def\x1fjohn\x1f\x1f1600000000 0\x1e";
+
+        let blame = ScmBlame::from_lines(
+            PathBuf::from("a.txt"),
+            records(output).filter_map(parse_annotation),
+        );
+        assert_eq!(2, blame.hunks.len());
+        assert_eq!("abc", blame.hunks[0].commit_id);
+        assert_eq!(2, blame.hunks[0].lines);
+        assert_eq!(1700000000, blame.hunks[0].author.timestamp);
+        assert_eq!(3, blame.hunks[1].start_line);
+        assert_eq!(None, blame.hunks[1].author.email);
+    }
+
+    #[test]
+    fn should_parse_log_records() {
+        let output = "abc\x1fJane\x1fjane@example.com\x1f1700000000 -3600\x1ffeat: one\n\nbody\x1e\
+                      def\x1fjane\x1f\x1f1600000000 0\x1ffix: two\x1e";
+
+        let commits: Vec<_> = records(output).filter_map(parse_commit).collect();
+        assert_eq!(2, commits.len());
+        assert_eq!("feat: one", commits[0].description);
+        assert_eq!("body", commits[0].body);
+        assert_eq!(1700000000, commits[0].timestamp);
+        assert_eq!(
+            Some("jane@example.com".to_string()),
+            commits[0].author.email
+        );
+        assert_eq!(None, commits[1].author.email);
+        assert_eq!("", commits[1].body);
     }
 }
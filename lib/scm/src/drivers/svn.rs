@@ -1,15 +1,22 @@
+use std::ffi::OsStr;
 use std::path::{Path, PathBuf};
+use std::process::Command;
 
+use chrono::DateTime;
 use git2::Signature;
 use glob::Pattern;
 use indexmap::IndexMap;
 use regex::Regex;
+use serde_derive::Deserialize;
 
-use crate::commit::{ScmCommit, ScmCommitRange, ScmTag};
-use crate::drivers::ScmRepository;
+use crate::SVN;
+use crate::blame::ScmBlame;
+use crate::commit::{ScmCommit, ScmCommitRange, ScmRevision, ScmSignature, ScmTag};
 use crate::drivers::git::TagSort;
-use crate::errors::ScmResult;
+use crate::drivers::{PathFilter, ScmRepository, check_output, version_cmp};
+use crate::errors::{ScmError, ScmResult};
 
+// SVN hooks are executed by the repository rather than the working copy
 const HOOK_NAMES: [&str; 9] = [
     "start-commit",
     "pre-commit",
@@ -22,29 +29,336 @@ const HOOK_NAMES: [&str; 9] = [
     "post-unlock",
 ];
 
-pub struct SvnScmRepository;
+/// Default location of tags relative to the repository root following the standard
+/// trunk/branches/tags layout
+pub const DEFAULT_TAGS_PATH: &str = "tags";
+const BRANCHES_PATH: &str = "branches";
+
+/// Revision property recording the name and email of the signature a commit or tag was made with
+const AUTHOR_REVPROP: &str = "doctavious:author";
+
+// svn's equivalent of git's HEAD for a working copy
+const BASE: &str = "BASE";
+
+#[derive(Debug, Default, Deserialize)]
+struct SvnLog {
+    #[serde(rename = "logentry", default)]
+    entries: Vec<SvnLogEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SvnLogEntry {
+    #[serde(rename = "@revision")]
+    revision: u64,
+    author: Option<String>,
+    date: Option<String>,
+    paths: Option<SvnLogPaths>,
+    msg: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SvnLogPaths {
+    #[serde(rename = "path", default)]
+    paths: Vec<SvnLogPath>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SvnLogPath {
+    #[serde(rename = "@action")]
+    action: String,
+    #[serde(rename = "@copyfrom-rev")]
+    copyfrom_rev: Option<u64>,
+    #[serde(rename = "#text")]
+    path: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct SvnBlame {
+    target: Option<SvnBlameTarget>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct SvnBlameTarget {
+    #[serde(rename = "entry", default)]
+    entries: Vec<SvnBlameEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SvnBlameEntry {
+    /// Missing for lines with local modifications
+    commit: Option<SvnBlameCommit>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SvnBlameCommit {
+    #[serde(rename = "@revision")]
+    revision: u64,
+    author: Option<String>,
+    date: Option<String>,
+}
+
+impl SvnLogEntry {
+    fn timestamp(&self) -> i64 {
+        self.date
+            .as_deref()
+            .and_then(|d| DateTime::parse_from_rfc3339(d).ok())
+            .map(|d| d.timestamp())
+            .unwrap_or_default()
+    }
+
+    fn changed_paths(&self) -> impl Iterator<Item = &SvnLogPath> {
+        self.paths.iter().flat_map(|p| p.paths.iter())
+    }
+}
+
+impl From<SvnLogEntry> for ScmCommit {
+    fn from(entry: SvnLogEntry) -> Self {
+        let timestamp = entry.timestamp();
+        let message = entry.msg.unwrap_or_default();
+        let (description, body) = message.split_once('\n').unwrap_or((&message, ""));
+        // svn only records the username of the committer
+        let signature = ScmSignature {
+            name: entry.author,
+            email: None,
+            timestamp,
+        };
+
+        ScmCommit {
+            id: entry.revision.to_string(),
+            description: description.to_string(),
+            body: body.trim_start_matches('\n').to_string(),
+            message,
+            author: signature.clone(),
+            committer: signature,
+            timestamp,
+        }
+    }
+}
+
+/// Tag along with the revision of the working copy path it was copied from
+#[derive(Debug)]
+struct SvnTag {
+    tag: ScmTag,
+    revision: u64,
+}
+
+pub struct SvnScmRepository {
+    /// Root of the working copy
+    root: PathBuf,
+
+    /// Location of tags relative to the repository root
+    tags_path: String,
+}
+
+impl SvnScmRepository {
+    /// Finds the root of the working copy containing `path`
+    pub fn discover<P: AsRef<Path>>(path: P) -> ScmResult<Self> {
+        let mut command = Command::new(SVN);
+        command
+            .current_dir(path)
+            .args(["info", "--show-item", "wc-root"]);
+        let output = command.output()?;
+        let root = String::from_utf8(check_output(&command, output)?)?;
+
+        Ok(Self {
+            root: PathBuf::from(root.trim_end()),
+            tags_path: DEFAULT_TAGS_PATH.to_string(),
+        })
+    }
+
+    pub fn new<P: AsRef<Path>>(path: P) -> ScmResult<Self> {
+        let root = path.as_ref();
+        if !root.join(".svn").is_dir() {
+            return Err(ScmError::Unsupported);
+        }
+
+        Ok(Self {
+            root: root.to_path_buf(),
+            tags_path: DEFAULT_TAGS_PATH.to_string(),
+        })
+    }
+
+    /// Sets the location of tags relative to the repository root e.g. `project/tags`
+    pub fn with_tags_path(mut self, tags_path: &str) -> Self {
+        self.tags_path = tags_path.trim_matches('/').to_string();
+        self
+    }
+
+    fn command(&self) -> Command {
+        let mut command = Command::new(SVN);
+        command.current_dir(&self.root).arg("--non-interactive");
+        command
+    }
+
+    /// Runs svn with the given args and returns stdout. Errors when svn exits unsuccessfully
+    fn run<I, S>(&self, args: I) -> ScmResult<String>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        let mut command = self.command();
+        command.args(args);
+        let output = command.output()?;
+        Ok(String::from_utf8(check_output(&command, output)?)?)
+    }
+
+    /// accepts svn command args and returns its result as a list of filepaths.
+    fn get_files<I, S>(&self, args: I) -> ScmResult<Vec<PathBuf>>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        let output = self.command().args(args).output()?.stdout;
+        let files: Vec<_> = output
+            .split(|&b| b == b'\n')
+            .filter(|&x| !x.is_empty())
+            .filter_map(|line| std::str::from_utf8(line).ok())
+            .map(|s| PathBuf::from(s.trim_end()))
+            .collect();
+
+        Ok(files)
+    }
+
+    fn info(&self, item: &str) -> ScmResult<String> {
+        Ok(self
+            .run(["info", "--show-item", item])?
+            .trim_end()
+            .to_string())
+    }
+
+    fn log<I, S>(&self, args: I) -> ScmResult<SvnLog>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        let mut command = self.command();
+        command.args(["log", "--xml"]).args(args);
+        let output = command.output()?;
+        let xml = String::from_utf8(check_output(&command, output)?)?;
+        parse_log(&xml)
+    }
+
+    /// Path of the working copy relative to the repository root e.g. `/trunk`
+    fn relative_url(&self) -> ScmResult<String> {
+        let url = self.info("relative-url")?;
+        Ok(url
+            .trim_start_matches('^')
+            .trim_end_matches('/')
+            .to_string())
+    }
+
+    /// Tags in the order they were created.
+    ///
+    /// The revision of a tag is the last revision of the working copy path at or before the
+    /// revision the tag was copied from, which matches the IDs returned by `commits`.
+    fn svn_tags(&self) -> ScmResult<Vec<SvnTag>> {
+        let tags_url = format!("{}/{}", self.info("repos-root-url")?, self.tags_path);
+        // svn errors when logging a path that doesn't exist
+        if self
+            .command()
+            .args(["info", &tags_url])
+            .output()?
+            .status
+            .success()
+        {
+            let log = self.log(["-v", "-r", "1:HEAD", &tags_url])?;
+            let revisions = self.log(["-q", "-r", &format!("{BASE}:1")])?;
+            let revisions: Vec<u64> = revisions.entries.iter().map(|e| e.revision).collect();
+            Ok(parse_tags(log, &self.tags_path, &revisions))
+        } else {
+            Ok(vec![])
+        }
+    }
+
+    /// Newest revision of the working copy path at or before the revision
+    fn revision_at_or_before(&self, revision: &str) -> ScmResult<Option<String>> {
+        let log = self.log(["-q", "-l", "1", "-r", &format!("{revision}:1")])?;
+        Ok(log.entries.first().map(|e| e.revision.to_string()))
+    }
+
+    /// Updates the working copy so that `BASE` includes the latest commit
+    fn update(&self) -> ScmResult<()> {
+        self.run(["update", "-q"])?;
+        Ok(())
+    }
+
+    fn commit_args(message: &str, signature: Option<&Signature>) -> Vec<String> {
+        let mut args = vec!["commit".to_string(), "-m".to_string(), message.to_string()];
+        args.extend(signature_revprop_args(signature));
+        args
+    }
+
+    /// Parses the output of `svn status` and `svn diff --summarize` which prefix paths with
+    /// status columns
+    fn status_paths<I, S>(&self, args: I, statuses: &[char]) -> ScmResult<Vec<PathBuf>>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        Ok(self
+            .run(args)?
+            .lines()
+            .filter(|line| line.chars().next().is_some_and(|c| statuses.contains(&c)))
+            .filter_map(|line| line.get(8..))
+            .map(|path| PathBuf::from(path.trim()))
+            .collect())
+    }
+}
 
 impl ScmRepository for SvnScmRepository {
+    /// Updates the working copy to a revision or switches to a path such as `branches/feature`
     fn checkout(&self, reference: &str) -> ScmResult<()> {
-        todo!()
+        if reference == "HEAD" || reference.parse::<u64>().is_ok() {
+            self.run(["update", "-q", "-r", reference])?;
+        } else {
+            self.run([
+                "switch",
+                "-q",
+                &format!("^/{}", reference.trim_start_matches('/')),
+            ])?;
+        }
+
+        Ok(())
     }
 
     fn branch_exists(&self, branch_name: &str) -> ScmResult<bool> {
-        todo!()
+        let re = Regex::new(branch_name)?;
+        Ok(self
+            .remote_branches(None)?
+            .iter()
+            .any(|name| re.is_match(name)))
     }
 
     fn write(&self, path: &Path, message: &str, signature: Option<&Signature>) -> ScmResult<()> {
-        todo!()
+        self.run([
+            OsStr::new("add"),
+            OsStr::new("-q"),
+            OsStr::new("--force"),
+            path.as_os_str(),
+        ])?;
+
+        // svn commits directly to the repository so there is no need to push
+        let mut args: Vec<&OsStr> = vec![];
+        let commit_args = Self::commit_args(message, signature);
+        args.extend(commit_args.iter().map(OsStr::new));
+        args.push(path.as_os_str());
+        self.run(args)?;
+        self.update()
     }
 
     fn commit(&self, message: &str, signature: Option<&Signature>) -> ScmResult<()> {
-        todo!()
+        self.run(Self::commit_args(message, signature))?;
+        self.update()
     }
 
     fn last_commit(&self) -> ScmResult<Option<ScmCommit>> {
-        todo!()
+        let log = self.log(["-l", "1", "-r", &format!("{BASE}:1")])?;
+        Ok(log.entries.into_iter().next().map(ScmCommit::from))
     }
 
+    /// Parses and returns the commits.
+    ///
+    /// Commits are sorted newest to oldest and IDs are revision numbers
     fn commits(
         &self,
         range: Option<&ScmCommitRange>,
@@ -52,76 +366,789 @@ impl ScmRepository for SvnScmRepository {
         exclude_paths: Option<&Vec<Pattern>>,
         limit_commits: Option<usize>,
     ) -> ScmResult<Vec<ScmCommit>> {
-        todo!()
+        let (start, end) = match range {
+            Some(range) => match self.resolve_range(range)? {
+                // mirror git's `start..end` which excludes start
+                Some(resolved) => (
+                    resolved
+                        .start
+                        .and_then(|s| s.parse::<u64>().ok())
+                        .map_or(1, |r| r + 1),
+                    resolved.end,
+                ),
+                None => return Ok(vec![]),
+            },
+            None => (1, BASE.to_string()),
+        };
+
+        let path_filter = PathFilter::new(include_paths, exclude_paths);
+        let filter_paths = !path_filter.is_empty();
+        let mut args = vec!["-r".to_string(), format!("{end}:{start}")];
+        if filter_paths {
+            args.push("-v".to_string());
+        } else if let Some(limit) = limit_commits {
+            args.extend(["-l".to_string(), limit.to_string()]);
+        }
+
+        let log = self.log(args)?;
+        let prefix = if filter_paths {
+            format!("{}/", self.relative_url()?)
+        } else {
+            String::new()
+        };
+
+        let commits = log
+            .entries
+            .into_iter()
+            .filter(|entry| {
+                !filter_paths
+                    || entry
+                        .changed_paths()
+                        .filter_map(|p| p.path.strip_prefix(&prefix))
+                        .any(|path| path_filter.matches(path))
+            })
+            .take(limit_commits.unwrap_or(usize::MAX))
+            .map(ScmCommit::from)
+            .collect();
+
+        Ok(commits)
+    }
+
+    /// Blames the file as of the working copy revision
+    fn blame(&self, path: &Path) -> ScmResult<ScmBlame> {
+        let mut command = self.command();
+        command
+            .args(["blame", "--xml", "-r", BASE])
+            .arg(path.as_os_str());
+        let output = command.output()?;
+        let xml = String::from_utf8(check_output(&command, output)?)?;
+        parse_blame(path, &xml)
     }
 
+    /// Parses and returns a commit-tag map.
+    ///
+    /// Tags are directories copied into the tags path and their message is the message of the
+    /// copy. `suffix_order` is not supported.
     fn tags(
         &self,
         includes: Option<&Vec<Regex>>,
         excludes: Option<&Vec<Regex>>,
         sort: TagSort,
-        suffix_order: Option<&Vec<String>>,
+        _suffix_order: Option<&Vec<String>>,
     ) -> ScmResult<IndexMap<String, ScmTag>> {
-        todo!()
+        let mut tags: Vec<SvnTag> = self
+            .svn_tags()?
+            .into_iter()
+            .filter(|t| includes.is_none_or(|i| i.iter().any(|r| r.is_match(&t.tag.name))))
+            .filter(|t| excludes.is_none_or(|e| !e.iter().any(|r| r.is_match(&t.tag.name))))
+            .collect();
+
+        match sort {
+            TagSort::Alphabetical => tags.sort_by(|a, b| a.tag.name.cmp(&b.tag.name)),
+            TagSort::Chronological => {}
+            TagSort::Version => tags.sort_by(|a, b| version_cmp(&a.tag.name, &b.tag.name)),
+        }
+
+        Ok(tags
+            .into_iter()
+            .map(|t| (t.revision.to_string(), t.tag))
+            .collect())
     }
 
+    /// Returns the closest tag copied from a revision at or before the working copy revision
     fn current_tag(&self) -> Option<ScmTag> {
-        todo!()
+        let revision: u64 = self.last_commit().ok()??.id.parse().ok()?;
+        self.svn_tags()
+            .ok()?
+            .into_iter()
+            .filter(|t| t.revision <= revision)
+            .max_by_key(|t| t.revision)
+            .map(|t| t.tag)
     }
 
     fn latest_tag(&self) -> ScmResult<Option<ScmTag>> {
-        todo!()
+        Ok(self.svn_tags()?.pop().map(|t| t.tag))
     }
 
-    fn get_tag(&self, name: &str) -> ScmTag {
-        todo!()
+    fn get_tag(&self, name: &str) -> ScmResult<Option<ScmTag>> {
+        Ok(self
+            .svn_tags()?
+            .into_iter()
+            .find(|t| t.tag.name == name)
+            .map(|t| t.tag))
     }
 
+    /// Copies the working copy path at the revision, or the working copy revision when `None`,
+    /// into the tags path. Subversion doesn't support signing tags.
+    fn create_tag(
+        &self,
+        name: &str,
+        revision: Option<&str>,
+        message: Option<&str>,
+        signature: Option<&Signature>,
+    ) -> ScmResult<ScmTag> {
+        let revision = match revision {
+            Some(revision) => revision.to_string(),
+            None => self.info("last-changed-revision")?,
+        };
+
+        let mut args = vec![
+            "copy".to_string(),
+            "-q".to_string(),
+            "-m".to_string(),
+            message.map_or_else(|| format!("Tag {name}"), str::to_string),
+        ];
+        args.extend(signature_revprop_args(signature));
+        args.extend([
+            format!("^{}@{revision}", self.relative_url()?),
+            format!("^/{}/{name}", self.tags_path),
+        ]);
+        self.run(args)?;
+
+        self.get_tag(name)?
+            .ok_or_else(|| ScmError::TagNotFound(name.to_string()))
+    }
+
+    /// Determines if there are any changes to versioned files in the working copy
     fn is_dirty(&self) -> ScmResult<bool> {
-        todo!()
+        Ok(!self.run(["status", "-q"])?.trim().is_empty())
+    }
+
+    fn remote_url(&self) -> ScmResult<Option<String>> {
+        let url = self.info("repos-root-url")?;
+        Ok((!url.is_empty()).then_some(url))
+    }
+
+    /// Updates the working copy. SVN only has a single remote, the repository
+    fn fetch(&self, _remote: Option<&str>) -> ScmResult<()> {
+        self.update()
+    }
+
+    /// Commits are made directly to the repository so there is nothing to push
+    fn push_branch(&self, _branch: &str, _remote: Option<&str>) -> ScmResult<()> {
+        Ok(())
+    }
+
+    /// Tags are created by copying to the repository so there is nothing to push
+    fn push_tag(&self, _tag: &str, _remote: Option<&str>) -> ScmResult<()> {
+        Ok(())
     }
 
+    fn remote_branches(&self, _remote: Option<&str>) -> ScmResult<Vec<String>> {
+        let output = self
+            .command()
+            .args(["list", &format!("^/{BRANCHES_PATH}")])
+            .output()?;
+        // repositories without a branches directory don't have any branches
+        if !output.status.success() {
+            return Ok(vec![]);
+        }
+
+        Ok(String::from_utf8(output.stdout)?
+            .lines()
+            .map(|name| name.trim_end_matches('/').to_string())
+            .collect())
+    }
+
+    /// Repository hooks. SVN doesn't support client side hooks
     fn supported_hooks(&self) -> Vec<&'static str> {
-        todo!()
+        HOOK_NAMES.to_vec()
     }
 
     fn supports_hook(&self, hook: &str) -> bool {
-        todo!()
+        HOOK_NAMES.contains(&hook)
     }
 
+    /// Hooks directory of the repository.
+    /// Only available when the repository is local i.e. accessed via a `file://` URL
     fn hooks_path(&self) -> ScmResult<PathBuf> {
-        todo!()
+        let url = self.info("repos-root-url")?;
+        match url.strip_prefix("file://") {
+            Some(path) => Ok(PathBuf::from(path).join("hooks")),
+            None => Err(ScmError::UnsupportedHooksLocation(url)),
+        }
     }
 
+    /// `svnadmin create` adds templates for each hook
     fn is_hook_file_sample(&self, path: &Path) -> bool {
-        path.ends_with(".tmpl")
+        path.extension().is_some_and(|e| e == "tmpl")
     }
 
     fn info_path(&self) -> ScmResult<PathBuf> {
-        todo!()
+        Ok(self.root.join(".svn"))
     }
 
     fn all_files(&self) -> ScmResult<Vec<PathBuf>> {
-        todo!()
+        Ok(self
+            .get_files(["list", "-R"])?
+            .into_iter()
+            // directories are listed with a trailing slash
+            .filter(|p| !p.to_string_lossy().ends_with('/'))
+            .collect())
     }
 
+    /// SVN doesn't have a staging area so this returns added, modified and replaced files
     fn staged_files(&self) -> ScmResult<Vec<PathBuf>> {
-        todo!()
+        self.status_paths(["status", "-q"], &['A', 'M', 'R'])
     }
 
+    /// Always empty as SVN commits are sent to the repository immediately
     fn push_files(&self) -> ScmResult<Vec<PathBuf>> {
-        todo!()
+        Ok(vec![])
     }
 
     fn files_by_command(&self, cmd: &String) -> ScmResult<Vec<PathBuf>> {
-        todo!()
+        self.get_files(cmd.split_whitespace())
     }
 
     fn scm(&self) -> &'static str {
-        todo!()
+        SVN
     }
 
     fn diff_paths(&self, range: Option<&ScmCommitRange>) -> ScmResult<Vec<PathBuf>> {
-        todo!()
+        let mut args = vec!["diff".to_string(), "--summarize".to_string()];
+        if let Some(range) = range {
+            let Some(resolved) = self.resolve_range(range)? else {
+                return Ok(vec![]);
+            };
+
+            args.extend([
+                "-r".to_string(),
+                format!(
+                    "{}:{}",
+                    resolved.start.as_deref().unwrap_or("0"),
+                    resolved.end
+                ),
+            ]);
+        }
+
+        self.status_paths(args, &['A', 'C', 'D', 'M', 'R'])
+    }
+
+    fn resolve_revision(
+        &self,
+        revision: &ScmRevision,
+        base: Option<&str>,
+    ) -> ScmResult<Option<String>> {
+        let revision = match revision {
+            ScmRevision::Branch(name) => {
+                let log = self.log(["-q", "-l", "1", &format!("^/{BRANCHES_PATH}/{name}")])?;
+                log.entries.first().map(|e| e.revision.to_string())
+            }
+            ScmRevision::Date(date) => {
+                // revisions before the working copy path existed can't be part of a range
+                return self.revision_at_or_before(&format!("{{{}T00:00:00Z}}", date));
+            }
+            ScmRevision::Head => self.revision_at_or_before(base.unwrap_or(BASE))?,
+            ScmRevision::MergeBase(_) => {
+                return Err(ScmError::UnsupportedRevision(
+                    revision.to_string(),
+                    SVN.to_string(),
+                ));
+            }
+            ScmRevision::Ref(id) => self.revision_at_or_before(id)?,
+            ScmRevision::Tag(name) => self
+                .svn_tags()?
+                .into_iter()
+                .find(|t| &t.tag.name == name)
+                .map(|t| t.revision.to_string()),
+        };
+
+        match revision {
+            Some(revision) => Ok(Some(revision)),
+            None => Err(ScmError::CommandFailed(
+                "svn log".to_string(),
+                "unknown revision".to_string(),
+            )),
+        }
+    }
+
+    fn parent(&self, id: &str) -> ScmResult<Option<String>> {
+        let log = self.log(["-q", "-l", "2", "-r", &format!("{id}:1")])?;
+        Ok(log.entries.get(1).map(|e| e.revision.to_string()))
+    }
+}
+
+/// svn records the authenticated user as the author and has no separate author override, so the
+/// signature is recorded in the [`AUTHOR_REVPROP`] revision property instead
+fn signature_revprop_args(signature: Option<&Signature>) -> Vec<String> {
+    let Some(name) = signature.and_then(|s| s.name()) else {
+        return vec![];
+    };
+
+    let author = match signature.and_then(|s| s.email()) {
+        Some(email) => format!("{name} <{email}>"),
+        None => name.to_string(),
+    };
+
+    vec![
+        "--with-revprop".to_string(),
+        format!("{AUTHOR_REVPROP}={author}"),
+    ]
+}
+
+fn parse_log(xml: &str) -> ScmResult<SvnLog> {
+    // an empty log is written as `<log>\n</log>` which doesn't deserialize into a struct
+    if !xml.contains("<logentry") {
+        return Ok(SvnLog::default());
+    }
+
+    Ok(serde_xml_rs::from_str(xml)?)
+}
+
+fn parse_blame(path: &Path, xml: &str) -> ScmResult<ScmBlame> {
+    // blame of an empty file doesn't have any entries
+    let blame: SvnBlame = if xml.contains("<entry") {
+        serde_xml_rs::from_str(xml)?
+    } else {
+        SvnBlame::default()
+    };
+
+    let lines = blame
+        .target
+        .into_iter()
+        .flat_map(|t| t.entries)
+        .filter_map(|entry| entry.commit)
+        .map(|commit| {
+            let timestamp = commit
+                .date
+                .as_deref()
+                .and_then(|d| DateTime::parse_from_rfc3339(d).ok())
+                .map(|d| d.timestamp())
+                .unwrap_or_default();
+            (
+                commit.revision.to_string(),
+                // svn only records the username of the committer
+                ScmSignature {
+                    name: commit.author,
+                    email: None,
+                    timestamp,
+                },
+            )
+        });
+
+    Ok(ScmBlame::from_lines(path.to_path_buf(), lines))
+}
+
+/// Builds tags from the log of the tags path which must be ordered oldest to newest.
+/// `revisions` are the revisions of the working copy path, newest to oldest.
+fn parse_tags(log: SvnLog, tags_path: &str, revisions: &[u64]) -> Vec<SvnTag> {
+    let prefix = format!("/{tags_path}/");
+    let mut tags: Vec<SvnTag> = vec![];
+    for entry in &log.entries {
+        for path in entry.changed_paths() {
+            // only direct children of the tags path are tags
+            let Some(name) = path.path.strip_prefix(&prefix).filter(|n| !n.contains('/')) else {
+                continue;
+            };
+
+            tags.retain(|t| t.tag.name != name);
+            if path.action == "D" {
+                continue;
+            }
+
+            let Some(copyfrom_rev) = path.copyfrom_rev else {
+                continue;
+            };
+
+            let Some(revision) = revisions.iter().find(|r| **r <= copyfrom_rev) else {
+                continue;
+            };
+
+            tags.push(SvnTag {
+                tag: ScmTag {
+                    id: Some(revision.to_string()),
+                    name: name.to_string(),
+                    message: entry.msg.as_ref().map(|m| m.trim().to_string()),
+                    timestamp: entry.timestamp(),
+                },
+                revision: *revision,
+            });
+        }
+    }
+
+    tags
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+    use std::process::Command;
+
+    use git2::Signature;
+    use glob::Pattern;
+    use regex::Regex;
+    use tempfile::TempDir;
+
+    use crate::SVN;
+    use crate::commit::{ScmCommit, ScmCommitRange, ScmRevision};
+    use crate::drivers::git::TagSort;
+    use crate::drivers::svn::{SvnScmRepository, parse_blame, parse_log, parse_tags};
+    use crate::drivers::{Scm, ScmRepository};
+
+    const LOG: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<log>
+<logentry revision="5">
+<author>jane</author>
+<date>2024-01-02T00:00:00.000000Z</date>
+<paths>
+<path action="A" kind="dir" copyfrom-path="/trunk" copyfrom-rev="3">/tags/v1.0.0</path>
+</paths>
+<msg>Tag 1.0.0</msg>
+</logentry>
+<logentry revision="3">
+<author>jane</author>
+<date>2024-01-01T00:00:00.000000Z</date>
+<paths>
+<path action="M" kind="file">/trunk/lib/a.rs</path>
+</paths>
+<msg>feat: add lib
+
+with body</msg>
+</logentry>
+<logentry revision="2">
+<date>2024-01-01T00:00:00.000000Z</date>
+<msg></msg>
+</logentry>
+</log>
+"#;
+
+    const BLAME: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<blame>
+<target path="a.txt">
+<entry line-number="1">
+<commit revision="2">
+<author>jane</author>
+<date>2024-01-01T00:00:00.000000Z</date>
+</commit>
+</entry>
+<entry line-number="2">
+<commit revision="2">
+<author>jane</author>
+<date>2024-01-01T00:00:00.000000Z</date>
+</commit>
+</entry>
+<entry line-number="3">
+<commit revision="4">
+<author>john</author>
+<date>2024-01-02T00:00:00.000000Z</date>
+</commit>
+</entry>
+</target>
+</blame>
+"#;
+
+    // requires Subversion, run with `cargo test -- --ignored` where it's installed
+    fn svn_repo() -> (TempDir, SvnScmRepository) {
+        let dir = TempDir::new().unwrap();
+        let repository = dir.path().join("repository");
+        let working_copy = dir.path().join("wc");
+        let url = format!("file://{}", repository.to_string_lossy());
+
+        let run = |program: &str, args: &[&str]| {
+            let status = Command::new(program).args(args).status().unwrap();
+            assert!(status.success(), "{program} {args:?} failed");
+        };
+
+        run("svnadmin", &["create", &repository.to_string_lossy()]);
+        run(
+            SVN,
+            &[
+                "mkdir",
+                "-q",
+                "-m",
+                "create layout",
+                &format!("{url}/trunk"),
+                &format!("{url}/tags"),
+                &format!("{url}/branches"),
+            ],
+        );
+        run(
+            SVN,
+            &[
+                "checkout",
+                "-q",
+                &format!("{url}/trunk"),
+                &working_copy.to_string_lossy(),
+            ],
+        );
+
+        let repo = SvnScmRepository::new(&working_copy).unwrap();
+        (dir, repo)
+    }
+
+    fn commit_file(repo: &SvnScmRepository, path: &str, message: &str) {
+        let full_path = repo.root.join(path);
+        fs::create_dir_all(full_path.parent().unwrap()).unwrap();
+        fs::write(&full_path, message).unwrap();
+        repo.run(["add", "-q", "--force", "--parents", path])
+            .unwrap();
+        repo.commit(message, None).unwrap();
+    }
+
+    fn tag(repo: &SvnScmRepository, name: &str) {
+        repo.run([
+            "copy",
+            "-q",
+            "-m",
+            &format!("Tag {name}"),
+            "^/trunk",
+            &format!("^/tags/{name}"),
+        ])
+        .unwrap();
+    }
+
+    #[test]
+    fn should_record_signature_as_revprop() {
+        let signature = Signature::now("Jane Doe", "jane@example.com").unwrap();
+        assert_eq!(
+            vec![
+                "commit",
+                "-m",
+                "message",
+                "--with-revprop",
+                "doctavious:author=Jane Doe <jane@example.com>"
+            ],
+            SvnScmRepository::commit_args("message", Some(&signature))
+        );
+        assert_eq!(
+            vec!["commit", "-m", "message"],
+            SvnScmRepository::commit_args("message", None)
+        );
+    }
+
+    #[test]
+    fn should_parse_log() {
+        let log = parse_log(LOG).unwrap();
+        let commits: Vec<ScmCommit> = log.entries.into_iter().map(ScmCommit::from).collect();
+
+        assert_eq!(3, commits.len());
+        assert_eq!("3", commits[1].id);
+        assert_eq!("feat: add lib", commits[1].description);
+        assert_eq!("with body", commits[1].body);
+        assert_eq!(Some("jane".to_string()), commits[1].author.name);
+        assert_eq!(1704067200, commits[1].timestamp);
+        assert_eq!("", commits[2].message);
+
+        assert!(
+            parse_log("<?xml version=\"1.0\"?>\n<log>\n</log>\n")
+                .unwrap()
+                .entries
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn should_parse_tags() {
+        let mut log = parse_log(LOG).unwrap();
+        log.entries.reverse();
+
+        // revision 3 didn't touch the working copy so the tag belongs to revision 2
+        let tags = parse_tags(log, "tags", &[4, 2, 1]);
+        assert_eq!(1, tags.len());
+        assert_eq!("v1.0.0", tags[0].tag.name);
+        assert_eq!(Some("2".to_string()), tags[0].tag.id);
+        assert_eq!(Some("Tag 1.0.0".to_string()), tags[0].tag.message);
+    }
+
+    #[test]
+    #[ignore = "requires svnadmin"]
+    fn commits() {
+        let (_dir, repo) = svn_repo();
+
+        commit_file(&repo, "README.md", "docs: add readme");
+        commit_file(&repo, "lib/a.rs", "feat: add lib\n\nwith body");
+        commit_file(&repo, "bin/main.rs", "feat: add bin");
+
+        let commits = repo.commits(None, None, None, None).unwrap();
+        assert_eq!(
+            vec![
+                "feat: add bin",
+                "feat: add lib",
+                "docs: add readme",
+                "create layout"
+            ],
+            commits
+                .iter()
+                .map(|c| c.description.as_str())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!("with body", commits[1].body);
+
+        let include = vec![Pattern::new("lib/").unwrap()];
+        let commits = repo.commits(None, Some(&include), None, None).unwrap();
+        assert_eq!(1, commits.len());
+        assert_eq!("feat: add lib", commits[0].description);
+
+        let exclude = vec![Pattern::new("bin/").unwrap()];
+        let commits = repo.commits(None, None, Some(&exclude), Some(1)).unwrap();
+        assert_eq!("feat: add lib", commits[0].description);
+
+        let range = ScmCommitRange::new(ScmRevision::Ref("2".to_string()), None);
+        assert_eq!(
+            2,
+            repo.commits(Some(&range), None, None, None).unwrap().len()
+        );
+        assert_eq!("4", repo.last_commit().unwrap().unwrap().id);
+
+        let mut paths = repo.diff_paths(Some(&range)).unwrap();
+        paths.sort();
+        assert_eq!(
+            vec!["bin", "bin/main.rs", "lib", "lib/a.rs"],
+            paths
+                .iter()
+                .map(|p| p.to_string_lossy())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    #[ignore = "requires svnadmin"]
+    fn tags() {
+        let (_dir, repo) = svn_repo();
+
+        commit_file(&repo, "a.txt", "feat: one");
+        tag(&repo, "v1.2.0");
+        commit_file(&repo, "b.txt", "feat: two");
+        tag(&repo, "v1.10.0");
+        tag(&repo, "other");
+
+        let includes = vec![Regex::new("^v").unwrap()];
+        let tags = repo
+            .tags(Some(&includes), None, TagSort::Version, None)
+            .unwrap();
+        assert_eq!(
+            vec![("2", "v1.2.0"), ("4", "v1.10.0")],
+            tags.iter()
+                .map(|(id, t)| (id.as_str(), t.name.as_str()))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(Some("Tag v1.2.0".to_string()), tags["2"].message);
+
+        assert_eq!("other", repo.latest_tag().unwrap().unwrap().name);
+        assert!(repo.current_tag().is_some());
+        assert!(repo.get_tag("missing").unwrap().is_none());
+
+        let tag = repo
+            .create_tag("v2.0.0", Some("4"), Some("Release v2.0.0"), None)
+            .unwrap();
+        assert_eq!(Some("4".to_string()), tag.id);
+        assert_eq!(Some("Release v2.0.0".to_string()), tag.message);
+    }
+
+    #[test]
+    fn should_parse_blame() {
+        let blame = parse_blame(Path::new("a.txt"), BLAME).unwrap();
+        assert_eq!(
+            vec![("2", 1..=2), ("4", 3..=3)],
+            blame
+                .hunks
+                .iter()
+                .map(|h| (h.commit_id.as_str(), h.line_range()))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(Some("john".to_string()), blame.hunks[1].author.name);
+        assert_eq!(1704153600, blame.hunks[1].author.timestamp);
+
+        let empty = r#"<?xml version="1.0" encoding="UTF-8"?>
+<blame>
+<target path="empty.txt">
+</target>
+</blame>
+"#;
+        assert!(
+            parse_blame(Path::new("empty.txt"), empty)
+                .unwrap()
+                .hunks
+                .is_empty()
+        );
+    }
+
+    #[test]
+    #[ignore = "requires svnadmin"]
+    fn blame_and_file_history() {
+        let (_dir, repo) = svn_repo();
+
+        commit_file(&repo, "a.txt", "feat: one\n");
+        commit_file(&repo, "b.txt", "feat: two");
+        fs::write(repo.root.join("a.txt"), "feat: one\nsecond line\n").unwrap();
+        let jane = Signature::now("jane", "jane@doctavious.com").unwrap();
+        repo.commit("docs: expand a", Some(&jane)).unwrap();
+
+        let blame = repo.blame(Path::new("a.txt")).unwrap();
+        assert_eq!(
+            vec![("2", 1..=1), ("4", 2..=2)],
+            blame
+                .hunks
+                .iter()
+                .map(|h| (h.commit_id.as_str(), h.line_range()))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(Some("jane".to_string()), blame.hunks[1].author.name);
+
+        let history = repo.file_history(Path::new("a.txt"), None).unwrap();
+        assert_eq!(
+            vec!["4", "2"],
+            history.iter().map(|c| c.id.as_str()).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Some("3".to_string()),
+            repo.first_commit_for(Path::new("b.txt"))
+                .unwrap()
+                .map(|c| c.id)
+        );
+    }
+
+    #[test]
+    #[ignore = "requires svnadmin"]
+    fn working_copy_changes() {
+        let (_dir, repo) = svn_repo();
+
+        let scm = Scm::get(&repo.root).unwrap();
+        assert_eq!(SVN, scm.scm());
+        assert!(repo.remote_url().unwrap().unwrap().starts_with("file://"));
+        assert!(repo.hooks_path().unwrap().ends_with("repository/hooks"));
+
+        commit_file(&repo, "a.txt", "feat: one");
+        assert!(!repo.is_dirty().unwrap());
+
+        fs::write(repo.root.join("a.txt"), "changed").unwrap();
+        fs::write(repo.root.join("b.txt"), "new").unwrap();
+        assert!(repo.is_dirty().unwrap());
+        assert_eq!(
+            vec!["a.txt"],
+            repo.staged_files()
+                .unwrap()
+                .iter()
+                .map(|p| p.to_string_lossy())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec!["a.txt"],
+            repo.diff_paths(None)
+                .unwrap()
+                .iter()
+                .map(|p| p.to_string_lossy())
+                .collect::<Vec<_>>()
+        );
+
+        repo.write(&repo.root.join("b.txt"), "feat: two", None)
+            .unwrap();
+        // only b.txt was committed
+        assert!(repo.is_dirty().unwrap());
+        assert_eq!(
+            "feat: two",
+            repo.last_commit().unwrap().unwrap().description
+        );
+
+        repo.commit("fix: three", None).unwrap();
+        assert!(!repo.is_dirty().unwrap());
+        assert_eq!(2, repo.all_files().unwrap().len());
+        assert!(repo.push_files().unwrap().is_empty());
+
+        repo.checkout("2").unwrap();
+        assert_eq!("2", repo.last_commit().unwrap().unwrap().id);
+        assert!(!repo.branch_exists("feature").unwrap());
     }
 }
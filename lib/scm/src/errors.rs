@@ -9,10 +9,19 @@ pub enum ScmError {
     #[error("Branch `{0}` already exists")]
     BranchAlreadyExists(String),
 
+    #[error("Command `{0}` failed: {1}")]
+    CommandFailed(String, String),
+
+    #[error("HEAD is detached and not on a branch")]
+    DetachedHead,
+
     /// Error variant that represents errors coming out of libgit2.
     #[error("Git error: `{0}`")]
     GitError(#[from] git2::Error),
 
+    #[error("Invalid commit range `{0}`")]
+    InvalidRange(String),
+
     #[error(transparent)]
     IoError(#[from] io::Error),
 
@@ -21,15 +30,30 @@ pub enum ScmError {
     )]
     OldHookExists(String),
 
+    #[error(transparent)]
+    PatternError(#[from] glob::PatternError),
+
     #[error("regex error: {0}")]
     RegexError(#[from] regex::Error),
 
+    #[error(transparent)]
+    SerdeXml(#[from] serde_xml_rs::Error),
+
+    #[error("Tag `{0}` does not exist")]
+    TagNotFound(String),
+
     #[error("Could not find supported SCM")]
     Unsupported,
 
     #[error("Hook {0} is not supported")]
     UnsupportedHook(String),
 
+    #[error("Hooks can only be installed for local repositories. `{0}` is not local")]
+    UnsupportedHooksLocation(String),
+
+    #[error("Revision `{0}` is not supported by {1}")]
+    UnsupportedRevision(String, String),
+
     #[error(transparent)]
     Utf8Error(#[from] FromUtf8Error),
 }
@@ -1,11 +1,15 @@
 use lazy_static::lazy_static;
 use regex::Regex;
 
+pub mod blame;
 pub mod commit;
+pub mod discovery;
 pub mod drivers;
 pub mod errors;
 pub mod hooks;
 pub mod platforms;
+pub mod remote;
+pub mod settings;
 
 pub const GIT: &str = "git";
 pub const HG: &str = "hg";
@@ -1,20 +1,65 @@
+pub mod azure;
+pub mod bitbucket;
+pub mod gitea;
 pub mod github;
 pub mod gitlab;
 
-use std::env;
 use std::hash::Hash;
 use std::path::PathBuf;
+use std::{env, fmt};
 
 use chrono::{DateTime, Utc};
 use serde_derive::{Deserialize, Serialize};
-use strum::{EnumIter, EnumString, VariantNames};
+use strum::{Display, EnumIter, EnumString, IntoEnumIterator, VariantNames};
+use thiserror::Error;
 
 use crate::commit::ScmSignature;
-use crate::platforms;
+use crate::platforms::azure::provider::AzureRepositoryBoundedProvider;
+use crate::platforms::bitbucket::provider::BitbucketRepositoryBoundedProvider;
+use crate::platforms::gitea::provider::GiteaRepositoryBoundedProvider;
+use crate::platforms::github::app::GitHubAppCredentials;
+use crate::platforms::github::provider::GithubRepositoryBoundedProvider;
+use crate::platforms::gitlab::provider::GitLabRepositoryBoundedProvider;
+use crate::remote::ScmRemote;
+
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub enum ScmPlatformError {
+    /// Errors of the platform clients which share the core API client
+    #[error(transparent)]
+    ApiClientError(#[from] api_client_core::client::ClientError),
+
+    #[error("Invalid GitHub App: {0}")]
+    InvalidGitHubApp(String),
+
+    #[error("No {0} access token found. Set one of {1}")]
+    MissingCredentials(ScmPlatform, String),
+
+    #[error("Unable to determine repository from {0}")]
+    UnknownRepository(String),
+
+    #[error("{0} is not supported")]
+    Unsupported(ScmPlatform),
+
+    #[error("{0} is not supported by the platform")]
+    UnsupportedOperation(&'static str),
+}
+
+pub type ScmPlatformResult<T> = Result<T, ScmPlatformError>;
 
 // TODO: rename to ScmHostedProviders?
 #[derive(
-    Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize, EnumIter, EnumString, VariantNames,
+    Clone,
+    Debug,
+    Deserialize,
+    Display,
+    Eq,
+    Hash,
+    PartialEq,
+    Serialize,
+    EnumIter,
+    EnumString,
+    VariantNames,
 )]
 #[serde(rename_all = "lowercase")]
 #[remain::sorted]
@@ -39,32 +84,284 @@ impl ScmPlatform {
         ]
     }
 
-    pub fn get_client_from_env<R>(&self) -> Box<dyn ScmPlatformClient<R>> {
-        let env_var_credentials_key = match self {
-            ScmPlatform::Azure => "",
-            ScmPlatform::BitBucket => "",
-            ScmPlatform::Gitea => "",
-            ScmPlatform::GitHub => "",
-            ScmPlatform::GitLab => "",
-            ScmPlatform::Gogs => "",
+    /// Environment variables, in order of precedence, holding an access token for the platform.
+    /// `SYSTEM_ACCESSTOKEN` is the job access token Azure Pipelines exposes when mapped into the
+    /// environment.
+    pub fn token_env_vars(&self) -> &'static [&'static str] {
+        match self {
+            ScmPlatform::Azure => &["AZURE_DEVOPS_EXT_PAT", "SYSTEM_ACCESSTOKEN"],
+            ScmPlatform::BitBucket => &["BITBUCKET_TOKEN"],
+            ScmPlatform::Gitea => &["GITEA_TOKEN", "FORGEJO_TOKEN"],
+            ScmPlatform::Gogs => &[],
+            ScmPlatform::GitHub => &["GITHUB_TOKEN", "GH_TOKEN"],
+            ScmPlatform::GitLab => &["GITLAB_TOKEN"],
+        }
+    }
+
+    /// Environment variables, in order of precedence, holding the API URL of a self-hosted
+    /// instance. `GITHUB_API_URL` and `CI_API_V4_URL` are set by GitHub Actions and GitLab CI.
+    /// Gitea and Forgejo Actions also set `GITHUB_API_URL`, to their own API, for compatibility.
+    pub fn api_url_env_vars(&self) -> &'static [&'static str] {
+        match self {
+            ScmPlatform::Azure | ScmPlatform::BitBucket | ScmPlatform::Gogs => &[],
+            ScmPlatform::Gitea => &["GITEA_API_URL", "FORGEJO_API_URL", "GITHUB_API_URL"],
+            ScmPlatform::GitHub => &["GITHUB_API_URL"],
+            ScmPlatform::GitLab => &["GITLAB_API_URL", "CI_API_V4_URL"],
+        }
+    }
+
+    /// Host of the platform's public instance
+    pub fn default_host(&self) -> Option<&'static str> {
+        match self {
+            ScmPlatform::Azure => Some("dev.azure.com"),
+            ScmPlatform::BitBucket => Some("bitbucket.org"),
+            ScmPlatform::Gitea => Some("gitea.com"),
+            ScmPlatform::GitHub => Some("github.com"),
+            ScmPlatform::GitLab => Some("gitlab.com"),
+            ScmPlatform::Gogs => None,
+        }
+    }
+
+    /// API URL of the instance at `host`.
+    /// Returns `None` for the public instance whose API clients use by default. Bitbucket's
+    /// client only supports Bitbucket Cloud.
+    pub fn api_url(&self, host: &str) -> Option<String> {
+        if self.default_host() == Some(host) {
+            return None;
+        }
+
+        match self {
+            // remotes of Azure DevOps Services use ssh.dev.azure.com or <org>.visualstudio.com
+            ScmPlatform::Azure if host.ends_with("dev.azure.com") => None,
+            ScmPlatform::Azure => Some(format!("https://{host}")),
+            ScmPlatform::BitBucket => None,
+            ScmPlatform::Gitea | ScmPlatform::Gogs => Some(format!("https://{host}/api/v1")),
+            ScmPlatform::GitHub => Some(format!("https://{host}/api/v3")),
+            ScmPlatform::GitLab => Some(format!("https://{host}/api/v4")),
+        }
+    }
+
+    /// Reads credentials from the [`token_env_vars`] and [`api_url_env_vars`] environment
+    /// variables. Without an API URL override the API URL is derived from `host`.
+    /// GitHub clients authenticate as the GitHub App of the `GITHUB_APP_*` variables when set,
+    /// falling back to access tokens otherwise.
+    ///
+    /// [`token_env_vars`]: ScmPlatform::token_env_vars
+    /// [`api_url_env_vars`]: ScmPlatform::api_url_env_vars
+    pub fn credentials_from_env(
+        &self,
+        host: Option<&str>,
+    ) -> ScmPlatformResult<ScmPlatformCredentials> {
+        self.credentials_from(host, None, |key| env::var(key).ok())
+    }
+
+    /// Reads credentials from environment variables, like [`credentials_from_env`], with GitHub
+    /// clients authenticating as `github_app` rather than the app of the `GITHUB_APP_*` variables
+    ///
+    /// [`credentials_from_env`]: ScmPlatform::credentials_from_env
+    pub fn credentials_from_env_with_github_app(
+        &self,
+        host: Option<&str>,
+        github_app: Option<GitHubAppCredentials>,
+    ) -> ScmPlatformResult<ScmPlatformCredentials> {
+        self.credentials_from(host, github_app, |key| env::var(key).ok())
+    }
+
+    fn credentials_from<F>(
+        &self,
+        host: Option<&str>,
+        github_app: Option<GitHubAppCredentials>,
+        lookup: F,
+    ) -> ScmPlatformResult<ScmPlatformCredentials>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let token_env_vars = self.token_env_vars();
+        if token_env_vars.is_empty() {
+            return Err(ScmPlatformError::Unsupported(self.clone()));
+        }
+
+        let find = |keys: &[&str]| {
+            keys.iter()
+                .filter_map(|key| lookup(key))
+                .find(|value| !value.trim().is_empty())
+        };
+
+        let github_app = match (self, github_app) {
+            (ScmPlatform::GitHub, Some(app)) => Some(app),
+            (ScmPlatform::GitHub, None) => GitHubAppCredentials::from_lookup(&lookup)?,
+            _ => None,
         };
 
+        // apps authenticate with installation tokens so don't need an access token
+        let token = match (find(token_env_vars), &github_app) {
+            (Some(token), _) => token,
+            (None, Some(_)) => String::new(),
+            (None, None) => {
+                return Err(ScmPlatformError::MissingCredentials(
+                    self.clone(),
+                    token_env_vars.join(", "),
+                ));
+            }
+        };
+        let api_url = find(self.api_url_env_vars()).or_else(|| host.and_then(|h| self.api_url(h)));
+
+        Ok(ScmPlatformCredentials {
+            token,
+            api_url,
+            github_app,
+        })
+    }
+
+    /// Creates a client bound to the `owner/repository` repository
+    pub fn get_client(
+        &self,
+        owner: &str,
+        repository: &str,
+        credentials: &ScmPlatformCredentials,
+    ) -> ScmPlatformResult<Box<dyn ScmPlatformRepositoryBoundedClient>> {
         match self {
-            ScmPlatform::Azure => {}
-            ScmPlatform::BitBucket => {}
-            ScmPlatform::Gitea => {}
             ScmPlatform::GitHub => {
-                let creds = env::var(env_var_credentials_key).unwrap();
-                let github_provider = platforms::github::provider::GithubProvider::new(&creds);
+                let client_credentials = match &credentials.github_app {
+                    Some(app) => {
+                        app.credentials(owner, repository, credentials.api_url.as_deref())?
+                    }
+                    None => {
+                        github_client::client::Credentials::PrivateToken(credentials.token.clone())
+                    }
+                };
+
+                Ok(Box::new(GithubRepositoryBoundedProvider::with_credentials(
+                    owner.to_string(),
+                    repository.to_string(),
+                    client_credentials,
+                    credentials.api_url.as_deref(),
+                )?))
             }
-            ScmPlatform::GitLab => {}
-            ScmPlatform::Gogs => {}
+            ScmPlatform::GitLab => Ok(Box::new(GitLabRepositoryBoundedProvider::new(
+                format!("{owner}/{repository}"),
+                &credentials.token,
+                credentials.api_url.as_deref(),
+            )?)),
+            ScmPlatform::Gitea => Ok(Box::new(GiteaRepositoryBoundedProvider::new(
+                owner.to_string(),
+                repository.to_string(),
+                &credentials.token,
+                credentials.api_url.as_deref(),
+            )?)),
+            ScmPlatform::Azure => Ok(Box::new(AzureRepositoryBoundedProvider::new(
+                owner.to_string(),
+                repository.to_string(),
+                &credentials.token,
+                credentials.api_url.as_deref(),
+            )?)),
+            ScmPlatform::BitBucket => Ok(Box::new(BitbucketRepositoryBoundedProvider::new(
+                owner.to_string(),
+                repository.to_string(),
+                &credentials.token,
+                credentials.api_url.as_deref(),
+            )?)),
+            ScmPlatform::Gogs => Err(ScmPlatformError::Unsupported(self.clone())),
         }
+    }
 
-        todo!()
+    /// Creates a client bound to the `owner/repository` repository with credentials from
+    /// environment variables. See [`credentials_from_env`].
+    ///
+    /// [`credentials_from_env`]: ScmPlatform::credentials_from_env
+    pub fn get_client_from_env(
+        &self,
+        owner: &str,
+        repository: &str,
+    ) -> ScmPlatformResult<Box<dyn ScmPlatformRepositoryBoundedClient>> {
+        let credentials = self.credentials_from_env(None)?;
+        self.get_client(owner, repository, &credentials)
     }
 
-    pub fn get_client_from_webhook(&self, data: serde_json::Value) {}
+    /// Creates a client bound to the remote's repository with credentials from environment
+    /// variables. The API URL of self-hosted instances is derived from the remote's host.
+    pub fn get_client_from_remote(
+        remote: &ScmRemote,
+    ) -> ScmPlatformResult<Box<dyn ScmPlatformRepositoryBoundedClient>> {
+        let credentials = remote.platform.credentials_from_env(Some(&remote.host))?;
+        remote
+            .platform
+            .get_client(&remote.owner, &remote.repo, &credentials)
+    }
+
+    /// Creates a client bound to the repository of a remote URL, detecting the platform from
+    /// the URL's host. Self-hosted instances whose host doesn't identify the platform are
+    /// detected from the [`api_url_env_vars`] pointing at the host.
+    ///
+    /// [`api_url_env_vars`]: ScmPlatform::api_url_env_vars
+    pub fn get_client_from_remote_url(
+        url: &str,
+    ) -> ScmPlatformResult<Box<dyn ScmPlatformRepositoryBoundedClient>> {
+        let remote = Self::remote_from_url(url, |key| env::var(key).ok())
+            .ok_or_else(|| ScmPlatformError::UnknownRepository(format!("remote `{url}`")))?;
+        Self::get_client_from_remote(&remote)
+    }
+
+    fn remote_from_url<F>(url: &str, lookup: F) -> Option<ScmRemote>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        if let Some(remote) = ScmRemote::parse(url) {
+            return Some(remote);
+        }
+
+        let host = ScmRemote::host_of(url)?;
+        // `GITHUB_API_URL` is shared by GitHub and Gitea so a variable only one platform reads
+        // wins, followed by the platform's precedence of the variable pointing at the host
+        let platform = ScmPlatform::iter()
+            .filter_map(|platform| {
+                platform
+                    .api_url_env_vars()
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, key)| {
+                        lookup(key)
+                            .is_some_and(|api_url| ScmRemote::host_of(&api_url) == Some(host))
+                    })
+                    .map(|(precedence, key)| {
+                        let shared = ScmPlatform::iter()
+                            .filter(|other| *other != platform)
+                            .any(|other| other.api_url_env_vars().contains(key));
+                        (shared, precedence)
+                    })
+                    .min()
+                    .map(|rank| (rank, platform))
+            })
+            .min_by_key(|(rank, _)| *rank)?
+            .1;
+
+        ScmRemote::parse_for_platform(url, platform)
+    }
+
+    /// Creates a client bound to the repository a webhook event was sent for with credentials
+    /// from environment variables
+    pub fn get_client_from_webhook(
+        &self,
+        data: &serde_json::Value,
+    ) -> ScmPlatformResult<Box<dyn ScmPlatformRepositoryBoundedClient>> {
+        Self::get_client_from_remote(&self.remote_from_webhook(data)?)
+    }
+
+    fn remote_from_webhook(&self, data: &serde_json::Value) -> ScmPlatformResult<ScmRemote> {
+        let pointer = match self {
+            ScmPlatform::Azure => "/resource/repository/remoteUrl",
+            ScmPlatform::BitBucket => "/repository/links/html/href",
+            ScmPlatform::Gitea | ScmPlatform::GitHub | ScmPlatform::Gogs => "/repository/html_url",
+            ScmPlatform::GitLab => "/project/web_url",
+        };
+
+        data.pointer(pointer)
+            .and_then(serde_json::Value::as_str)
+            .and_then(|url| ScmRemote::parse_for_platform(url, self.clone()))
+            .ok_or_else(|| {
+                ScmPlatformError::UnknownRepository(format!("{self} webhook `{pointer}`"))
+            })
+    }
 
     // pub fn get_client<R>(&self) -> Box<dyn ScmPlatformClient<R>> {
     //     match &self {
@@ -113,32 +410,65 @@ pub struct ScmPlatformMergeRequestComment {
     pub body: String,
 }
 
-pub enum Credentials {
-    Environment(String),
+/// Thread of comments on a merge request which, unlike individual comments, can be resolved
+pub struct ScmPlatformMergeRequestThread {
+    pub id: String,
+    pub resolvable: bool,
+    pub resolved: bool,
+    pub comments: Vec<ScmPlatformMergeRequestComment>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct MergeRequestNote {}
+/// Users and teams to request reviews of a merge request from
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ScmPlatformReviewers {
+    /// Usernames of the users
+    pub users: Vec<String>,
+    /// Teams, or groups, as `organization/team`
+    pub teams: Vec<String>,
+}
 
-// TODO: what if we have a concept of a "BoundScmPlatformClient" in which the client is bound
-// to an repository based on env, webhook, or other context?
-// Why? This way construction would be through a specific context that would internally the
-// associated repository identifier and it wouldnt have to be constructed externally and passed in
+/// Rule requiring a number of approvals from its users, or members of its groups, before a merge
+/// request can be merged
+pub struct ScmPlatformApprovalRule {
+    pub id: u64,
+    pub name: String,
+    pub approvals_required: u64,
+    /// Usernames of the users who can approve
+    pub users: Vec<String>,
+    /// Full paths of the groups whose members can approve
+    pub groups: Vec<String>,
+}
 
-// How about auth? We could get auth from env var, context object, Doctavious configuration, etc
-pub trait ScmPlatformClientBuilder {
-    fn from_env();
+pub enum Credentials {
+    Environment(String),
+}
 
-    fn from_webhook();
+/// Access token and API URL used to authenticate with an SCM platform
+#[derive(Clone, PartialEq)]
+pub struct ScmPlatformCredentials {
+    /// Empty when authenticating as a GitHub App without an access token to fall back to
+    pub token: String,
 
-    fn auth_from_env(&self);
+    /// API URL of a self-hosted instance. `None` uses the platform's public API
+    pub api_url: Option<String>,
 
-    // TODO: pass in auth. What do we need to support?
-    fn auth(&self);
+    /// GitHub App to authenticate GitHub clients as instead of the access token
+    pub github_app: Option<GitHubAppCredentials>,
+}
 
-    fn build(&self);
+impl fmt::Debug for ScmPlatformCredentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScmPlatformCredentials")
+            .field("token", &"*".repeat(self.token.len()))
+            .field("api_url", &self.api_url)
+            .field("github_app", &self.github_app)
+            .finish()
+    }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeRequestNote {}
+
 #[async_trait::async_trait]
 pub trait ScmPlatformClient<R> {
     // TODO: input so perhaps better as generic type parameter?
@@ -174,18 +504,19 @@ pub trait ScmPlatformClient<R> {
 // TODO: probably could just be named ScmPlatformRepositoryClient
 // A SCM platform client that is bound to a specific repository
 #[async_trait::async_trait]
-pub trait ScmPlatformRepositoryBoundedClient: Send {
+pub trait ScmPlatformRepositoryBoundedClient: Send + Sync {
     // TODO: per_page / page, sort, direction
-    // -> Vec<PullRequestNote>
-    // async fn list_all_merge_requests_notes(&self, mr: u64);
-    async fn list_all_merge_requests_notes(&self, mr: u64) -> Vec<ScmPlatformMergeRequestComment>;
+    async fn list_all_merge_requests_notes(
+        &self,
+        mr: u64,
+    ) -> ScmPlatformResult<Vec<ScmPlatformMergeRequestComment>>;
 
     // async fn create_merge_request_note(
     //     &self,
     //     mr: u64,
     //     body: String,
     // );
-    async fn create_merge_request_note(&self, mr: u64, body: String);
+    async fn create_merge_request_note(&self, mr: u64, body: String) -> ScmPlatformResult<()>;
 
     // async fn update_merge_request_note(
     //     &self,
@@ -193,5 +524,219 @@ pub trait ScmPlatformRepositoryBoundedClient: Send {
     //     note_id: u64,
     //     body: String,
     // );
-    async fn update_merge_request_note(&self, mr: u64, note_id: u64, body: String);
+    async fn update_merge_request_note(
+        &self,
+        mr: u64,
+        note_id: u64,
+        body: String,
+    ) -> ScmPlatformResult<()>;
+
+    /// Paths of the files the merge request changes, for when there's no local checkout to diff
+    async fn list_merge_request_changed_files(&self, _mr: u64) -> ScmPlatformResult<Vec<String>> {
+        Err(ScmPlatformError::UnsupportedOperation(
+            "listing merge request changed files",
+        ))
+    }
+
+    async fn list_merge_request_threads(
+        &self,
+        _mr: u64,
+    ) -> ScmPlatformResult<Vec<ScmPlatformMergeRequestThread>> {
+        Err(ScmPlatformError::UnsupportedOperation(
+            "merge request threads",
+        ))
+    }
+
+    async fn create_merge_request_thread(
+        &self,
+        _mr: u64,
+        _body: String,
+    ) -> ScmPlatformResult<ScmPlatformMergeRequestThread> {
+        Err(ScmPlatformError::UnsupportedOperation(
+            "merge request threads",
+        ))
+    }
+
+    async fn resolve_merge_request_thread(
+        &self,
+        _mr: u64,
+        _thread_id: &str,
+        _resolved: bool,
+    ) -> ScmPlatformResult<()> {
+        Err(ScmPlatformError::UnsupportedOperation(
+            "merge request threads",
+        ))
+    }
+
+    async fn list_merge_request_approval_rules(
+        &self,
+        _mr: u64,
+    ) -> ScmPlatformResult<Vec<ScmPlatformApprovalRule>> {
+        Err(ScmPlatformError::UnsupportedOperation(
+            "merge request approval rules",
+        ))
+    }
+
+    /// Requests reviews of the merge request, keeping any reviews already requested
+    async fn request_merge_request_reviewers(
+        &self,
+        _mr: u64,
+        _reviewers: ScmPlatformReviewers,
+    ) -> ScmPlatformResult<()> {
+        Err(ScmPlatformError::UnsupportedOperation(
+            "requesting merge request reviewers",
+        ))
+    }
+
+    /// Usernames of the members of a team, given as `organization/team`
+    async fn list_team_members(&self, _team: &str) -> ScmPlatformResult<Vec<String>> {
+        Err(ScmPlatformError::UnsupportedOperation(
+            "listing team members",
+        ))
+    }
+
+    /// Creates the approval rule named `name`, or replaces the approvers of the existing rule, so
+    /// that approvers derived from files such as CODEOWNERS stay in sync with the merge request
+    async fn sync_merge_request_approval_rule(
+        &self,
+        _mr: u64,
+        _name: &str,
+        _approvals_required: u64,
+        _usernames: Vec<String>,
+    ) -> ScmPlatformResult<()> {
+        Err(ScmPlatformError::UnsupportedOperation(
+            "merge request approval rules",
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use serde_json::json;
+    use test_case::test_case;
+
+    use crate::platforms::{ScmPlatform, ScmPlatformCredentials, ScmPlatformError};
+
+    #[test_case(ScmPlatform::GitHub, &[("GH_TOKEN", "gh")], None => (Some("gh".to_string()), None); "github fallback token")]
+    #[test_case(ScmPlatform::GitHub, &[("GITHUB_TOKEN", "a"), ("GH_TOKEN", "b")], Some("github.com") => (Some("a".to_string()), None); "github public host")]
+    #[test_case(ScmPlatform::GitHub, &[("GITHUB_TOKEN", "a")], Some("github.example.com") => (Some("a".to_string()), Some("https://github.example.com/api/v3".to_string())); "github enterprise host")]
+    #[test_case(ScmPlatform::GitHub, &[("GITHUB_TOKEN", "a"), ("GITHUB_API_URL", "https://ghe.example.com/api/v3")], Some("github.example.com") => (Some("a".to_string()), Some("https://ghe.example.com/api/v3".to_string())); "github api url override")]
+    #[test_case(ScmPlatform::GitLab, &[("GITLAB_TOKEN", "gl"), ("CI_API_V4_URL", "https://git.example.com/api/v4")], None => (Some("gl".to_string()), Some("https://git.example.com/api/v4".to_string())); "gitlab ci api url")]
+    #[test_case(ScmPlatform::GitLab, &[("GITLAB_TOKEN", " ")], None => (None, None); "gitlab blank token")]
+    #[test_case(ScmPlatform::Gitea, &[("GITEA_TOKEN", "gt")], Some("codeberg.org") => (Some("gt".to_string()), Some("https://codeberg.org/api/v1".to_string())); "gitea host")]
+    #[test_case(ScmPlatform::Azure, &[("SYSTEM_ACCESSTOKEN", "az")], Some("ssh.dev.azure.com") => (Some("az".to_string()), None); "azure pipelines token")]
+    #[test_case(ScmPlatform::Azure, &[("AZURE_DEVOPS_EXT_PAT", "pat")], Some("org.visualstudio.com") => (Some("pat".to_string()), Some("https://org.visualstudio.com".to_string())); "azure legacy host")]
+    #[test_case(ScmPlatform::BitBucket, &[("BITBUCKET_TOKEN", "bb")], Some("bitbucket.org") => (Some("bb".to_string()), None); "bitbucket")]
+    fn credentials_from(
+        platform: ScmPlatform,
+        env: &[(&str, &str)],
+        host: Option<&str>,
+    ) -> (Option<String>, Option<String>) {
+        let env: HashMap<&str, &str> = env.iter().copied().collect();
+        match platform.credentials_from(host, None, |key| env.get(key).map(|v| v.to_string())) {
+            Ok(credentials) => (Some(credentials.token), credentials.api_url),
+            Err(ScmPlatformError::MissingCredentials(_, vars)) => {
+                assert_eq!("GITLAB_TOKEN", vars);
+                (None, None)
+            }
+            Err(e) => panic!("unexpected error {e}"),
+        }
+    }
+
+    #[test_case("git@git.corp.com:team/repo.git", &[("GITLAB_API_URL", "https://git.corp.com/api/v4")] => Some(ScmPlatform::GitLab); "gitlab api url")]
+    #[test_case("https://git.corp.com/team/repo", &[("GITEA_API_URL", "https://git.corp.com/api/v1")] => Some(ScmPlatform::Gitea); "gitea api url")]
+    #[test_case("git@git.corp.com:team/repo.git", &[("GITHUB_API_URL", "https://git.corp.com/api/v3")] => Some(ScmPlatform::GitHub); "shared github api url")]
+    #[test_case("git@git.corp.com:team/repo.git", &[("GITHUB_API_URL", "https://git.corp.com/api/v1"), ("FORGEJO_API_URL", "https://git.corp.com/api/v1")] => Some(ScmPlatform::Gitea); "forgejo actions")]
+    #[test_case("git@git.corp.com:team/repo.git", &[("GITLAB_API_URL", "https://gitlab.other.com/api/v4")] => None; "other host")]
+    #[test_case("git@github.com:team/repo.git", &[("GITLAB_API_URL", "https://github.com/api/v4")] => Some(ScmPlatform::GitHub); "recognized host")]
+    fn remote_from_url(url: &str, env: &[(&str, &str)]) -> Option<ScmPlatform> {
+        let env: HashMap<&str, &str> = env.iter().copied().collect();
+        let remote = ScmPlatform::remote_from_url(url, |key| env.get(key).map(|v| v.to_string()))?;
+        assert_eq!(
+            ("team", "repo"),
+            (remote.owner.as_str(), remote.repo.as_str())
+        );
+        Some(remote.platform)
+    }
+
+    #[test]
+    fn should_prefer_github_app_over_access_tokens() {
+        let env: HashMap<&str, &str> =
+            HashMap::from([("GITHUB_APP_ID", "123"), ("GITHUB_APP_PRIVATE_KEY", "key")]);
+        let lookup = |key: &str| env.get(key).map(|v| v.to_string());
+
+        let credentials = ScmPlatform::GitHub
+            .credentials_from(None, None, lookup)
+            .unwrap();
+        assert_eq!("", credentials.token);
+        assert_eq!(123, credentials.github_app.unwrap().app_id);
+
+        // only GitHub clients authenticate as apps
+        assert!(matches!(
+            ScmPlatform::GitLab.credentials_from(None, None, lookup),
+            Err(ScmPlatformError::MissingCredentials(..))
+        ));
+    }
+
+    #[test]
+    fn should_not_support_platforms_without_clients() {
+        let credentials = ScmPlatformCredentials {
+            token: "token".to_string(),
+            api_url: None,
+            github_app: None,
+        };
+
+        assert!(
+            ScmPlatform::GitHub
+                .get_client("doctavious", "doctavious", &credentials)
+                .is_ok()
+        );
+        assert!(
+            ScmPlatform::GitLab
+                .get_client("group/sub", "project", &credentials)
+                .is_ok()
+        );
+        assert!(
+            ScmPlatform::Gitea
+                .get_client("forgejo", "forgejo", &credentials)
+                .is_ok()
+        );
+        assert!(
+            ScmPlatform::BitBucket
+                .get_client("workspace", "repo", &credentials)
+                .is_ok()
+        );
+        assert!(
+            ScmPlatform::Azure
+                .get_client("org/project", "repo", &credentials)
+                .is_ok()
+        );
+        assert!(matches!(
+            ScmPlatform::Gogs.get_client("gogs", "gogs", &credentials),
+            Err(ScmPlatformError::Unsupported(ScmPlatform::Gogs))
+        ));
+        assert!(matches!(
+            ScmPlatform::Gogs.credentials_from(None, None, |_| Some("token".to_string())),
+            Err(ScmPlatformError::Unsupported(ScmPlatform::Gogs))
+        ));
+        assert!(matches!(
+            ScmPlatform::get_client_from_remote_url("/srv/git/repo"),
+            Err(ScmPlatformError::UnknownRepository(_))
+        ));
+    }
+
+    #[test_case(ScmPlatform::GitHub, json!({"repository": {"html_url": "https://github.example.com/doctavious/doctavious"}}) => Some(("github.example.com".to_string(), "doctavious".to_string(), "doctavious".to_string())); "github")]
+    #[test_case(ScmPlatform::GitLab, json!({"project": {"web_url": "https://gitlab.com/group/sub/project"}}) => Some(("gitlab.com".to_string(), "group/sub".to_string(), "project".to_string())); "gitlab")]
+    #[test_case(ScmPlatform::GitLab, json!({"repository": {"html_url": "https://gitlab.com/group/project"}}) => None; "missing project")]
+    fn remote_from_webhook(
+        platform: ScmPlatform,
+        data: serde_json::Value,
+    ) -> Option<(String, String, String)> {
+        platform
+            .remote_from_webhook(&data)
+            .ok()
+            .map(|r| (r.host, r.owner, r.repo))
+    }
 }
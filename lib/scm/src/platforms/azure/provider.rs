@@ -0,0 +1,168 @@
+use std::sync::Arc;
+
+use crate::platforms::{
+    ScmPlatformMergeRequestComment, ScmPlatformRepositoryBoundedClient, ScmPlatformResult,
+};
+
+/// Azure DevOps numbers comments within each thread so a thread's first comment is always 1
+const FIRST_COMMENT_ID: u64 = 1;
+
+/// Azure DevOps groups comments into threads. Notes are the first comment of threads, identified
+/// by the thread, as replies and comments recording pull request updates aren't notes.
+pub struct AzureRepositoryBoundedProvider {
+    /// Organization and project, e.g., `org/project`
+    pub project: String,
+    pub repository: String,
+    pub client: Arc<azure_devops_client::client::Client>,
+}
+
+impl AzureRepositoryBoundedProvider {
+    pub fn new(
+        project: String,
+        repository: String,
+        credentials: &str,
+        host: Option<&str>,
+    ) -> ScmPlatformResult<Self> {
+        let mut builder = azure_devops_client::client::ClientBuilder::new()?
+            .with_credentials(
+                azure_devops_client::client::Credentials::PersonalAccessToken(String::from(
+                    credentials,
+                )),
+            )
+            .with_rate_limit()
+            .with_tracing();
+
+        if let Some(host) = host {
+            builder = builder.with_host_override(host.trim_end_matches('/'));
+        }
+
+        let client = builder.build()?;
+
+        Ok(Self {
+            project,
+            repository,
+            client: Arc::new(client),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl ScmPlatformRepositoryBoundedClient for AzureRepositoryBoundedProvider {
+    async fn list_all_merge_requests_notes(
+        &self,
+        pr: u64,
+    ) -> ScmPlatformResult<Vec<ScmPlatformMergeRequestComment>> {
+        let threads = self
+            .client
+            .pull_requests()
+            .list_pull_request_threads(&self.project, &self.repository, pr)
+            .await?;
+
+        Ok(threads
+            .body
+            .into_iter()
+            .filter(|t| !t.is_deleted)
+            .filter_map(|t| {
+                let comment = t.comments.into_iter().next()?;
+                if comment.is_deleted || comment.comment_type.as_deref() != Some("text") {
+                    return None;
+                }
+
+                Some(ScmPlatformMergeRequestComment {
+                    id: t.id,
+                    body: comment.content.unwrap_or_default(),
+                })
+            })
+            .collect())
+    }
+
+    async fn create_merge_request_note(&self, pr: u64, body: String) -> ScmPlatformResult<()> {
+        self.client
+            .pull_requests()
+            .create_pull_request_thread(&self.project, &self.repository, pr, body)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn update_merge_request_note(
+        &self,
+        pr: u64,
+        note_id: u64,
+        body: String,
+    ) -> ScmPlatformResult<()> {
+        self.client
+            .pull_requests()
+            .update_pull_request_comment(
+                &self.project,
+                &self.repository,
+                pr,
+                note_id,
+                FIRST_COMMENT_ID,
+                body,
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use wiremock::matchers::{body_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use crate::platforms::ScmPlatformRepositoryBoundedClient;
+    use crate::platforms::azure::provider::AzureRepositoryBoundedProvider;
+
+    const THREADS: &str = "/org/project/_apis/git/repositories/repo/pullRequests/7/threads";
+
+    #[tokio::test]
+    async fn should_use_first_comment_of_threads_as_notes() {
+        let server = MockServer::start().await;
+        let comment = |id: u64, content: &str, comment_type: &str| json!({"id": id, "parentCommentId": 0, "content": content, "commentType": comment_type});
+        Mock::given(method("GET"))
+            .and(path(THREADS))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "count": 4,
+                "value": [
+                    {"id": 1, "comments": [comment(1, "Policy check passed", "system")]},
+                    {"id": 2, "comments": [comment(1, "note", "text"), comment(2, "reply", "text")]},
+                    {"id": 3, "comments": [comment(1, "deleted", "text")], "isDeleted": true},
+                    {"id": 4, "comments": []}
+                ]
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("PATCH"))
+            .and(path(format!("{THREADS}/2/comments/1")))
+            .and(body_json(json!({"content": "updated"})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(comment(1, "updated", "text")))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = AzureRepositoryBoundedProvider::new(
+            "org/project".to_string(),
+            "repo".to_string(),
+            "pat",
+            Some(&server.uri()),
+        )
+        .unwrap();
+
+        let notes = provider.list_all_merge_requests_notes(7).await.unwrap();
+        assert_eq!(
+            vec![(2, "note")],
+            notes
+                .iter()
+                .map(|n| (n.id, n.body.as_str()))
+                .collect::<Vec<_>>()
+        );
+
+        provider
+            .update_merge_request_note(7, notes[0].id, "updated".to_string())
+            .await
+            .unwrap();
+    }
+}
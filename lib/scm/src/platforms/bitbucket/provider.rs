@@ -0,0 +1,86 @@
+use std::sync::Arc;
+
+use crate::platforms::{
+    ScmPlatformMergeRequestComment, ScmPlatformRepositoryBoundedClient, ScmPlatformResult,
+};
+
+pub struct BitbucketRepositoryBoundedProvider {
+    pub workspace: String,
+    pub repository: String,
+    pub client: Arc<bitbucket_client::client::Client>,
+}
+
+impl BitbucketRepositoryBoundedProvider {
+    pub fn new(
+        workspace: String,
+        repository: String,
+        credentials: &str,
+        host: Option<&str>,
+    ) -> ScmPlatformResult<Self> {
+        let mut builder = bitbucket_client::client::ClientBuilder::new()?
+            .with_credentials(bitbucket_client::client::Credentials::Token(String::from(
+                credentials,
+            )))
+            .with_rate_limit()
+            .with_tracing();
+
+        if let Some(host) = host {
+            builder = builder.with_host_override(host.trim_end_matches('/'));
+        }
+
+        let client = builder.build()?;
+
+        Ok(Self {
+            workspace,
+            repository,
+            client: Arc::new(client),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl ScmPlatformRepositoryBoundedClient for BitbucketRepositoryBoundedProvider {
+    async fn list_all_merge_requests_notes(
+        &self,
+        pr: u64,
+    ) -> ScmPlatformResult<Vec<ScmPlatformMergeRequestComment>> {
+        let comments = self
+            .client
+            .pull_requests()
+            .list_all_pull_request_comments(&self.workspace, &self.repository, pr, None)
+            .await?;
+
+        Ok(comments
+            .body
+            .into_iter()
+            .filter(|c| !c.deleted)
+            .map(|c| ScmPlatformMergeRequestComment {
+                id: c.id,
+                body: c.content.raw,
+            })
+            .collect())
+    }
+
+    async fn create_merge_request_note(&self, pr: u64, body: String) -> ScmPlatformResult<()> {
+        self.client
+            .pull_requests()
+            .create_pull_request_comment(&self.workspace, &self.repository, pr, body)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn update_merge_request_note(
+        &self,
+        pr: u64,
+        note_id: u64,
+        body: String,
+    ) -> ScmPlatformResult<()> {
+        self.client
+            .pull_requests()
+            .update_pull_request_comment(&self.workspace, &self.repository, pr, note_id, body)
+            .await?;
+
+        Ok(())
+    }
+}
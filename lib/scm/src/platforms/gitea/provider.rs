@@ -0,0 +1,116 @@
+use std::sync::Arc;
+
+use gitea_client::pulls::ReviewersRequest;
+
+use crate::platforms::{
+    ScmPlatformMergeRequestComment, ScmPlatformRepositoryBoundedClient, ScmPlatformResult,
+    ScmPlatformReviewers,
+};
+
+pub struct GiteaRepositoryBoundedProvider {
+    pub owner: String,
+    pub repository: String,
+    pub client: Arc<gitea_client::client::Client>,
+}
+
+impl GiteaRepositoryBoundedProvider {
+    pub fn new(
+        owner: String,
+        repository: String,
+        credentials: &str,
+        host: Option<&str>,
+    ) -> ScmPlatformResult<Self> {
+        let mut builder = gitea_client::client::ClientBuilder::new()?
+            .with_credentials(gitea_client::client::Credentials::Token(String::from(
+                credentials,
+            )))
+            .with_rate_limit()
+            .with_tracing();
+
+        if let Some(host) = host {
+            builder = builder.with_host_override(host.trim_end_matches('/'));
+        }
+
+        let client = builder.build()?;
+
+        Ok(Self {
+            owner,
+            repository,
+            client: Arc::new(client),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl ScmPlatformRepositoryBoundedClient for GiteaRepositoryBoundedProvider {
+    async fn list_all_merge_requests_notes(
+        &self,
+        pr: u64,
+    ) -> ScmPlatformResult<Vec<ScmPlatformMergeRequestComment>> {
+        let comments = self
+            .client
+            .pull_requests()
+            .list_all_pull_request_comments(&self.owner, &self.repository, pr, None)
+            .await?;
+
+        Ok(comments
+            .body
+            .into_iter()
+            .map(|c| ScmPlatformMergeRequestComment {
+                id: c.id,
+                body: c.body,
+            })
+            .collect())
+    }
+
+    async fn create_merge_request_note(&self, pr: u64, body: String) -> ScmPlatformResult<()> {
+        self.client
+            .pull_requests()
+            .create_pull_request_comment(&self.owner, &self.repository, pr, body)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn update_merge_request_note(
+        &self,
+        _pr: u64,
+        note_id: u64,
+        body: String,
+    ) -> ScmPlatformResult<()> {
+        self.client
+            .pull_requests()
+            .update_pull_request_comment(&self.owner, &self.repository, note_id, body)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn request_merge_request_reviewers(
+        &self,
+        pr: u64,
+        reviewers: ScmPlatformReviewers,
+    ) -> ScmPlatformResult<()> {
+        // teams are requested by their name within the repository's organization
+        let team_reviewers = reviewers
+            .teams
+            .iter()
+            .map(|t| t.rsplit('/').next().unwrap_or(t).to_string())
+            .collect();
+
+        self.client
+            .pull_requests()
+            .request_reviewers(
+                &self.owner,
+                &self.repository,
+                pr,
+                &ReviewersRequest {
+                    reviewers: reviewers.users,
+                    team_reviewers,
+                },
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -1,3 +1,4 @@
+pub mod app;
 pub mod provider;
 
 type ClientResult<T> = Result<T, ClientError>;
@@ -1,11 +1,14 @@
 use std::sync::Arc;
 
+use github_client::models::pulls::ReviewersRequest;
+
 use crate::platforms::github::ClientResult;
 use crate::platforms::{
     ScmPlatformClient, ScmPlatformMergeRequestComment, ScmPlatformRepositoryBoundedClient,
+    ScmPlatformResult, ScmPlatformReviewers,
 };
 
-pub(crate) struct GithubProvider {
+pub struct GithubProvider {
     pub client: Arc<github_client::client::Client>,
 }
 
@@ -99,11 +102,27 @@ impl GithubRepositoryBoundedProvider {
         repository: String,
         credentials: &str,
         host: Option<&str>,
-    ) -> ClientResult<Self> {
-        // TODO: include retry middleware and eventually tracing
-        let mut builder = github_client::client::ClientBuilder::new()?.with_credentials(
+    ) -> ScmPlatformResult<Self> {
+        Self::with_credentials(
+            owner,
+            repository,
             github_client::client::Credentials::PrivateToken(String::from(credentials)),
-        );
+            host,
+        )
+    }
+
+    /// Creates a provider authenticating with any client credentials, such as those of a GitHub
+    /// App installation
+    pub fn with_credentials(
+        owner: String,
+        repository: String,
+        credentials: github_client::client::Credentials,
+        host: Option<&str>,
+    ) -> ScmPlatformResult<Self> {
+        let mut builder = github_client::client::ClientBuilder::new()?
+            .with_credentials(credentials)
+            .with_rate_limit()
+            .with_tracing();
 
         if let Some(host) = host {
             builder = builder.with_host_override(host);
@@ -121,33 +140,85 @@ impl GithubRepositoryBoundedProvider {
 
 #[async_trait::async_trait]
 impl ScmPlatformRepositoryBoundedClient for GithubRepositoryBoundedProvider {
-    // TODO: sort / order by / pagination
-    async fn list_all_merge_requests_notes(&self, pr: u64) -> Vec<ScmPlatformMergeRequestComment> {
+    // TODO: sort / order by
+    async fn list_all_merge_requests_notes(
+        &self,
+        pr: u64,
+    ) -> ScmPlatformResult<Vec<ScmPlatformMergeRequestComment>> {
         let comments = self
             .client
             .pull_requests()
             .list_all_pull_request_notes(&self.owner, &self.repository, pr, None, None, None)
-            .await;
-
-        todo!()
+            .await?;
+
+        Ok(comments
+            .body
+            .into_iter()
+            .map(|c| ScmPlatformMergeRequestComment {
+                id: c.id,
+                body: c.body,
+            })
+            .collect())
     }
 
-    async fn create_merge_request_note(&self, pr: u64, body: String) {
-        let comment = self
-            .client
+    async fn create_merge_request_note(&self, pr: u64, body: String) -> ScmPlatformResult<()> {
+        self.client
             .pull_requests()
             .create_pull_request_note(&self.owner, &self.repository, pr, body)
-            .await;
+            .await?;
+
+        Ok(())
     }
 
     // TODO: should we try and avoid forcing pr argument if its not used?
     // We could try and force something common at the SCM level rather than the client
     // Could use a struct rather than individual args which could be a common struct or a generic/associative type
-    async fn update_merge_request_note(&self, _pr: u64, note_id: u64, body: String) {
-        let comment = self
-            .client
+    async fn update_merge_request_note(
+        &self,
+        _pr: u64,
+        note_id: u64,
+        body: String,
+    ) -> ScmPlatformResult<()> {
+        self.client
             .pull_requests()
             .update_pull_request_note(&self.owner, &self.repository, note_id, body)
-            .await;
+            .await?;
+
+        Ok(())
+    }
+
+    async fn request_merge_request_reviewers(
+        &self,
+        pr: u64,
+        reviewers: ScmPlatformReviewers,
+    ) -> ScmPlatformResult<()> {
+        // teams are requested by their slug within the repository's organization
+        let team_reviewers = reviewers
+            .teams
+            .iter()
+            .map(|t| t.rsplit('/').next().unwrap_or(t).to_string())
+            .collect();
+
+        self.client
+            .pull_requests()
+            .request_reviewers(
+                &self.owner,
+                &self.repository,
+                pr,
+                &ReviewersRequest {
+                    reviewers: reviewers.users,
+                    team_reviewers,
+                },
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn list_team_members(&self, team: &str) -> ScmPlatformResult<Vec<String>> {
+        let (org, slug) = team.split_once('/').unwrap_or((&self.owner, team));
+        let members = self.client.teams().list_team_members(org, slug).await?;
+
+        Ok(members.body.into_iter().map(|m| m.login).collect())
     }
 }
@@ -1,6 +1,13 @@
 use std::sync::Arc;
 
-use crate::platforms::ScmPlatformClient;
+use gitlab_client::merge_requests::{MergeRequestApprovalRuleRequest, MergeRequestDiscussion};
+use tracing::{debug, warn};
+
+use crate::platforms::{
+    ScmPlatformApprovalRule, ScmPlatformClient, ScmPlatformMergeRequestComment,
+    ScmPlatformMergeRequestThread, ScmPlatformRepositoryBoundedClient, ScmPlatformResult,
+    ScmPlatformReviewers,
+};
 
 pub struct GitLabProvider {
     pub client: Arc<gitlab_client::client::Client>,
@@ -57,3 +64,256 @@ impl ScmPlatformClient<GitlabRepositoryIdentifier> for GitLabProvider {
             .body;
     }
 }
+
+pub struct GitLabRepositoryBoundedProvider {
+    /// ID or path, including namespace, of the project
+    pub project_id: String,
+    pub client: Arc<gitlab_client::client::Client>,
+}
+
+impl GitLabRepositoryBoundedProvider {
+    pub fn new(
+        project_id: String,
+        credentials: &str,
+        host: Option<&str>,
+    ) -> ScmPlatformResult<Self> {
+        let mut builder = gitlab_client::client::ClientBuilder::new()?
+            .with_credentials(gitlab_client::client::Credentials::PrivateToken(
+                String::from(credentials),
+            ))
+            .with_rate_limit()
+            .with_tracing();
+
+        if let Some(host) = host {
+            builder = builder.with_host_override(host.trim_end_matches('/'));
+        }
+
+        let client = builder.build()?;
+
+        Ok(Self {
+            project_id,
+            client: Arc::new(client),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl ScmPlatformRepositoryBoundedClient for GitLabRepositoryBoundedProvider {
+    // TODO: sort / order by
+    async fn list_all_merge_requests_notes(
+        &self,
+        mr: u64,
+    ) -> ScmPlatformResult<Vec<ScmPlatformMergeRequestComment>> {
+        let notes = self
+            .client
+            .merge_requests()
+            .list_all_merge_request_notes(&self.project_id, mr, None, None, None)
+            .await?;
+
+        Ok(notes
+            .body
+            .into_iter()
+            .map(|n| ScmPlatformMergeRequestComment {
+                id: n.id,
+                body: n.body,
+            })
+            .collect())
+    }
+
+    async fn create_merge_request_note(&self, mr: u64, body: String) -> ScmPlatformResult<()> {
+        self.client
+            .merge_requests()
+            .create_merge_request_note(&self.project_id, mr, body)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn update_merge_request_note(
+        &self,
+        mr: u64,
+        note_id: u64,
+        body: String,
+    ) -> ScmPlatformResult<()> {
+        self.client
+            .merge_requests()
+            .update_merge_request_note(&self.project_id, mr, note_id, body)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn list_merge_request_changed_files(&self, mr: u64) -> ScmPlatformResult<Vec<String>> {
+        let diffs = self
+            .client
+            .merge_requests()
+            .list_merge_request_diffs(&self.project_id, mr)
+            .await?;
+
+        Ok(diffs.body.into_iter().map(|d| d.new_path).collect())
+    }
+
+    async fn list_merge_request_threads(
+        &self,
+        mr: u64,
+    ) -> ScmPlatformResult<Vec<ScmPlatformMergeRequestThread>> {
+        let discussions = self
+            .client
+            .merge_requests()
+            .list_merge_request_discussions(&self.project_id, mr)
+            .await?;
+
+        Ok(discussions
+            .body
+            .into_iter()
+            .filter(|d| !d.individual_note)
+            .map(thread_from)
+            .collect())
+    }
+
+    async fn create_merge_request_thread(
+        &self,
+        mr: u64,
+        body: String,
+    ) -> ScmPlatformResult<ScmPlatformMergeRequestThread> {
+        let discussion = self
+            .client
+            .merge_requests()
+            .create_merge_request_discussion(&self.project_id, mr, body)
+            .await?;
+
+        Ok(thread_from(discussion.body))
+    }
+
+    async fn resolve_merge_request_thread(
+        &self,
+        mr: u64,
+        thread_id: &str,
+        resolved: bool,
+    ) -> ScmPlatformResult<()> {
+        self.client
+            .merge_requests()
+            .resolve_merge_request_discussion(&self.project_id, mr, thread_id, resolved)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn list_merge_request_approval_rules(
+        &self,
+        mr: u64,
+    ) -> ScmPlatformResult<Vec<ScmPlatformApprovalRule>> {
+        let rules = self
+            .client
+            .merge_requests()
+            .list_merge_request_approval_rules(&self.project_id, mr)
+            .await?;
+
+        Ok(rules
+            .body
+            .into_iter()
+            .map(|r| ScmPlatformApprovalRule {
+                id: r.id,
+                name: r.name,
+                approvals_required: r.approvals_required,
+                users: r.users.into_iter().map(|u| u.username).collect(),
+                groups: r.groups.into_iter().map(|g| g.full_path).collect(),
+            })
+            .collect())
+    }
+
+    async fn request_merge_request_reviewers(
+        &self,
+        mr: u64,
+        reviewers: ScmPlatformReviewers,
+    ) -> ScmPlatformResult<()> {
+        if !reviewers.teams.is_empty() {
+            debug!(
+                "GitLab can only request reviews from users, skipping groups {}",
+                reviewers.teams.join(", ")
+            );
+        }
+
+        // setting reviewers replaces them so keep those already requested
+        let merge_request = self
+            .client
+            .merge_requests()
+            .get_merge_request(&self.project_id, mr)
+            .await?
+            .body;
+        let mut reviewer_ids: Vec<u64> = merge_request.reviewers.iter().map(|r| r.id).collect();
+        for username in &reviewers.users {
+            let users = self
+                .client
+                .users()
+                .list_users_by_username(username)
+                .await?
+                .body;
+            match users.first() {
+                Some(user) if !reviewer_ids.contains(&user.id) => reviewer_ids.push(user.id),
+                Some(_) => {}
+                None => warn!("GitLab user {username} not found"),
+            }
+        }
+
+        self.client
+            .merge_requests()
+            .update_merge_request_reviewers(&self.project_id, mr, reviewer_ids)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn sync_merge_request_approval_rule(
+        &self,
+        mr: u64,
+        name: &str,
+        approvals_required: u64,
+        usernames: Vec<String>,
+    ) -> ScmPlatformResult<()> {
+        let merge_requests = self.client.merge_requests();
+        let existing = merge_requests
+            .list_merge_request_approval_rules(&self.project_id, mr)
+            .await?
+            .body
+            .into_iter()
+            .find(|r| r.name == name);
+
+        let rule = MergeRequestApprovalRuleRequest {
+            name: name.to_string(),
+            approvals_required,
+            usernames,
+            ..Default::default()
+        };
+        match existing {
+            Some(existing) => {
+                merge_requests
+                    .update_merge_request_approval_rule(&self.project_id, mr, existing.id, &rule)
+                    .await?;
+            }
+            None => {
+                merge_requests
+                    .create_merge_request_approval_rule(&self.project_id, mr, &rule)
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn thread_from(discussion: MergeRequestDiscussion) -> ScmPlatformMergeRequestThread {
+    ScmPlatformMergeRequestThread {
+        resolvable: discussion.resolvable(),
+        resolved: discussion.resolved(),
+        id: discussion.id,
+        comments: discussion
+            .notes
+            .into_iter()
+            .map(|n| ScmPlatformMergeRequestComment {
+                id: n.id,
+                body: n.body,
+            })
+            .collect(),
+    }
+}
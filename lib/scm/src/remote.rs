@@ -0,0 +1,123 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::platforms::ScmPlatform;
+
+lazy_static! {
+    // supports scp-like (git@host:owner/repo.git), ssh:// and http(s):// remote URLs
+    static ref REMOTE_URL_RE: Regex = Regex::new(
+        r"^(?:[a-z+]+://)?(?:[^@/]+@)?(?<host>[^:/]+)(?::\d+)?[:/](?<path>.+?)(?:\.git)?/?$"
+    )
+    .unwrap();
+}
+
+/// Repository on an SCM platform derived from a remote URL
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScmRemote {
+    pub platform: ScmPlatform,
+
+    pub host: String,
+
+    /// Owner of the repository. For GitLab this includes any subgroups and for Azure DevOps this
+    /// is the organization and project
+    pub owner: String,
+
+    pub repo: String,
+}
+
+impl ScmRemote {
+    /// Parses a remote URL.
+    /// Returns `None` when the URL can't be parsed or the host isn't a known platform.
+    pub fn parse(url: &str) -> Option<Self> {
+        let captures = REMOTE_URL_RE.captures(url.trim())?;
+        let platform = Self::platform(captures.name("host")?.as_str())?;
+        Self::parse_for_platform(url, platform)
+    }
+
+    /// Parses a remote URL of a repository known to be hosted on the platform. Unlike [`parse`]
+    /// this supports self-hosted instances whose host doesn't identify the platform.
+    ///
+    /// [`parse`]: ScmRemote::parse
+    pub fn parse_for_platform(url: &str, platform: ScmPlatform) -> Option<Self> {
+        let captures = REMOTE_URL_RE.captures(url.trim())?;
+        let host = captures.name("host")?.as_str();
+
+        let mut path = captures.name("path")?.as_str();
+        if platform == ScmPlatform::Azure {
+            // https://dev.azure.com/org/project/_git/repo and git@ssh.dev.azure.com:v3/org/project/repo
+            path = path.trim_start_matches("v3/");
+        }
+
+        let (owner, repo) = path.rsplit_once('/')?;
+        let owner = owner.trim_end_matches("/_git");
+        if owner.is_empty() || repo.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            platform,
+            host: host.to_string(),
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+        })
+    }
+
+    /// Host of a remote, or API, URL
+    pub fn host_of(url: &str) -> Option<&str> {
+        REMOTE_URL_RE
+            .captures(url.trim())?
+            .name("host")
+            .map(|host| host.as_str())
+    }
+
+    fn platform(host: &str) -> Option<ScmPlatform> {
+        Some(if host.contains("github") {
+            ScmPlatform::GitHub
+        } else if host.contains("gitlab") {
+            ScmPlatform::GitLab
+        } else if host.contains("gitea") || host.contains("forgejo") || host == "codeberg.org" {
+            ScmPlatform::Gitea
+        } else if host.contains("bitbucket") {
+            ScmPlatform::BitBucket
+        } else if host.ends_with("dev.azure.com") || host.ends_with("visualstudio.com") {
+            ScmPlatform::Azure
+        } else {
+            return None;
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use crate::platforms::ScmPlatform;
+    use crate::remote::ScmRemote;
+
+    #[test_case("git@github.com:doctavious/doctavious.git" => Some((ScmPlatform::GitHub, "doctavious".to_string(), "doctavious".to_string())); "github scp")]
+    #[test_case("https://github.com/doctavious/doctavious" => Some((ScmPlatform::GitHub, "doctavious".to_string(), "doctavious".to_string())); "github https")]
+    #[test_case("ssh://git@gitlab.com:2222/group/sub/project.git" => Some((ScmPlatform::GitLab, "group/sub".to_string(), "project".to_string())); "gitlab subgroup")]
+    #[test_case("https://user@codeberg.org/owner/repo.git/" => Some((ScmPlatform::Gitea, "owner".to_string(), "repo".to_string())); "codeberg")]
+    #[test_case("https://code.forgejo.org/forgejo/runner" => Some((ScmPlatform::Gitea, "forgejo".to_string(), "runner".to_string())); "forgejo")]
+    #[test_case("git@bitbucket.org:workspace/repo.git" => Some((ScmPlatform::BitBucket, "workspace".to_string(), "repo".to_string())); "bitbucket")]
+    #[test_case("https://org@dev.azure.com/org/project/_git/repo" => Some((ScmPlatform::Azure, "org/project".to_string(), "repo".to_string())); "azure https")]
+    #[test_case("git@ssh.dev.azure.com:v3/org/project/repo" => Some((ScmPlatform::Azure, "org/project".to_string(), "repo".to_string())); "azure ssh")]
+    #[test_case("https://example.com/owner/repo.git" => None; "unknown host")]
+    #[test_case("/srv/git/repo" => None; "local path")]
+    #[test_case("file:///srv/git/repo" => None; "file url")]
+    fn parse(url: &str) -> Option<(ScmPlatform, String, String)> {
+        ScmRemote::parse(url).map(|r| (r.platform, r.owner, r.repo))
+    }
+
+    #[test]
+    fn parse_for_platform_should_support_self_hosted_instances() {
+        let remote =
+            ScmRemote::parse_for_platform("git@git.example.com:team/repo.git", ScmPlatform::GitLab)
+                .unwrap();
+
+        assert_eq!(ScmPlatform::GitLab, remote.platform);
+        assert_eq!("git.example.com", remote.host);
+        assert_eq!("team", remote.owner);
+        assert_eq!("repo", remote.repo);
+    }
+}
@@ -0,0 +1,10 @@
+use serde_derive::{Deserialize, Serialize};
+
+/// Repository settings that can't be detected from the repository itself
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ScmSettings {
+    /// Location of Subversion tags relative to the repository root e.g. `project/tags`.
+    /// Defaults to [`crate::drivers::svn::DEFAULT_TAGS_PATH`]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub svn_tags_path: Option<String>,
+}
@@ -33,9 +33,6 @@ pub(crate) fn date(value: Value, kwargs: Kwargs) -> Result<Value, Error> {
     // TODO: how to determine local vs UTC
     // TODO: timezone / locale
 
-    println!("are we entering custom filter????");
-    println!("{}", format!("value [{:?}]", &value));
-
     // let format = format.unwrap_or_else(|| "%Y-%m-%d");
     let format = kwargs
         .get::<Option<&str>>("format")?
@@ -160,14 +157,134 @@ pub(crate) fn date(value: Value, kwargs: Kwargs) -> Result<Value, Error> {
     Ok(Value::from(datetime.to_string()))
 }
 
+/// Uppercases the first character of the value leaving the rest untouched
+pub(crate) fn upper_first(value: &str) -> String {
+    let mut chars = value.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Splits the value by the given pattern, which can be passed positionally or as `pat`.
+/// Splits on whitespace when no pattern is provided.
+pub(crate) fn split(value: &str, pat: Option<&str>, kwargs: Kwargs) -> Result<Value, Error> {
+    let pat = match pat {
+        Some(pat) => Some(pat),
+        None => kwargs.get::<Option<&str>>("pat")?,
+    };
+    kwargs.assert_all_used()?;
+
+    Ok(match pat {
+        Some(pat) => Value::from_iter(value.split(pat).map(Value::from)),
+        None => Value::from_iter(value.split_whitespace().map(Value::from)),
+    })
+}
+
+/// Removes items that have the same value for the given attribute keeping the first occurrence
+pub(crate) fn unique_by(value: Value, attr: &str) -> Result<Value, Error> {
+    let mut seen = vec![];
+    let mut rv = vec![];
+    for item in value.try_iter()? {
+        let key = get_path(&item, attr)?;
+        if !seen.contains(&key) {
+            seen.push(key);
+            rv.push(item);
+        }
+    }
+
+    Ok(Value::from(rv))
+}
+
+/// Indents each line of the value.
+/// Accepts a `width` in spaces, positionally or as a keyword, or a `prefix` string along with
+/// `first` to also indent the first line and `blank` to also indent blank lines.
+pub(crate) fn indent(value: &str, width: Option<usize>, kwargs: Kwargs) -> Result<String, Error> {
+    let width = match width {
+        Some(width) => width,
+        None => kwargs.get::<Option<usize>>("width")?.unwrap_or(4),
+    };
+    let prefix = kwargs
+        .get::<Option<&str>>("prefix")?
+        .map(|p| p.to_string())
+        .unwrap_or_else(|| " ".repeat(width));
+    let first = kwargs.get::<Option<bool>>("first")?.unwrap_or(false);
+    let blank = kwargs.get::<Option<bool>>("blank")?.unwrap_or(false);
+    kwargs.assert_all_used()?;
+
+    let mut rv = String::with_capacity(value.len());
+    for (i, line) in value.split_inclusive('\n').enumerate() {
+        let is_blank = line.trim().is_empty();
+        if (i > 0 || first) && (blank || !is_blank) {
+            rv.push_str(&prefix);
+        }
+        rv.push_str(line);
+    }
+
+    Ok(rv)
+}
+
+/// Converts the value into a lowercase, dash separated, URL friendly string
+pub(crate) fn slugify(value: &str) -> String {
+    let mut slug = String::with_capacity(value.len());
+    for c in value.chars() {
+        if c.is_alphanumeric() {
+            slug.extend(c.to_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+
+    slug.trim_end_matches('-').to_string()
+}
+
+/// Bumps the semantic version by the given level (`major`, `minor` or `patch`) retaining any
+/// leading `v`. Pre-release and build metadata are dropped.
+pub(crate) fn semver_bump(value: &str, level: &str) -> Result<String, Error> {
+    let (prefix, version) = match value.strip_prefix('v') {
+        Some(version) => ("v", version),
+        None => ("", value),
+    };
+
+    let mut version = semver::Version::parse(version).map_err(|e| {
+        Error::new(
+            ErrorKind::InvalidOperation,
+            format!("Error parsing `{}` as a semantic version: {}", value, e),
+        )
+    })?;
+
+    match level {
+        "major" => {
+            version.major += 1;
+            version.minor = 0;
+            version.patch = 0;
+        }
+        "minor" => {
+            version.minor += 1;
+            version.patch = 0;
+        }
+        "patch" => version.patch += 1,
+        _ => {
+            return Err(Error::new(
+                ErrorKind::InvalidOperation,
+                format!(
+                    "Invalid bump level `{}`. Expected major, minor or patch",
+                    level
+                ),
+            ));
+        }
+    }
+    version.pre = semver::Prerelease::EMPTY;
+    version.build = semver::BuildMetadata::EMPTY;
+
+    Ok(format!("{prefix}{version}"))
+}
+
 // trim_start_matches(pat="v")
-// date(format="%Y-%m-%d")
-// upper_first -- same as https://docs.rs/minijinja/latest/minijinja/filters/fn.capitalize.html / title case
 
 //arrays
 // nth
 // sort
-// unique
 // map
 // concat
 
@@ -178,10 +295,8 @@ pub(crate) fn date(value: Value, kwargs: Kwargs) -> Result<Value, Error> {
 // wordcount
 // replace
 // linebreaksbr
-// indent
 // striptags
 // spaceless
-// split
 
 pub(crate) fn get_path(val: &Value, path: &str) -> Result<Value, Error> {
     let mut rv = val.clone();
@@ -194,3 +309,89 @@ pub(crate) fn get_path(val: &Value, path: &str) -> Result<Value, Error> {
     }
     Ok(rv)
 }
+
+#[cfg(test)]
+mod tests {
+    use minijinja::{Environment, context};
+    use test_case::test_case;
+
+    use crate::filters::{indent, semver_bump, slugify, split, unique_by, upper_first};
+
+    fn render(template: &str, ctx: minijinja::Value) -> String {
+        let mut env = Environment::new();
+        env.add_filter("indent", indent);
+        env.add_filter("split", split);
+        env.add_filter("unique_by", unique_by);
+        env.render_str(template, ctx).unwrap()
+    }
+
+    #[test_case("feature" => "Feature"; "lowercase")]
+    #[test_case("API changes" => "API changes"; "keeps remaining case")]
+    #[test_case("" => ""; "empty")]
+    fn upper_first_filter(value: &str) -> String {
+        upper_first(value)
+    }
+
+    #[test_case("Add Foo & Bar!" => "add-foo-bar"; "punctuation")]
+    #[test_case("  Breaking   Changes " => "breaking-changes"; "whitespace")]
+    fn slugify_filter(value: &str) -> String {
+        slugify(value)
+    }
+
+    #[test_case("1.2.3", "major" => "2.0.0"; "major")]
+    #[test_case("v1.2.3", "minor" => "v1.3.0"; "minor keeps prefix")]
+    #[test_case("1.2.3-alpha.1", "patch" => "1.2.4"; "patch drops pre-release")]
+    fn semver_bump_filter(value: &str, level: &str) -> String {
+        semver_bump(value, level).unwrap()
+    }
+
+    #[test]
+    fn semver_bump_should_reject_invalid_level() {
+        assert!(semver_bump("1.2.3", "huge").is_err());
+        assert!(semver_bump("one", "major").is_err());
+    }
+
+    #[test]
+    fn split_filter() {
+        let ctx = context! { message => "first\nsecond" };
+        assert_eq!(
+            "first",
+            render("{{ (message | split(pat=\"\\n\")) | first }}", ctx.clone())
+        );
+        assert_eq!(
+            "second",
+            render("{{ (message | split(\"\\n\")) | last }}", ctx)
+        );
+    }
+
+    #[test]
+    fn indent_filter() {
+        let ctx = context! { body => "a\n\nb" };
+        assert_eq!("a\n\n  b", render("{{ body | indent(2) }}", ctx.clone()));
+        assert_eq!(
+            "> a\n> \n> b",
+            render(
+                "{{ body | indent(prefix=\"> \", first=true, blank=true) }}",
+                ctx
+            )
+        );
+    }
+
+    #[test]
+    fn unique_by_filter() {
+        let ctx = context! {
+            authors => vec![
+                context! { name => "jane" },
+                context! { name => "john" },
+                context! { name => "jane" },
+            ]
+        };
+        assert_eq!(
+            "jane,john",
+            render(
+                "{{ authors | unique_by(\"name\") | map(attribute=\"name\") | join(\",\") }}",
+                ctx
+            )
+        );
+    }
+}
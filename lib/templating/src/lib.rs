@@ -8,7 +8,7 @@ use serde::Serialize;
 use serde_json::{Value, to_value};
 use thiserror::Error;
 
-use crate::filters::{date, groupby};
+use crate::filters::{date, groupby, indent, semver_bump, slugify, split, unique_by, upper_first};
 
 #[remain::sorted]
 #[derive(Debug, Error)]
@@ -116,13 +116,14 @@ pub struct Templates<'a> {
 impl<'a> Templates<'a> {
     /// Constructs a new instance.
     pub fn new() -> TemplatingResult<Self> {
-        let env = Environment::new();
-        // env.add_fi
+        let mut env = Environment::new();
+        add_filters(&mut env);
         Ok(Self { env })
     }
 
     pub fn new_with_templates(templates: HashMap<&'a str, String>) -> TemplatingResult<Self> {
         let mut env = Environment::new();
+        add_filters(&mut env);
         for (k, v) in templates {
             if let Err(e) = env.add_template_owned(k, v) {
                 return if let Some(error_source) = e.source() {
@@ -152,8 +153,7 @@ impl<'a> Templates<'a> {
         escape: bool,
     ) -> TemplatingResult<String> {
         let mut env = Environment::new();
-        env.add_filter("groupby", groupby);
-        env.add_filter("date", date);
+        add_filters(&mut env);
         if escape {
             env.set_auto_escape_callback(|_| AutoEscape::Html);
         }
@@ -161,3 +161,14 @@ impl<'a> Templates<'a> {
         Ok(env.render_str(template, &context.data)?)
     }
 }
+
+fn add_filters(env: &mut Environment) {
+    env.add_filter("date", date);
+    env.add_filter("groupby", groupby);
+    env.add_filter("indent", indent);
+    env.add_filter("semver_bump", semver_bump);
+    env.add_filter("slugify", slugify);
+    env.add_filter("split", split);
+    env.add_filter("unique_by", unique_by);
+    env.add_filter("upper_first", upper_first);
+}
@@ -0,0 +1,179 @@
+use std::fmt;
+use std::ops::Deref;
+use std::sync::Arc;
+
+pub use api_client_core::auth::AuthenticationConstraint;
+use api_client_core::auth::Authenticator;
+pub use api_client_core::client::{
+    ApiClient, ClientError, ClientResult, MediaType, Message, Response, RestClient,
+    RestClientBuilder,
+};
+pub use api_client_core::pagination::{KeysetPagination, Page, PaginationStrategy};
+pub(crate) use api_client_core::support;
+use reqwest::header::HeaderMap;
+use serde::Deserialize;
+use url::Url;
+
+use crate::pulls;
+
+const DEFAULT_HOST: &str = "https://dev.azure.com";
+const DEFAULT_CLIENT_AGENT: &str = concat!("doctavious-azure-devops/", env!("CARGO_PKG_VERSION"));
+
+/// Version of the REST API requests are made against
+pub(crate) const API_VERSION: &str = "7.1";
+
+/// Entrypoint for interacting with the API client.
+///
+/// Build one for an Azure DevOps Server instance with its URL as the host override,
+/// e.g., `https://devops.example.com/tfs`
+#[derive(Clone)]
+pub struct Client {
+    client: RestClient,
+}
+
+pub type ClientBuilder = RestClientBuilder<Client>;
+
+impl ApiClient for Client {
+    type Credentials = Credentials;
+    const DEFAULT_HOST: &'static str = DEFAULT_HOST;
+    const DEFAULT_AGENT: &'static str = DEFAULT_CLIENT_AGENT;
+
+    fn pagination() -> Arc<dyn PaginationStrategy> {
+        Arc::new(AzureDevOpsPagination::default())
+    }
+
+    fn error_message(body: &[u8]) -> String {
+        api_client_core::client::json_error_message(body)
+    }
+}
+
+impl From<RestClient> for Client {
+    fn from(client: RestClient) -> Self {
+        Self { client }
+    }
+}
+
+impl Deref for Client {
+    type Target = RestClient;
+
+    fn deref(&self) -> &RestClient {
+        &self.client
+    }
+}
+
+/// Various forms of authentication credentials supported by Azure DevOps.
+#[derive(PartialEq, Clone)]
+pub enum Credentials {
+    /// Personal access token, sent as the password of basic authentication
+    PersonalAccessToken(String),
+
+    /// OAuth access token, such as the `System.AccessToken` of pipelines
+    Bearer(String),
+}
+
+impl fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Credentials::PersonalAccessToken(value) => f
+                .debug_tuple("Credentials::PersonalAccessToken")
+                .field(&"*".repeat(value.len()))
+                .finish(),
+            Credentials::Bearer(value) => f
+                .debug_tuple("Credentials::Bearer")
+                .field(&"*".repeat(value.len()))
+                .finish(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Authenticator for Credentials {
+    async fn authenticate(
+        &self,
+        _client: &RestClient,
+        url: &mut Url,
+        _authentication: AuthenticationConstraint,
+    ) -> ClientResult<Option<String>> {
+        match self {
+            Credentials::PersonalAccessToken(token) => {
+                // reqwest sends the URL's user info as basic authentication. Setting it only
+                // fails for URLs without a host, which can't be requested anyway
+                let _ = url.set_password(Some(token));
+                Ok(None)
+            }
+            Credentials::Bearer(token) => Ok(Some(format!("Bearer {token}"))),
+        }
+    }
+}
+
+/// Passes the `x-ms-continuationtoken` of each page as the `continuationToken` of the next and
+/// unwraps the values of list responses, e.g., `{"count": 2, "value": [...]}`
+#[derive(Clone, Copy, Debug)]
+pub struct AzureDevOpsPagination {
+    continuation: KeysetPagination,
+}
+
+impl Default for AzureDevOpsPagination {
+    fn default() -> Self {
+        Self {
+            continuation: KeysetPagination::new("x-ms-continuationtoken", "continuationToken"),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct ListBody {
+    value: Vec<serde_json::Value>,
+}
+
+impl PaginationStrategy for AzureDevOpsPagination {
+    fn next_page(&self, url: &Url, headers: &HeaderMap, items: usize) -> Option<Url> {
+        self.continuation.next_page(url, headers, items)
+    }
+
+    fn page(&self, body: serde_json::Value) -> ClientResult<Page> {
+        let list: ListBody = serde_json::from_value(body)?;
+        Ok(Page {
+            items: list.value,
+            next: None,
+        })
+    }
+}
+
+impl Client {
+    pub fn new<A, C>(agent: A, credentials: C) -> ClientResult<Self>
+    where
+        A: Into<String>,
+        C: Into<Option<Credentials>>,
+    {
+        let mut builder = ClientBuilder::new()?.with_agent(&agent.into());
+        if let Some(credentials) = credentials.into() {
+            builder = builder.with_credentials(credentials);
+        }
+
+        builder.build()
+    }
+
+    pub fn pull_requests(&self) -> pulls::PullRequests {
+        pulls::PullRequests::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::client::{API_VERSION, support};
+
+    #[test]
+    fn should_encode_path_and_query() {
+        assert_eq!(
+            "/org/my%20project/_apis/git/repositories?api-version=7.1",
+            support::with_query(
+                format!(
+                    "/org/{}/_apis/git/repositories",
+                    support::encode_path_segment("my project")
+                ),
+                &[("api-version", API_VERSION.to_string())]
+            )
+        );
+    }
+}
@@ -0,0 +1,5 @@
+// Client for the Azure DevOps Services REST API, limited to Azure Repos.
+// Follows the design of the GitHub and Gitea clients.
+
+pub mod client;
+pub mod pulls;
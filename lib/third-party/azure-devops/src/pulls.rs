@@ -0,0 +1,362 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::client::{API_VERSION, Client, ClientResult, Message, Response, support};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IdentityRef {
+    pub id: String,
+    pub display_name: String,
+    /// Email, or domain and user name, of the identity
+    pub unique_name: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitRef {
+    pub commit_id: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PullRequest {
+    pub pull_request_id: u64,
+    pub title: String,
+    pub description: Option<String>,
+    // TODO: could make into an enum. active, abandoned or completed
+    pub status: String,
+    #[serde(default)]
+    pub is_draft: bool,
+    pub created_by: IdentityRef,
+    pub creation_date: DateTime<Utc>,
+    /// Full name of the source ref, e.g., `refs/heads/feature`
+    pub source_ref_name: String,
+    pub target_ref_name: String,
+    pub last_merge_source_commit: Option<CommitRef>,
+    pub last_merge_target_commit: Option<CommitRef>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Comment {
+    /// Identifies the comment within its thread, starting at 1
+    pub id: u64,
+    /// Comment replied to, 0 for the thread's first comment
+    #[serde(default)]
+    pub parent_comment_id: u64,
+    pub content: Option<String>,
+    pub author: Option<IdentityRef>,
+    /// `text` for comments by users and `system` for those recording pull request updates
+    pub comment_type: Option<String>,
+    #[serde(default)]
+    pub is_deleted: bool,
+    pub published_date: Option<DateTime<Utc>>,
+    pub last_updated_date: Option<DateTime<Utc>>,
+}
+
+/// Pull request comments are grouped into threads which start with a comment
+/// and may be tied to a file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Thread {
+    pub id: u64,
+    #[serde(default)]
+    pub comments: Vec<Comment>,
+    // TODO: could make into an enum. active, fixed, wontFix, closed, byDesign or pending
+    pub status: Option<String>,
+    #[serde(default)]
+    pub is_deleted: bool,
+    pub published_date: Option<DateTime<Utc>>,
+    pub last_updated_date: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ThreadRequest {
+    comments: Vec<CommentRequest>,
+    status: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CommentRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parent_comment_id: Option<u64>,
+    content: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    comment_type: Option<&'static str>,
+}
+
+pub struct PullRequests {
+    pub client: Client,
+}
+
+impl PullRequests {
+    pub fn new(client: Client) -> Self {
+        PullRequests { client }
+    }
+
+    /// `project` is prefixed by the organization, e.g., `org/project`, or for Azure DevOps Server
+    /// the collection, e.g., `tfs/DefaultCollection/project`
+    fn url(&self, project: &str, repository: &str, path: &str) -> String {
+        let project = project
+            .split('/')
+            .map(support::encode_path_segment)
+            .collect::<Vec<_>>()
+            .join("/");
+
+        self.client.url(
+            &support::with_query(
+                format!(
+                    "/{}/_apis/git/repositories/{}/pullRequests/{}",
+                    project,
+                    support::encode_path_segment(repository),
+                    path
+                ),
+                &[("api-version", API_VERSION.to_string())],
+            ),
+            None,
+        )
+    }
+
+    /// Gets a pull request.
+    ///
+    /// This function performs a `GET` to `/:project/_apis/git/repositories/:repository/pullRequests/:id`
+    /// From https://learn.microsoft.com/en-us/rest/api/azure/devops/git/pull-requests/get-pull-request
+    pub async fn get_pull_request(
+        &self,
+        project: &str,
+        repository: &str,
+        id: u64,
+    ) -> ClientResult<Response<PullRequest>> {
+        let url = self.url(project, repository, &id.to_string());
+        self.client.get(&url, Message::default()).await
+    }
+
+    /// Lists the comment threads of a pull request.
+    ///
+    /// This function performs a `GET` to `/:project/_apis/git/repositories/:repository/pullRequests/:id/threads`
+    /// From https://learn.microsoft.com/en-us/rest/api/azure/devops/git/pull-request-threads/list
+    pub async fn list_pull_request_threads(
+        &self,
+        project: &str,
+        repository: &str,
+        id: u64,
+    ) -> ClientResult<Response<Vec<Thread>>> {
+        let url = self.url(project, repository, &format!("{id}/threads"));
+        self.client.get_all_pages(&url, Message::default()).await
+    }
+
+    /// Creates an active thread, not tied to a file, starting with a comment.
+    ///
+    /// This function performs a `POST` to `/:project/_apis/git/repositories/:repository/pullRequests/:id/threads`
+    /// From https://learn.microsoft.com/en-us/rest/api/azure/devops/git/pull-request-threads/create
+    pub async fn create_pull_request_thread(
+        &self,
+        project: &str,
+        repository: &str,
+        id: u64,
+        body: String,
+    ) -> ClientResult<Response<Thread>> {
+        let url = self.url(project, repository, &format!("{id}/threads"));
+        let thread = ThreadRequest {
+            comments: vec![CommentRequest {
+                parent_comment_id: Some(0),
+                content: body,
+                comment_type: Some("text"),
+            }],
+            status: "active",
+        };
+
+        self.client.post(&url, Message::json(&thread)?).await
+    }
+
+    /// Updates a comment within a pull request thread.
+    ///
+    /// This function performs a `PATCH` to `/:project/_apis/git/repositories/:repository/pullRequests/:id/threads/:thread_id/comments/:comment_id`
+    /// From https://learn.microsoft.com/en-us/rest/api/azure/devops/git/pull-request-thread-comments/update
+    pub async fn update_pull_request_comment(
+        &self,
+        project: &str,
+        repository: &str,
+        id: u64,
+        thread_id: u64,
+        comment_id: u64,
+        body: String,
+    ) -> ClientResult<Response<Comment>> {
+        let url = self.url(
+            project,
+            repository,
+            &format!("{id}/threads/{thread_id}/comments/{comment_id}"),
+        );
+        let comment = CommentRequest {
+            parent_comment_id: None,
+            content: body,
+            comment_type: None,
+        };
+
+        self.client.patch(&url, Message::json(&comment)?).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use wiremock::matchers::{body_json, header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use crate::client::{Client, ClientBuilder, Credentials};
+
+    const THREADS: &str = "/org/project/_apis/git/repositories/repo/pullRequests/7/threads";
+
+    fn comment(id: u64, content: &str, comment_type: &str) -> serde_json::Value {
+        json!({
+            "id": id,
+            "parentCommentId": 0,
+            "author": {"id": "d6245f20", "displayName": "Doctavious", "uniqueName": "bot@doctavious.com"},
+            "content": content,
+            "publishedDate": "2025-01-02T03:04:05.123Z",
+            "lastUpdatedDate": "2025-01-02T03:04:05.123Z",
+            "commentType": comment_type
+        })
+    }
+
+    fn client(server: &MockServer) -> Client {
+        ClientBuilder::new()
+            .unwrap()
+            .with_host_override(&server.uri())
+            .with_credentials(Credentials::PersonalAccessToken("pat".to_string()))
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn should_list_threads() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(THREADS))
+            .and(query_param("api-version", "7.1"))
+            // base64 of ":pat"
+            .and(header("Authorization", "Basic OnBhdA=="))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "count": 2,
+                "value": [
+                    {
+                        "id": 1,
+                        "comments": [comment(1, "Policy check passed", "system")],
+                        "status": null,
+                        "isDeleted": false
+                    },
+                    {
+                        "id": 2,
+                        "comments": [comment(1, "hello", "text"), comment(2, "reply", "text")],
+                        "status": "active",
+                        "isDeleted": false,
+                        "publishedDate": "2025-01-02T03:04:05.123Z"
+                    }
+                ]
+            })))
+            .mount(&server)
+            .await;
+
+        let threads = client(&server)
+            .pull_requests()
+            .list_pull_request_threads("org/project", "repo", 7)
+            .await
+            .unwrap()
+            .body;
+
+        assert_eq!(2, threads.len());
+        assert_eq!(
+            Some("system"),
+            threads[0].comments[0].comment_type.as_deref()
+        );
+        assert_eq!(Some("hello"), threads[1].comments[0].content.as_deref());
+        assert_eq!(2, threads[1].comments.len());
+    }
+
+    #[tokio::test]
+    async fn should_list_threads_of_every_page() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(THREADS))
+            .and(query_param("continuationToken", "next"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "count": 1,
+                "value": [{"id": 2, "comments": [comment(1, "second", "text")]}]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(THREADS))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("x-ms-continuationtoken", "next")
+                    .set_body_json(json!({
+                        "count": 1,
+                        "value": [{"id": 1, "comments": [comment(1, "first", "text")]}]
+                    })),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let threads = client(&server)
+            .pull_requests()
+            .list_pull_request_threads("org/project", "repo", 7)
+            .await
+            .unwrap()
+            .body;
+
+        assert_eq!(vec![1, 2], threads.iter().map(|t| t.id).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn should_create_threads_and_update_comments() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path(THREADS))
+            .and(body_json(json!({
+                "comments": [{"parentCommentId": 0, "content": "hello", "commentType": "text"}],
+                "status": "active"
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 3,
+                "comments": [comment(1, "hello", "text")],
+                "status": "active"
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("PATCH"))
+            .and(path(format!("{THREADS}/3/comments/1")))
+            .and(body_json(json!({"content": "updated"})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(comment(1, "updated", "text")))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let pull_requests = client(&server).pull_requests();
+        let thread = pull_requests
+            .create_pull_request_thread("org/project", "repo", 7, "hello".to_string())
+            .await
+            .unwrap()
+            .body;
+        let comment = pull_requests
+            .update_pull_request_comment(
+                "org/project",
+                "repo",
+                7,
+                thread.id,
+                thread.comments[0].id,
+                "updated".to_string(),
+            )
+            .await
+            .unwrap()
+            .body;
+
+        assert_eq!(Some("updated"), comment.content.as_deref());
+    }
+}
@@ -0,0 +1,223 @@
+use std::fmt;
+use std::ops::Deref;
+use std::sync::Arc;
+
+pub use api_client_core::auth::AuthenticationConstraint;
+use api_client_core::auth::Authenticator;
+pub use api_client_core::client::{
+    ApiClient, ClientError, ClientResult, MediaType, Message, Response, RestClient,
+    RestClientBuilder,
+};
+pub use api_client_core::pagination::{OffsetBasedPagination, Page, PaginationStrategy};
+pub(crate) use api_client_core::support;
+use reqwest::header::HeaderMap;
+use serde::Deserialize;
+use url::Url;
+
+use crate::pullrequests;
+
+const DEFAULT_HOST: &str = "https://api.bitbucket.org/2.0";
+const DEFAULT_CLIENT_AGENT: &str = concat!("doctavious-bitbucket/", env!("CARGO_PKG_VERSION"));
+
+/// Entrypoint for interacting with the API client.
+#[derive(Clone)]
+pub struct Client {
+    client: RestClient,
+}
+
+pub type ClientBuilder = RestClientBuilder<Client>;
+
+impl ApiClient for Client {
+    type Credentials = Credentials;
+    const DEFAULT_HOST: &'static str = DEFAULT_HOST;
+    const DEFAULT_AGENT: &'static str = DEFAULT_CLIENT_AGENT;
+
+    fn pagination() -> Arc<dyn PaginationStrategy> {
+        Arc::new(BitbucketPagination)
+    }
+
+    fn error_message(body: &[u8]) -> String {
+        // errors nest their message, e.g., `{"type": "error", "error": {"message": "..."}}`
+        serde_json::from_slice::<serde_json::Value>(body)
+            .ok()
+            .and_then(|error| error["error"]["message"].as_str().map(str::to_string))
+            .unwrap_or_else(|| api_client_core::client::json_error_message(body))
+    }
+}
+
+impl From<RestClient> for Client {
+    fn from(client: RestClient) -> Self {
+        Self { client }
+    }
+}
+
+impl Deref for Client {
+    type Target = RestClient;
+
+    fn deref(&self) -> &RestClient {
+        &self.client
+    }
+}
+
+/// Various forms of authentication credentials supported by Bitbucket Cloud.
+#[derive(PartialEq, Clone)]
+pub enum Credentials {
+    /// Repository, project or workspace access token, or an OAuth access token
+    Token(String),
+
+    /// Username and app password
+    Basic { username: String, password: String },
+}
+
+impl fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Credentials::Token(value) => f
+                .debug_tuple("Credentials::Token")
+                .field(&"*".repeat(value.len()))
+                .finish(),
+            Credentials::Basic { username, password } => f
+                .debug_struct("Credentials::Basic")
+                .field("username", username)
+                .field("password", &"*".repeat(password.len()))
+                .finish(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Authenticator for Credentials {
+    async fn authenticate(
+        &self,
+        _client: &RestClient,
+        url: &mut Url,
+        _authentication: AuthenticationConstraint,
+    ) -> ClientResult<Option<String>> {
+        match self {
+            Credentials::Token(token) => Ok(Some(format!("Bearer {token}"))),
+            Credentials::Basic { username, password } => {
+                // reqwest sends the URL's user info as basic authentication. Setting it only
+                // fails for URLs without a host, which can't be requested anyway
+                let _ = url.set_username(username);
+                let _ = url.set_password(Some(password));
+                Ok(None)
+            }
+        }
+    }
+}
+
+/// Follows the `next` link Bitbucket returns within the body of each page, alongside its values,
+/// rather than a `Link` header.
+/// https://developer.atlassian.com/cloud/bitbucket/rest/intro/#pagination
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BitbucketPagination;
+
+#[derive(Deserialize)]
+struct PageBody {
+    values: Vec<serde_json::Value>,
+    /// Link to the next page, absent on the last page
+    next: Option<String>,
+}
+
+impl PaginationStrategy for BitbucketPagination {
+    fn next_page(&self, _url: &Url, _headers: &HeaderMap, _items: usize) -> Option<Url> {
+        None
+    }
+
+    fn page(&self, body: serde_json::Value) -> ClientResult<Page> {
+        let page: PageBody = serde_json::from_value(body)?;
+        Ok(Page {
+            items: page.values,
+            next: page.next.as_deref().map(Url::parse).transpose()?,
+        })
+    }
+}
+
+/// Query of a page. Bitbucket names the page size `pagelen`, defaulting to 10 and capping it at
+/// 100 for most endpoints
+fn pagination_query(pagination: &OffsetBasedPagination) -> Vec<(&'static str, String)> {
+    let mut query = Vec::new();
+    if let Some(page) = pagination.page {
+        query.push(("page", page.to_string()));
+    }
+
+    if let Some(pagelen) = pagination.per_page {
+        query.push(("pagelen", pagelen.to_string()));
+    }
+
+    query
+}
+
+impl Client {
+    pub fn new<A, C>(agent: A, credentials: C) -> ClientResult<Self>
+    where
+        A: Into<String>,
+        C: Into<Option<Credentials>>,
+    {
+        let mut builder = ClientBuilder::new()?.with_agent(&agent.into());
+        if let Some(credentials) = credentials.into() {
+            builder = builder.with_credentials(credentials);
+        }
+
+        builder.build()
+    }
+
+    /// Gets a single page when paginated otherwise every page
+    pub(crate) async fn get_list<D>(
+        &self,
+        path: String,
+        query: Vec<(&str, String)>,
+        pagination: Option<OffsetBasedPagination>,
+    ) -> ClientResult<Response<Vec<D>>>
+    where
+        D: serde::de::DeserializeOwned + 'static + Send,
+    {
+        match pagination {
+            Some(pagination) => {
+                let query = [query, pagination_query(&pagination)].concat();
+                let url = self.url(&support::with_query(path, &query), None);
+                self.get_page(&url).await
+            }
+            None => {
+                let url = self.url(&support::with_query(path, &query), None);
+                self.get_all_pages(&url, Message::default()).await
+            }
+        }
+    }
+
+    pub fn pull_requests(&self) -> pullrequests::PullRequests {
+        pullrequests::PullRequests::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::client::{ApiClient, Client, OffsetBasedPagination, pagination_query, support};
+
+    #[test]
+    fn should_name_page_size_pagelen() {
+        let pagination = OffsetBasedPagination {
+            per_page: Some(50),
+            page: Some(2),
+        };
+        assert_eq!(
+            "/repositories/w/r/pullrequests?page=2&pagelen=50",
+            support::with_query(
+                "/repositories/w/r/pullrequests".to_string(),
+                &pagination_query(&pagination)
+            )
+        );
+        assert_eq!("%7Buuid%7D", support::encode_path_segment("{uuid}"));
+    }
+
+    #[test]
+    fn should_map_nested_error_messages() {
+        assert_eq!(
+            "Resource not found",
+            Client::error_message(
+                br#"{"type": "error", "error": {"message": "Resource not found"}}"#
+            )
+        );
+        assert_eq!("Bad Gateway", Client::error_message(b"Bad Gateway"));
+    }
+}
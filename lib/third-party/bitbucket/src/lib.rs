@@ -0,0 +1,5 @@
+// Client for the Bitbucket Cloud REST API, 2.0.
+// Follows the design of the GitHub and Gitea clients. Bitbucket Data Center has a separate API.
+
+pub mod client;
+pub mod pullrequests;
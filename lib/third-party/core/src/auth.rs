@@ -0,0 +1,148 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use jsonwebtoken as jwt;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::client::{ClientResult, RestClient};
+
+// We use 9 minutes for the life to give some buffer for clock drift between
+// our clock and GitHub's. The absolute max is 10 minutes.
+const MAX_JWT_TOKEN_LIFE: Duration = Duration::from_secs(60 * 9);
+// 8 minutes so we refresh sooner than it actually expires
+const JWT_TOKEN_REFRESH_PERIOD: Duration = Duration::from_secs(60 * 8);
+
+/// Controls what sort of authentication is required for this request.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AuthenticationConstraint {
+    /// No constraint
+    Unconstrained,
+    /// Must be JWT
+    JWT,
+}
+
+/// Credentials of a platform, authenticating each request the client sends
+#[async_trait::async_trait]
+pub trait Authenticator: Send + Sync {
+    /// Authenticates a request to `url`, either by adding to its query or returning the value of
+    /// its `Authorization` header. `client` sends any requests needed to authenticate, such as
+    /// those exchanging a token for another.
+    async fn authenticate(
+        &self,
+        client: &RestClient,
+        url: &mut Url,
+        authentication: AuthenticationConstraint,
+    ) -> ClientResult<Option<String>>;
+}
+
+/// JSON Web Token authentication mechanism.
+///
+/// The client methods are all &self, but the dynamically
+/// generated JWT token changes regularly. The token is also a bit
+/// expensive to regenerate, so we do want to have a mutable cache.
+///
+/// We use a token inside a Mutex so we can have interior mutability
+/// even though JWTCredentials is not mutable.
+#[derive(Clone)]
+pub struct JWTCredentials {
+    pub app_id: i64,
+    /// PEM RSA key, as downloaded from the app's settings, or DER RSA key. Generate the latter
+    /// with `openssl rsa -in private_rsa_key.pem -outform DER -out private_rsa_key.der`
+    pub private_key: Vec<u8>,
+    cache: Arc<Mutex<ExpiringJWTCredential>>,
+}
+
+impl JWTCredentials {
+    pub fn new(app_id: i64, private_key: Vec<u8>) -> ClientResult<JWTCredentials> {
+        let creds = ExpiringJWTCredential::calculate(app_id, &private_key)?;
+
+        Ok(JWTCredentials {
+            app_id,
+            private_key,
+            cache: Arc::new(Mutex::new(creds)),
+        })
+    }
+
+    /// Fetch a valid JWT token, regenerating it if necessary
+    pub fn token(&self) -> String {
+        let mut expiring = self.cache.lock().unwrap();
+        if expiring.is_stale() {
+            *expiring = ExpiringJWTCredential::calculate(self.app_id, &self.private_key)
+                .expect("JWT private key worked before, it should work now...");
+        }
+
+        expiring.token.clone()
+    }
+}
+
+impl PartialEq for JWTCredentials {
+    fn eq(&self, other: &JWTCredentials) -> bool {
+        self.app_id == other.app_id && self.private_key == other.private_key
+    }
+}
+
+#[derive(Debug)]
+struct ExpiringJWTCredential {
+    token: String,
+    created_at: std::time::Instant,
+}
+
+#[derive(Serialize)]
+struct JWTCredentialClaim {
+    iat: u64,
+    exp: u64,
+    iss: i64,
+}
+
+impl ExpiringJWTCredential {
+    fn calculate(app_id: i64, private_key: &[u8]) -> ClientResult<ExpiringJWTCredential> {
+        // SystemTime can go backwards, Instant can't, so always use
+        // Instant for ensuring regular cycling.
+        let created_at = std::time::Instant::now();
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap();
+        let expires = now + MAX_JWT_TOKEN_LIFE;
+
+        let payload = JWTCredentialClaim {
+            // specifying this as 60 seconds in the past to avoid problems with clock drift.
+            iat: now.as_secs().saturating_sub(60),
+            exp: expires.as_secs(),
+            iss: app_id,
+        };
+        let key = if private_key.starts_with(b"-----BEGIN") {
+            jwt::EncodingKey::from_rsa_pem(private_key)?
+        } else {
+            jwt::EncodingKey::from_rsa_der(private_key)
+        };
+        let header = jwt::Header::new(jwt::Algorithm::RS256);
+        let jwt = jwt::encode(&header, &payload, &key)?;
+
+        Ok(ExpiringJWTCredential {
+            created_at,
+            token: jwt,
+        })
+    }
+
+    fn is_stale(&self) -> bool {
+        self.created_at.elapsed() >= JWT_TOKEN_REFRESH_PERIOD
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AccessToken {
+    pub token_type: String,
+
+    pub access_token: String,
+
+    #[serde(default)]
+    pub expires_in: i64,
+
+    pub refresh_token: String,
+
+    #[serde(default, alias = "x_refresh_token_expires_in")]
+    pub refresh_token_expires_in: i64,
+
+    pub scope: String,
+}
@@ -0,0 +1,706 @@
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use reqwest::StatusCode;
+use reqwest::header::HeaderMap;
+use reqwest_middleware::Middleware;
+use reqwest_retry::RetryTransientMiddleware;
+use reqwest_tracing::TracingMiddleware;
+use serde::Serialize;
+use thiserror::Error;
+use tracing::debug;
+use url::Url;
+
+use crate::auth::{AuthenticationConstraint, Authenticator};
+use crate::pagination::{LinkHeaderPagination, PaginationStrategy};
+use crate::rate_limit::RateLimitMiddleware;
+
+#[derive(Debug)]
+pub struct Response<T> {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: T,
+}
+
+impl<T> Response<T> {
+    pub fn new(status: StatusCode, headers: HeaderMap, body: T) -> Self {
+        Self {
+            status,
+            headers,
+            body,
+        }
+    }
+}
+
+/// Errors returned by the client.
+/// Large sources are boxed to keep results of the client small.
+#[derive(Debug, Error)]
+pub enum ClientError {
+    /// Generic HTTP Error
+    #[error("HTTP Error. Code: {status}, message: {error}")]
+    HttpError {
+        status: StatusCode,
+        headers: Box<HeaderMap>,
+        error: String,
+    },
+
+    #[error(transparent)]
+    JsonWebTokenError(#[from] jsonwebtoken::errors::Error),
+
+    /// Errors returned by reqwest
+    #[error(transparent)]
+    ReqwestError(#[from] reqwest::Error),
+
+    /// Errors returned by reqwest middleware
+    #[error(transparent)]
+    ReqwestMiddleWareError(Box<reqwest_middleware::Error>),
+
+    /// Serde JSON parsing error
+    #[error(transparent)]
+    SerdeJsonError(#[from] serde_json::Error),
+
+    /// Query serialization error
+    #[error(transparent)]
+    SerdeUrlEncodedError(#[from] serde_urlencoded::ser::Error),
+
+    /// URL Parsing Error
+    #[error(transparent)]
+    UrlParserError(#[from] url::ParseError),
+}
+
+impl From<reqwest_middleware::Error> for ClientError {
+    fn from(error: reqwest_middleware::Error) -> Self {
+        ClientError::ReqwestMiddleWareError(Box::new(error))
+    }
+}
+
+impl ClientError {
+    /// Whether the error is a `404 Not Found` response
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, ClientError::HttpError { status, .. } if *status == StatusCode::NOT_FOUND)
+    }
+}
+
+pub type ClientResult<T> = Result<T, ClientError>;
+
+#[derive(Debug, Default)]
+pub struct Message {
+    pub body: Option<reqwest::Body>,
+    pub content_type: Option<String>,
+}
+
+impl Message {
+    pub fn json<T: Serialize>(body: &T) -> ClientResult<Self> {
+        Ok(Self {
+            body: Some(reqwest::Body::from(serde_json::to_vec(body)?)),
+            content_type: Some("application/json".to_string()),
+        })
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+pub enum MediaType {
+    /// Return json (the default)
+    #[default]
+    Json,
+}
+
+impl fmt::Display for MediaType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MediaType::Json => write!(f, "application/json"),
+        }
+    }
+}
+
+impl From<MediaType> for mime::Mime {
+    fn from(media: MediaType) -> mime::Mime {
+        match media {
+            MediaType::Json => "application/json".parse().unwrap(),
+        }
+    }
+}
+
+/// Platform specific parts of an API client built on [`RestClient`].
+///
+/// Platform crates wrap a [`RestClient`], adding their endpoints, and are built with a
+/// [`RestClientBuilder`] of their client.
+pub trait ApiClient: From<RestClient> {
+    /// Credentials requests are authenticated with
+    type Credentials: Authenticator + 'static;
+
+    /// API URL of the platform's public instance
+    const DEFAULT_HOST: &'static str;
+
+    /// User agent sent with requests unless overridden
+    const DEFAULT_AGENT: &'static str;
+
+    /// How list endpoints are paged through. Defaults to following `Link` headers
+    fn pagination() -> Arc<dyn PaginationStrategy> {
+        Arc::new(LinkHeaderPagination)
+    }
+
+    /// Message of an error response's body. Defaults to the body
+    fn error_message(body: &[u8]) -> String {
+        String::from_utf8_lossy(body).to_string()
+    }
+}
+
+/// Message of JSON error bodies with a `message` field, such as GitHub's and GitLab's, falling
+/// back to the body
+pub fn json_error_message(body: &[u8]) -> String {
+    serde_json::from_slice::<serde_json::Value>(body)
+        .ok()
+        .and_then(|error| {
+            ["message", "error"]
+                .iter()
+                .find_map(|field| match &error[field] {
+                    serde_json::Value::Null => None,
+                    serde_json::Value::String(message) => Some(message.clone()),
+                    message => Some(message.to_string()),
+                })
+        })
+        .unwrap_or_else(|| String::from_utf8_lossy(body).to_string())
+}
+
+/// HTTP client shared by the platform API clients which handles authentication, pagination and
+/// mapping error responses
+#[derive(Clone)]
+pub struct RestClient {
+    host: String,
+    agent: String,
+    client: reqwest_middleware::ClientWithMiddleware,
+    credentials: Option<Arc<dyn Authenticator>>,
+    pagination: Arc<dyn PaginationStrategy>,
+    error_message: fn(&[u8]) -> String,
+    // TODO: http_cache
+}
+
+impl RestClient {
+    pub fn url(&self, path: &str, host: Option<&str>) -> String {
+        format!("{}{}", host.unwrap_or(self.host.as_str()), path)
+    }
+
+    // TODO (sean): As of 2025-05-20 it doesn't appear that Gitlab includes etag/checksum to support
+    // HTTP cache / conditional gets. When/if it does, include logic to support
+    pub async fn request<Out>(
+        &self,
+        method: http::Method,
+        uri: &str,
+        message: Message,
+        media_type: MediaType,
+        authentication: AuthenticationConstraint,
+    ) -> ClientResult<Response<Out>>
+    where
+        Out: serde::de::DeserializeOwned + 'static + Send,
+    {
+        let req = self
+            .make_request(method, uri, message, media_type, authentication)
+            .await?;
+
+        let response = req.send().await?;
+
+        let status = response.status();
+        let headers = response.headers().clone();
+        let response_body = response.bytes().await?;
+
+        if status.is_success() {
+            debug!("Received successful response. Read payload.");
+
+            let parsed_response = if status == StatusCode::NO_CONTENT
+                || std::any::TypeId::of::<Out>() == std::any::TypeId::of::<()>()
+            {
+                serde_json::from_str("null")?
+            } else {
+                serde_json::from_slice::<Out>(&response_body)?
+            };
+            Ok(Response::new(status, headers, parsed_response))
+        } else if status.is_redirection() {
+            match status {
+                StatusCode::NOT_MODIFIED => {
+                    unreachable!("this should not be reachable without the an HTTP cache")
+                }
+                _ => {
+                    let body = if std::any::TypeId::of::<Out>() == std::any::TypeId::of::<()>() {
+                        serde_json::from_str("null")?
+                    } else {
+                        serde_json::from_slice::<Out>(&response_body)?
+                    };
+
+                    Ok(Response::new(status, headers, body))
+                }
+            }
+        } else {
+            let error_msg = if response_body.is_empty() {
+                "empty response".to_string()
+            } else {
+                (self.error_message)(&response_body)
+            };
+
+            Err(ClientError::HttpError {
+                status,
+                headers: Box::new(headers),
+                error: error_msg,
+            })
+        }
+    }
+
+    pub async fn get<D>(&self, uri: &str, message: Message) -> ClientResult<Response<D>>
+    where
+        D: serde::de::DeserializeOwned + 'static + Send,
+    {
+        self.request(
+            http::Method::GET,
+            uri,
+            message,
+            MediaType::Json,
+            AuthenticationConstraint::Unconstrained,
+        )
+        .await
+    }
+
+    pub async fn get_authenticated<D>(
+        &self,
+        uri: &str,
+        authentication: AuthenticationConstraint,
+    ) -> ClientResult<Response<D>>
+    where
+        D: serde::de::DeserializeOwned + 'static + Send,
+    {
+        self.request(
+            http::Method::GET,
+            uri,
+            Message::default(),
+            MediaType::Json,
+            authentication,
+        )
+        .await
+    }
+
+    /// Gets every page of a list endpoint, following the client's pagination strategy from the
+    /// first page at `uri`
+    pub async fn get_all_pages<D>(
+        &self,
+        uri: &str,
+        _message: Message,
+    ) -> ClientResult<Response<Vec<D>>>
+    where
+        D: serde::de::DeserializeOwned + 'static + Send,
+    {
+        let mut url = Url::parse(uri)?;
+        let mut items = Vec::new();
+        loop {
+            let (mut response, next) = self.get_page_with_next::<D>(&url).await?;
+            items.append(&mut response.body);
+
+            match next {
+                Some(next) => url = next,
+                None => return Ok(Response::new(response.status, response.headers, items)),
+            }
+        }
+    }
+
+    /// Gets the items of a single page of a list endpoint, for APIs whose pages wrap their items
+    pub async fn get_page<D>(&self, uri: &str) -> ClientResult<Response<Vec<D>>>
+    where
+        D: serde::de::DeserializeOwned + 'static + Send,
+    {
+        let (response, _) = self.get_page_with_next(&Url::parse(uri)?).await?;
+        Ok(response)
+    }
+
+    async fn get_page_with_next<D>(
+        &self,
+        url: &Url,
+    ) -> ClientResult<(Response<Vec<D>>, Option<Url>)>
+    where
+        D: serde::de::DeserializeOwned + 'static + Send,
+    {
+        let response: Response<serde_json::Value> =
+            self.get(url.as_str(), Message::default()).await?;
+        let page = self.pagination.page(response.body)?;
+        let next = page
+            .next
+            .or_else(|| {
+                self.pagination
+                    .next_page(url, &response.headers, page.items.len())
+            })
+            .filter(|next| next != url);
+
+        let items = page
+            .items
+            .into_iter()
+            .map(serde_json::from_value)
+            .collect::<Result<Vec<D>, _>>()?;
+
+        Ok((
+            Response::new(response.status, response.headers, items),
+            next,
+        ))
+    }
+
+    // TODO: change to build_request as 'make_request' sounds like the action of actually executing
+    // the call but its really creating the request builder
+    async fn make_request(
+        &self,
+        method: http::Method,
+        uri: &str,
+        message: Message,
+        media_type: MediaType,
+        authentication: AuthenticationConstraint,
+    ) -> ClientResult<reqwest_middleware::RequestBuilder> {
+        let mut url = uri.parse::<reqwest::Url>()?;
+        let auth = match &self.credentials {
+            Some(credentials) => {
+                credentials
+                    .authenticate(self, &mut url, authentication)
+                    .await?
+            }
+            None => None,
+        };
+
+        let mut req = self.client.request(method, url);
+
+        if let Some(content_type) = &message.content_type {
+            req = req.header(http::header::CONTENT_TYPE, content_type.clone());
+        }
+
+        req = req.header(http::header::USER_AGENT, &*self.agent);
+        req = req.header(http::header::ACCEPT, &media_type.to_string());
+
+        if let Some(auth_str) = auth {
+            req = req.header(http::header::AUTHORIZATION, &*auth_str);
+        }
+
+        if let Some(body) = message.body {
+            req = req.body(body);
+        }
+
+        Ok(req)
+    }
+
+    pub async fn post<D>(&self, uri: &str, message: Message) -> ClientResult<Response<D>>
+    where
+        D: serde::de::DeserializeOwned + 'static + Send,
+    {
+        self.post_media(
+            uri,
+            message,
+            MediaType::Json,
+            AuthenticationConstraint::Unconstrained,
+        )
+        .await
+    }
+
+    pub async fn post_media<D>(
+        &self,
+        uri: &str,
+        message: Message,
+        media: MediaType,
+        authentication: AuthenticationConstraint,
+    ) -> ClientResult<Response<D>>
+    where
+        D: serde::de::DeserializeOwned + 'static + Send,
+    {
+        self.request(http::Method::POST, uri, message, media, authentication)
+            .await
+    }
+
+    pub async fn patch<D>(&self, uri: &str, message: Message) -> ClientResult<Response<D>>
+    where
+        D: serde::de::DeserializeOwned + 'static + Send,
+    {
+        self.request(
+            http::Method::PATCH,
+            uri,
+            message,
+            MediaType::Json,
+            AuthenticationConstraint::Unconstrained,
+        )
+        .await
+    }
+
+    pub async fn put<D>(&self, uri: &str, message: Message) -> ClientResult<Response<D>>
+    where
+        D: serde::de::DeserializeOwned + 'static + Send,
+    {
+        self.request(
+            http::Method::PUT,
+            uri,
+            message,
+            MediaType::Json,
+            AuthenticationConstraint::Unconstrained,
+        )
+        .await
+    }
+
+    pub async fn delete<D>(&self, uri: &str, message: Message) -> ClientResult<Response<D>>
+    where
+        D: serde::de::DeserializeOwned + 'static + Send,
+    {
+        self.request(
+            http::Method::DELETE,
+            uri,
+            message,
+            MediaType::Json,
+            AuthenticationConstraint::Unconstrained,
+        )
+        .await
+    }
+}
+
+/// Builds a platform's API client, `C`, with its defaults
+pub struct RestClientBuilder<C> {
+    host: String,
+    agent: String,
+    // TODO: if we want to support more convenience fns we could make this a ClientBuilder
+    http: reqwest::Client,
+    middleware: Vec<Arc<dyn Middleware>>,
+    credentials: Option<Arc<dyn Authenticator>>,
+    pagination: Arc<dyn PaginationStrategy>,
+    client: PhantomData<fn() -> C>,
+}
+
+impl<C: ApiClient> RestClientBuilder<C> {
+    pub fn new() -> ClientResult<Self> {
+        let http = reqwest::Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .build()?;
+
+        Ok(Self {
+            host: C::DEFAULT_HOST.to_string(),
+            agent: C::DEFAULT_AGENT.to_string(),
+            http,
+            middleware: Vec::new(),
+            credentials: None,
+            pagination: C::pagination(),
+            client: PhantomData,
+        })
+    }
+
+    pub fn with_agent(mut self, agent: &str) -> Self {
+        self.agent = agent.to_string();
+        self
+    }
+
+    pub fn with_host_override(mut self, host_override: &str) -> Self {
+        self.host = host_override.to_string();
+        self
+    }
+
+    pub fn with_http(mut self, http: reqwest::Client) -> Self {
+        self.http = http;
+        self
+    }
+
+    pub fn add_middleware<M>(self, middleware: M) -> Self
+    where
+        M: Middleware,
+    {
+        self.add_middleware_arc(Arc::new(middleware))
+    }
+
+    fn add_middleware_arc(mut self, middleware: Arc<dyn Middleware>) -> Self {
+        self.middleware.push(middleware);
+        self
+    }
+
+    /// Convenience method to attach tracing middleware
+    pub fn with_tracing(self) -> Self {
+        self.add_middleware(TracingMiddleware::default())
+    }
+
+    /// Convenience method to attach rate limit middleware which pauses and retries requests
+    /// around the API's rate limits.
+    ///
+    /// Prefer it over [`Self::with_retry`] for rate limited APIs, as the retry middleware retries
+    /// rate limited requests regardless of when their limit resets
+    pub fn with_rate_limit(self) -> Self {
+        self.add_middleware(RateLimitMiddleware::default())
+    }
+
+    /// Convenience method to attach retry middleware
+    pub fn with_retry(self) -> Self {
+        // TODO: conditional middleware? https://github.com/oxidecomputer/reqwest-conditional-middleware/tree/main
+        let retry_policy =
+            reqwest_retry::policies::ExponentialBackoff::builder().build_with_max_retries(3);
+        self.add_middleware(RetryTransientMiddleware::new_with_policy(retry_policy))
+    }
+
+    pub fn with_credentials(mut self, credentials: C::Credentials) -> Self {
+        self.credentials = Some(Arc::new(credentials));
+        self
+    }
+
+    /// Overrides how list endpoints are paged through
+    pub fn with_pagination<P>(mut self, pagination: P) -> Self
+    where
+        P: PaginationStrategy + 'static,
+    {
+        self.pagination = Arc::new(pagination);
+        self
+    }
+
+    pub fn build(self) -> ClientResult<C> {
+        let mut builder = reqwest_middleware::ClientBuilder::new(self.http);
+        for middleware in self.middleware {
+            builder = builder.with_arc(middleware)
+        }
+
+        Ok(C::from(RestClient {
+            host: self.host,
+            agent: self.agent,
+            client: builder.build(),
+            credentials: self.credentials,
+            pagination: self.pagination,
+            error_message: C::error_message,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use wiremock::matchers::{header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use crate::auth::{AuthenticationConstraint, Authenticator};
+    use crate::client::{
+        ApiClient, ClientResult, Message, RestClient, RestClientBuilder, json_error_message,
+    };
+    use crate::pagination::OffsetPagination;
+
+    struct Token(String);
+
+    #[async_trait::async_trait]
+    impl Authenticator for Token {
+        async fn authenticate(
+            &self,
+            _client: &RestClient,
+            _url: &mut url::Url,
+            _authentication: AuthenticationConstraint,
+        ) -> ClientResult<Option<String>> {
+            Ok(Some(format!("Bearer {}", self.0)))
+        }
+    }
+
+    struct TestClient(RestClient);
+
+    impl From<RestClient> for TestClient {
+        fn from(client: RestClient) -> Self {
+            Self(client)
+        }
+    }
+
+    impl ApiClient for TestClient {
+        type Credentials = Token;
+        const DEFAULT_HOST: &'static str = "https://api.example.com";
+        const DEFAULT_AGENT: &'static str = "doctavious-test";
+
+        fn error_message(body: &[u8]) -> String {
+            json_error_message(body)
+        }
+    }
+
+    async fn client(server: &MockServer) -> RestClient {
+        RestClientBuilder::<TestClient>::new()
+            .unwrap()
+            .with_host_override(&server.uri())
+            .with_credentials(Token("secret".to_string()))
+            .build()
+            .unwrap()
+            .0
+    }
+
+    #[tokio::test]
+    async fn should_follow_link_headers() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/notes"))
+            .and(query_param("page", "2"))
+            .and(header("Authorization", "Bearer secret"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([3])))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/notes"))
+            .and(header("User-Agent", "doctavious-test"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header(
+                        "Link",
+                        format!(r#"<{}/notes?page=2>; rel="next""#, server.uri()),
+                    )
+                    .set_body_json(json!([1, 2])),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = client(&server).await;
+        let notes: Vec<u64> = client
+            .get_all_pages(&client.url("/notes", None), Message::default())
+            .await
+            .unwrap()
+            .body;
+        assert_eq!(vec![1, 2, 3], notes);
+    }
+
+    #[tokio::test]
+    async fn should_page_through_offsets() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/notes"))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([3])))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/notes"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([1, 2])))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = RestClientBuilder::<TestClient>::new()
+            .unwrap()
+            .with_host_override(&server.uri())
+            .with_pagination(OffsetPagination::new("page", "per_page"))
+            .build()
+            .unwrap()
+            .0;
+        let notes: Vec<u64> = client
+            .get_all_pages(&client.url("/notes?per_page=2", None), Message::default())
+            .await
+            .unwrap()
+            .body;
+        assert_eq!(vec![1, 2, 3], notes);
+    }
+
+    #[tokio::test]
+    async fn should_map_error_messages() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/notes"))
+            .respond_with(ResponseTemplate::new(404).set_body_json(json!({
+                "message": "Not Found",
+                "documentation_url": "https://docs.example.com"
+            })))
+            .mount(&server)
+            .await;
+
+        let client = client(&server).await;
+        let error = client
+            .get::<Vec<u64>>(&client.url("/notes", None), Message::default())
+            .await
+            .unwrap_err();
+        assert!(error.is_not_found());
+        assert_eq!(
+            "HTTP Error. Code: 404 Not Found, message: Not Found",
+            error.to_string()
+        );
+    }
+}
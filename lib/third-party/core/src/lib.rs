@@ -1 +1,6 @@
+pub mod auth;
+pub mod client;
+pub mod pagination;
+pub mod rate_limit;
 pub mod serde;
+pub mod support;
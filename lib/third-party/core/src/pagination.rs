@@ -0,0 +1,274 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use reqwest::header::HeaderMap;
+use serde::Serialize;
+use url::Url;
+
+use crate::client::ClientResult;
+
+/// How a list endpoint's pages are paged through
+pub trait PaginationStrategy: Send + Sync {
+    /// URL of the page after the one at `url`, `None` when it's the last page.
+    /// `headers` and `items`, the number of items, are those of the page's response.
+    fn next_page(&self, url: &Url, headers: &HeaderMap, items: usize) -> Option<Url>;
+
+    /// Items of a page's body. Defaults to bodies which are an array of the items
+    fn page(&self, body: serde_json::Value) -> ClientResult<Page> {
+        Ok(Page {
+            items: serde_json::from_value(body)?,
+            next: None,
+        })
+    }
+}
+
+/// Page of a list endpoint
+#[derive(Debug, Default)]
+pub struct Page {
+    pub items: Vec<serde_json::Value>,
+    /// URL of the next page for APIs which link to it within the body rather than a header
+    pub next: Option<Url>,
+}
+
+/// Follows the `rel="next"` link of the [`LinkHeader`] as GitHub, GitLab and Gitea return
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LinkHeaderPagination;
+
+impl PaginationStrategy for LinkHeaderPagination {
+    fn next_page(&self, _url: &Url, headers: &HeaderMap, _items: usize) -> Option<Url> {
+        headers
+            .get(http::header::LINK)
+            .and_then(|l| l.to_str().ok())
+            .and_then(|l| LinkHeader::parse(l).ok())
+            .and_then(|l| l.get("next").map(|next| next.uri.clone()))
+    }
+}
+
+/// Increments the page number query parameter until a page has fewer items than the page size,
+/// for APIs which don't link to the next page
+#[derive(Clone, Copy, Debug)]
+pub struct OffsetPagination {
+    /// Query parameter of the page number, starting at 1
+    pub page: &'static str,
+    /// Query parameter of the page size
+    pub per_page: &'static str,
+}
+
+impl OffsetPagination {
+    pub fn new(page: &'static str, per_page: &'static str) -> Self {
+        Self { page, per_page }
+    }
+}
+
+impl PaginationStrategy for OffsetPagination {
+    fn next_page(&self, url: &Url, _headers: &HeaderMap, items: usize) -> Option<Url> {
+        let param = |name: &str| {
+            url.query_pairs()
+                .find(|(key, _)| key == name)
+                .and_then(|(_, value)| value.parse::<usize>().ok())
+        };
+
+        // without the page size there's no telling a partial page so stop at the first empty one
+        if items == 0 || param(self.per_page).is_some_and(|per_page| items < per_page) {
+            return None;
+        }
+
+        let page = param(self.page).unwrap_or(1);
+        Some(with_query_param(url, self.page, &(page + 1).to_string()))
+    }
+}
+
+/// Passes the token, or cursor, a response header holds for the next page as a query parameter,
+/// such as Azure DevOps' `x-ms-continuationtoken`
+#[derive(Clone, Copy, Debug)]
+pub struct KeysetPagination {
+    /// Response header of the next page's token, absent on the last page
+    pub header: &'static str,
+    /// Query parameter the token is passed as
+    pub param: &'static str,
+}
+
+impl KeysetPagination {
+    pub fn new(header: &'static str, param: &'static str) -> Self {
+        Self { header, param }
+    }
+}
+
+impl PaginationStrategy for KeysetPagination {
+    fn next_page(&self, url: &Url, headers: &HeaderMap, _items: usize) -> Option<Url> {
+        let token = headers
+            .get(self.header)
+            .and_then(|t| t.to_str().ok())
+            .filter(|t| !t.is_empty())?;
+        Some(with_query_param(url, self.param, token))
+    }
+}
+
+/// Copy of `url` with the query parameter set to `value`
+fn with_query_param(url: &Url, name: &str, value: &str) -> Url {
+    let query: Vec<(String, String)> = url
+        .query_pairs()
+        .filter(|(key, _)| key != name)
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect();
+
+    let mut next = url.clone();
+    next.query_pairs_mut()
+        .clear()
+        .extend_pairs(query)
+        .append_pair(name, value);
+    next
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize)]
+pub struct OffsetBasedPagination {
+    /// For offset-based and keyset-based paginated result sets, the number of results to include per page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub per_page: Option<u64>,
+    /// For offset-based paginated result sets, page of results to retrieve. Defaults to 20 and max 100
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<u64>,
+}
+
+// Note(sean): I'm somewhat confused by what Gitlab uses as the key to page as they seem to have a
+// a few different ways which include: id_after, page_token, cursor.
+// I think for now we won't codify it as we'll be using whatever is returned from the Link header
+/// Keyset-pagination allows for more efficient retrieval of pages and - in contrast to offset-based
+/// pagination - runtime is independent of the size of the collection.
+/// This method is controlled by the following parameters. order_by and sort are both mandatory.
+#[derive(Serialize)]
+pub struct KeysetBasedPagination {
+    /// For keyset-based paginated result sets, the value must be `"keyset"`
+    pagination: String,
+    /// For offset-based and keyset-based paginated result sets, the number of results to include per page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    per_page: Option<u64>,
+    /// For keyset-based paginated result sets, name of the column by which to order
+    order_by: String,
+    /// For keyset-based paginated result sets, sort order (`"asc"`` or `"desc"`)
+    sort: String,
+}
+
+#[derive(Serialize)]
+pub enum Pagination {
+    Offset(OffsetBasedPagination),
+    Keyset(KeysetBasedPagination),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Link {
+    /// A parsed form of the URI
+    pub uri: Url,
+
+    /// The raw text string of the URI
+    pub raw_uri: String,
+}
+
+type Rel = String;
+pub type RelLinkMap = HashMap<Rel, Link>;
+
+#[derive(Debug)]
+pub struct LinkHeader {
+    links: RelLinkMap,
+}
+
+impl LinkHeader {
+    // link: <https://gitlab.example.com/api/v4/projects/8/issues/8/notes?page=1&per_page=3>; rel="prev", <https://gitlab.example.com/api/v4/projects/8/issues/8/notes?page=3&per_page=3>; rel="next", <https://gitlab.example.com/api/v4/projects/8/issues/8/notes?page=1&per_page=3>; rel="first", <https://gitlab.example.com/api/v4/projects/8/issues/8/notes?page=3&per_page=3>; rel="last"
+
+    // TODO: do we want to return an error if we fail to parse?
+    pub fn parse(header: &str) -> ClientResult<Self> {
+        let mut links = RelLinkMap::new();
+        for part in header.split(',') {
+            let sections: Vec<&str> = part.trim().split(';').collect();
+            if sections.len() < 2 {
+                continue;
+            }
+
+            let raw_url = sections[0]
+                .trim()
+                .trim_start_matches('<')
+                .trim_end_matches('>');
+            let mut rel = "";
+            for param in &sections[1..] {
+                let trimmed = param.trim();
+                if trimmed.starts_with("rel=") {
+                    rel = trimmed.trim_start_matches("rel=").trim_matches('"');
+                }
+            }
+
+            if !rel.is_empty() {
+                links.insert(
+                    rel.to_string(),
+                    Link {
+                        raw_uri: raw_url.to_string(),
+                        uri: Url::from_str(raw_url)?,
+                    },
+                );
+            }
+        }
+
+        Ok(Self { links })
+    }
+
+    pub fn get(&self, rel: &str) -> Option<&Link> {
+        self.links.get(rel)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::header::HeaderMap;
+    use url::Url;
+
+    use crate::pagination::{KeysetPagination, LinkHeader, OffsetPagination, PaginationStrategy};
+
+    #[test]
+    fn should_parse_link_header() {
+        let header = LinkHeader::parse(
+            r#"<https://gitlab.example.com/api/v4/projects/8/notes?page=1&per_page=3>; rel="prev", <https://gitlab.example.com/api/v4/projects/8/notes?page=3&per_page=3>; rel="next""#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            "https://gitlab.example.com/api/v4/projects/8/notes?page=3&per_page=3",
+            header.get("next").unwrap().raw_uri
+        );
+        assert!(header.get("last").is_none());
+    }
+
+    #[test]
+    fn should_increment_offset_until_partial_page() {
+        let pagination = OffsetPagination::new("page", "per_page");
+        let url = Url::parse("https://example.com/notes?per_page=2&sort=asc").unwrap();
+
+        let next = pagination.next_page(&url, &HeaderMap::new(), 2).unwrap();
+        assert_eq!(
+            "https://example.com/notes?per_page=2&sort=asc&page=2",
+            next.as_str()
+        );
+        assert_eq!(
+            "https://example.com/notes?per_page=2&sort=asc&page=3",
+            pagination
+                .next_page(&next, &HeaderMap::new(), 2)
+                .unwrap()
+                .as_str()
+        );
+        assert_eq!(None, pagination.next_page(&next, &HeaderMap::new(), 1));
+    }
+
+    #[test]
+    fn should_pass_continuation_token() {
+        let pagination = KeysetPagination::new("x-ms-continuationtoken", "continuationToken");
+        let url =
+            Url::parse("https://dev.azure.com/org/_apis/threads?continuationToken=a").unwrap();
+
+        let mut headers = HeaderMap::new();
+        assert_eq!(None, pagination.next_page(&url, &headers, 100));
+
+        headers.insert("x-ms-continuationtoken", "b".parse().unwrap());
+        assert_eq!(
+            "https://dev.azure.com/org/_apis/threads?continuationToken=b",
+            pagination.next_page(&url, &headers, 100).unwrap().as_str()
+        );
+    }
+}
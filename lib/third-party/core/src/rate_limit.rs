@@ -0,0 +1,428 @@
+//! Rate limit aware request middleware.
+//!
+//! Reads the quota platforms return with each response, GitHub's `X-RateLimit-*` and GitLab's
+//! `RateLimit-*` headers, pausing requests once it's exhausted and retrying requests rejected by
+//! primary or secondary rate limits after `Retry-After`, or the quota's reset, has passed.
+//!
+//! See
+//! - https://docs.github.com/en/rest/using-the-rest-api/rate-limits-for-the-rest-api
+//! - https://docs.gitlab.com/ee/administration/settings/user_and_ip_rate_limits.html#response-headers
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use http::{Extensions, HeaderMap, StatusCode};
+use reqwest::{Request, Response};
+use reqwest_middleware::{Middleware, Next};
+use tokio::sync::Semaphore;
+use tracing::{debug, warn};
+
+/// Share of the quota remaining below which a warning is logged
+const LOW_QUOTA_RATIO: f64 = 0.1;
+
+/// Quota of the last response
+#[derive(Clone, Debug, PartialEq)]
+pub struct RateLimit {
+    pub limit: Option<u64>,
+    pub remaining: u64,
+    pub reset: Option<DateTime<Utc>>,
+}
+
+impl RateLimit {
+    /// Parses GitHub's `X-RateLimit-*` or GitLab's `RateLimit-*` headers.
+    /// Both reset headers are in UTC epoch seconds.
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        ["x-ratelimit-", "ratelimit-"].iter().find_map(|prefix| {
+            let header = |name: &str| {
+                headers
+                    .get(format!("{prefix}{name}"))
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| v.trim().parse::<i64>().ok())
+            };
+
+            Some(RateLimit {
+                limit: header("limit").and_then(|l| u64::try_from(l).ok()),
+                remaining: u64::try_from(header("remaining")?).ok()?,
+                reset: header("reset").and_then(|r| DateTime::from_timestamp(r, 0)),
+            })
+        })
+    }
+
+    fn is_exhausted(&self) -> bool {
+        self.remaining == 0
+    }
+
+    fn until_reset(&self) -> Option<Duration> {
+        self.reset
+            .and_then(|reset| (reset - Utc::now()).to_std().ok())
+    }
+}
+
+/// Middleware which pauses and retries requests around the platform's rate limits, and limits
+/// the number of concurrent requests as recommended to avoid secondary rate limits
+#[derive(Clone, Debug)]
+pub struct RateLimitMiddleware {
+    max_retries: u32,
+    max_wait: Duration,
+    backoff: Duration,
+    concurrency: Option<Arc<Semaphore>>,
+    quota: Arc<Mutex<Option<RateLimit>>>,
+}
+
+impl Default for RateLimitMiddleware {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            max_wait: Duration::from_secs(5 * 60),
+            backoff: Duration::from_secs(1),
+            concurrency: Some(Arc::new(Semaphore::new(8))),
+            quota: Arc::new(Mutex::new(None)),
+        }
+    }
+}
+
+impl RateLimitMiddleware {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of times a rate limited request is retried
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Longest time to pause before a request. Rate limited responses are returned as is when
+    /// the limit resets later than this
+    pub fn with_max_wait(mut self, max_wait: Duration) -> Self {
+        self.max_wait = max_wait;
+        self
+    }
+
+    /// Initial delay, doubled on each retry, for rate limited responses which don't say when
+    /// to retry
+    pub fn with_backoff(mut self, backoff: Duration) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Number of requests to send at once. `None` doesn't limit them
+    pub fn with_max_concurrency(mut self, max_concurrency: Option<usize>) -> Self {
+        self.concurrency = max_concurrency.map(|n| Arc::new(Semaphore::new(n)));
+        self
+    }
+
+    /// Quota of the last response which included rate limit headers
+    pub fn quota(&self) -> Option<RateLimit> {
+        self.quota.lock().unwrap().clone()
+    }
+
+    fn record(&self, response: &Response) -> Option<RateLimit> {
+        let rate_limit = RateLimit::from_headers(response.headers())?;
+        debug!(
+            limit = rate_limit.limit,
+            remaining = rate_limit.remaining,
+            reset = ?rate_limit.reset,
+            "{} rate limit quota",
+            response.url().host_str().unwrap_or_default()
+        );
+
+        if let Some(limit) = rate_limit.limit.filter(|l| *l > 0)
+            && (rate_limit.remaining as f64) < limit as f64 * LOW_QUOTA_RATIO
+        {
+            warn!(
+                "{} of {limit} requests remaining until {:?}",
+                rate_limit.remaining, rate_limit.reset
+            );
+        }
+
+        *self.quota.lock().unwrap() = Some(rate_limit.clone());
+        Some(rate_limit)
+    }
+
+    /// How long to wait before retrying a response, `None` when it wasn't rate limited.
+    /// GitHub rejects requests over its limits with `403 Forbidden`, as it does for missing
+    /// permissions, so those are only retried when their headers point to a rate limit.
+    fn retry_after(
+        &self,
+        response: &Response,
+        rate_limit: Option<&RateLimit>,
+        attempt: u32,
+    ) -> Option<Duration> {
+        let status = response.status();
+        if status != StatusCode::TOO_MANY_REQUESTS && status != StatusCode::FORBIDDEN {
+            return None;
+        }
+
+        if let Some(retry_after) = parse_retry_after(response.headers()) {
+            return Some(retry_after);
+        }
+
+        match rate_limit {
+            Some(rate_limit) if rate_limit.is_exhausted() => {
+                Some(rate_limit.until_reset().unwrap_or_default())
+            }
+            _ if status == StatusCode::TOO_MANY_REQUESTS => {
+                Some(self.backoff.saturating_mul(2u32.saturating_pow(attempt)))
+            }
+            _ => None,
+        }
+    }
+
+    /// Waits out an exhausted quota before sending a request
+    async fn pause(&self) {
+        let wait = self
+            .quota
+            .lock()
+            .unwrap()
+            .as_ref()
+            .filter(|q| q.is_exhausted())
+            .and_then(RateLimit::until_reset);
+
+        if let Some(wait) = wait.filter(|w| *w <= self.max_wait) {
+            warn!("rate limit exhausted, pausing requests for {wait:?}");
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Middleware for RateLimitMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        let _permit = match &self.concurrency {
+            Some(semaphore) => Some(
+                semaphore
+                    .acquire()
+                    .await
+                    .map_err(reqwest_middleware::Error::middleware)?,
+            ),
+            None => None,
+        };
+
+        let mut attempt = 0;
+        loop {
+            self.pause().await;
+
+            // requests with streamed bodies can't be retried
+            let Some(retry) = req.try_clone() else {
+                return next.run(req, extensions).await;
+            };
+
+            let response = next.clone().run(retry, extensions).await?;
+            let rate_limit = self.record(&response);
+            let Some(wait) = self.retry_after(&response, rate_limit.as_ref(), attempt) else {
+                return Ok(response);
+            };
+
+            if attempt >= self.max_retries || wait > self.max_wait {
+                warn!(
+                    "{} rate limited, not retrying after {attempt} retries",
+                    response.url()
+                );
+                return Ok(response);
+            }
+
+            attempt += 1;
+            warn!(
+                "{} rate limited, retrying in {wait:?} ({attempt}/{})",
+                response.url(),
+                self.max_retries
+            );
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+/// `Retry-After` in either seconds or as an HTTP date
+fn parse_retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers
+        .get(http::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        (date.with_timezone(&Utc) - Utc::now())
+            .to_std()
+            .unwrap_or_default(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use http::HeaderMap;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use crate::rate_limit::{RateLimit, RateLimitMiddleware, parse_retry_after};
+
+    fn client(middleware: RateLimitMiddleware) -> reqwest_middleware::ClientWithMiddleware {
+        reqwest_middleware::ClientBuilder::new(reqwest::Client::new())
+            .with(middleware.with_backoff(Duration::from_millis(10)))
+            .build()
+    }
+
+    async fn mock_rate_limited(server: &MockServer, response: ResponseTemplate, times: u64) {
+        Mock::given(method("GET"))
+            .and(path("/notes"))
+            .respond_with(response)
+            .up_to_n_times(times)
+            .expect(times)
+            .mount(server)
+            .await;
+    }
+
+    async fn mock_ok(server: &MockServer, times: u64) {
+        Mock::given(method("GET"))
+            .and(path("/notes"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("x-ratelimit-limit", "5000")
+                    .insert_header("x-ratelimit-remaining", "4999"),
+            )
+            .expect(times)
+            .mount(server)
+            .await;
+    }
+
+    #[test]
+    fn should_parse_github_and_gitlab_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-limit", "5000".parse().unwrap());
+        headers.insert("x-ratelimit-remaining", "0".parse().unwrap());
+        headers.insert("x-ratelimit-reset", "1700000000".parse().unwrap());
+        let github = RateLimit::from_headers(&headers).unwrap();
+        assert_eq!(Some(5000), github.limit);
+        assert_eq!(0, github.remaining);
+        assert_eq!(1700000000, github.reset.unwrap().timestamp());
+
+        let mut headers = HeaderMap::new();
+        headers.insert("ratelimit-limit", "600".parse().unwrap());
+        headers.insert("ratelimit-remaining", "42".parse().unwrap());
+        let gitlab = RateLimit::from_headers(&headers).unwrap();
+        assert_eq!(Some(600), gitlab.limit);
+        assert_eq!(42, gitlab.remaining);
+        assert_eq!(None, gitlab.reset);
+
+        assert_eq!(None, RateLimit::from_headers(&HeaderMap::new()));
+    }
+
+    #[test]
+    fn should_parse_retry_after() {
+        let mut headers = HeaderMap::new();
+        headers.insert("retry-after", "60".parse().unwrap());
+        assert_eq!(Some(Duration::from_secs(60)), parse_retry_after(&headers));
+
+        headers.insert(
+            "retry-after",
+            "Wed, 21 Oct 2015 07:28:00 GMT".parse().unwrap(),
+        );
+        assert_eq!(Some(Duration::ZERO), parse_retry_after(&headers));
+    }
+
+    #[tokio::test]
+    async fn should_retry_too_many_requests() {
+        let server = MockServer::start().await;
+        mock_rate_limited(
+            &server,
+            ResponseTemplate::new(429).insert_header("Retry-After", "0"),
+            1,
+        )
+        .await;
+        mock_rate_limited(&server, ResponseTemplate::new(429), 1).await;
+        mock_ok(&server, 1).await;
+
+        let middleware = RateLimitMiddleware::new();
+        let response = client(middleware.clone())
+            .get(format!("{}/notes", server.uri()))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(200, response.status().as_u16());
+        assert_eq!(4999, middleware.quota().unwrap().remaining);
+    }
+
+    #[tokio::test]
+    async fn should_retry_forbidden_once_quota_resets() {
+        let server = MockServer::start().await;
+        mock_rate_limited(
+            &server,
+            ResponseTemplate::new(403)
+                .insert_header("x-ratelimit-remaining", "0")
+                .insert_header("x-ratelimit-reset", "0"),
+            1,
+        )
+        .await;
+        mock_ok(&server, 1).await;
+
+        let response = client(RateLimitMiddleware::new())
+            .get(format!("{}/notes", server.uri()))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(200, response.status().as_u16());
+    }
+
+    #[tokio::test]
+    async fn should_not_retry_forbidden_without_rate_limit() {
+        let server = MockServer::start().await;
+        mock_rate_limited(
+            &server,
+            ResponseTemplate::new(403).insert_header("x-ratelimit-remaining", "4000"),
+            1,
+        )
+        .await;
+        mock_ok(&server, 0).await;
+
+        let response = client(RateLimitMiddleware::new())
+            .get(format!("{}/notes", server.uri()))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(403, response.status().as_u16());
+    }
+
+    #[tokio::test]
+    async fn should_return_rate_limited_response_after_max_retries() {
+        let server = MockServer::start().await;
+        mock_rate_limited(&server, ResponseTemplate::new(429), 3).await;
+
+        let response = client(RateLimitMiddleware::new().with_max_retries(2))
+            .get(format!("{}/notes", server.uri()))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(429, response.status().as_u16());
+
+        // resets later than the longest wait aren't waited for
+        let server = MockServer::start().await;
+        mock_rate_limited(
+            &server,
+            ResponseTemplate::new(429).insert_header("Retry-After", "3600"),
+            1,
+        )
+        .await;
+
+        let response = client(RateLimitMiddleware::new())
+            .get(format!("{}/notes", server.uri()))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(429, response.status().as_u16());
+    }
+}
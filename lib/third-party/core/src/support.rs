@@ -0,0 +1,34 @@
+use percent_encoding::{AsciiSet, CONTROLS, utf8_percent_encode};
+
+const PATH_SET: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'<')
+    .add(b'>')
+    .add(b'?')
+    .add(b'`')
+    .add(b'{')
+    .add(b'}');
+
+/// Like [`PATH_SET`] but also encoding `/` so a value can't span path segments
+const PATH_SEGMENT_SET: &AsciiSet = &PATH_SET.add(b'/');
+
+pub fn encode_path(pc: &str) -> String {
+    utf8_percent_encode(pc, PATH_SET).to_string()
+}
+
+/// Encodes a value, such as a tag or branch name, as a single path segment
+pub fn encode_path_segment(pc: &str) -> String {
+    utf8_percent_encode(pc, PATH_SEGMENT_SET).to_string()
+}
+
+/// Appends the query to the path when there is one
+pub fn with_query(path: String, query: &[(&str, String)]) -> String {
+    let query = serde_urlencoded::to_string(query).unwrap_or_default();
+    if query.is_empty() {
+        path
+    } else {
+        format!("{path}?{query}")
+    }
+}
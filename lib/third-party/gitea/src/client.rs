@@ -0,0 +1,173 @@
+use std::fmt;
+use std::ops::Deref;
+
+pub use api_client_core::auth::AuthenticationConstraint;
+use api_client_core::auth::Authenticator;
+pub use api_client_core::client::{
+    ApiClient, ClientError, ClientResult, MediaType, Message, Response, RestClient,
+    RestClientBuilder,
+};
+pub use api_client_core::pagination::{Link, LinkHeader, OffsetBasedPagination, RelLinkMap};
+pub(crate) use api_client_core::support;
+use url::Url;
+
+use crate::{pulls, releases};
+
+const DEFAULT_HOST: &str = "https://gitea.com/api/v1";
+const DEFAULT_CLIENT_AGENT: &str = concat!("doctavious-gitea/", env!("CARGO_PKG_VERSION"));
+
+/// Entrypoint for interacting with the API client.
+///
+/// Build one for a self-hosted Gitea or Forgejo instance with its API URL as the host override,
+/// e.g., `https://codeberg.org/api/v1`
+#[derive(Clone)]
+pub struct Client {
+    client: RestClient,
+}
+
+pub type ClientBuilder = RestClientBuilder<Client>;
+
+impl ApiClient for Client {
+    type Credentials = Credentials;
+    const DEFAULT_HOST: &'static str = DEFAULT_HOST;
+    const DEFAULT_AGENT: &'static str = DEFAULT_CLIENT_AGENT;
+
+    fn error_message(body: &[u8]) -> String {
+        api_client_core::client::json_error_message(body)
+    }
+}
+
+impl From<RestClient> for Client {
+    fn from(client: RestClient) -> Self {
+        Self { client }
+    }
+}
+
+impl Deref for Client {
+    type Target = RestClient;
+
+    fn deref(&self) -> &RestClient {
+        &self.client
+    }
+}
+
+/// Various forms of authentication credentials supported by Gitea.
+#[derive(PartialEq, Clone)]
+pub enum Credentials {
+    /// Personal access token or the Actions `GITEA_TOKEN`, sent as `Authorization: token <token>`
+    Token(String),
+}
+
+impl fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Credentials::Token(value) => f
+                .debug_tuple("Credentials::Token")
+                .field(&"*".repeat(value.len()))
+                .finish(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Authenticator for Credentials {
+    async fn authenticate(
+        &self,
+        _client: &RestClient,
+        _url: &mut Url,
+        _authentication: AuthenticationConstraint,
+    ) -> ClientResult<Option<String>> {
+        match self {
+            Credentials::Token(token) => Ok(Some(format!("token {token}"))),
+        }
+    }
+}
+
+/// Query of a page. Gitea names the page size `limit` and caps it, 50 by default
+fn pagination_query(pagination: &OffsetBasedPagination) -> Vec<(&'static str, String)> {
+    let mut query = Vec::new();
+    if let Some(page) = pagination.page {
+        query.push(("page", page.to_string()));
+    }
+
+    if let Some(limit) = pagination.per_page {
+        query.push(("limit", limit.to_string()));
+    }
+
+    query
+}
+
+impl Client {
+    pub fn new<A, C>(agent: A, credentials: C) -> ClientResult<Self>
+    where
+        A: Into<String>,
+        C: Into<Option<Credentials>>,
+    {
+        let mut builder = ClientBuilder::new()?.with_agent(&agent.into());
+        if let Some(credentials) = credentials.into() {
+            builder = builder.with_credentials(credentials);
+        }
+
+        builder.build()
+    }
+
+    /// Gets a single page when paginated otherwise every page
+    pub(crate) async fn get_list<D>(
+        &self,
+        path: String,
+        query: Vec<(&str, String)>,
+        pagination: Option<OffsetBasedPagination>,
+    ) -> ClientResult<Response<Vec<D>>>
+    where
+        D: serde::de::DeserializeOwned + 'static + Send,
+    {
+        match pagination {
+            Some(pagination) => {
+                let query = [query, pagination_query(&pagination)].concat();
+                let url = self.url(&support::with_query(path, &query), None);
+                self.get(&url, Message::default()).await
+            }
+            None => {
+                let url = self.url(&support::with_query(path, &query), None);
+                self.get_all_pages(&url, Message::default()).await
+            }
+        }
+    }
+
+    pub fn pull_requests(&self) -> pulls::PullRequests {
+        pulls::PullRequests::new(self.clone())
+    }
+
+    pub fn releases(&self) -> releases::Releases {
+        releases::Releases::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::client::{OffsetBasedPagination, pagination_query, support};
+
+    #[test]
+    fn should_name_page_size_limit() {
+        let pagination = OffsetBasedPagination {
+            per_page: Some(20),
+            page: Some(2),
+        };
+        assert_eq!(
+            "/repos/o/r/releases?draft=false&page=2&limit=20",
+            support::with_query(
+                "/repos/o/r/releases".to_string(),
+                &[
+                    [("draft", "false".to_string())].to_vec(),
+                    pagination_query(&pagination)
+                ]
+                .concat()
+            )
+        );
+        assert_eq!(
+            "/repos/o/r/releases",
+            support::with_query("/repos/o/r/releases".to_string(), &[])
+        );
+        assert_eq!("a%2Fb", support::encode_path_segment("a/b"));
+    }
+}
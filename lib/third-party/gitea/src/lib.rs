@@ -0,0 +1,6 @@
+// Client for the Gitea API which Forgejo, Gitea's soft fork, remains compatible with.
+// Follows the design of the GitHub and GitLab clients.
+
+pub mod client;
+pub mod pulls;
+pub mod releases;
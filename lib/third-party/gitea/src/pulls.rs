@@ -0,0 +1,381 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::client::{Client, ClientResult, Message, OffsetBasedPagination, Response, support};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct User {
+    pub id: u64,
+    pub login: String,
+    #[serde(default)]
+    pub full_name: String,
+    #[serde(default)]
+    pub email: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PullRequestBranch {
+    pub label: String,
+    #[serde(rename = "ref")]
+    pub reference: String,
+    pub sha: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PullRequest {
+    pub id: u64,
+    /// The pull request number, which is shared with issues
+    pub number: u64,
+    pub title: String,
+    #[serde(default)]
+    pub body: String,
+    // TODO: could make into an enum
+    pub state: String,
+    #[serde(default)]
+    pub draft: bool,
+    #[serde(default)]
+    pub merged: bool,
+    pub merged_at: Option<DateTime<Utc>>,
+    pub merge_commit_sha: Option<String>,
+    pub html_url: String,
+    pub user: Option<User>,
+    pub base: PullRequestBranch,
+    pub head: PullRequestBranch,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Comment on a pull request. Gitea stores these as comments of the pull request's issue
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Comment {
+    pub id: u64,
+    pub body: String,
+    pub html_url: String,
+    pub user: Option<User>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+struct CommentRequest {
+    body: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ReviewersRequest {
+    /// Usernames of the users to request reviews from
+    pub reviewers: Vec<String>,
+    /// Names of the organization's teams to request reviews from
+    pub team_reviewers: Vec<String>,
+}
+
+/// Review, or request for one, of a pull request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PullReview {
+    pub id: u64,
+    // TODO: could make into an enum
+    pub state: String,
+    pub user: Option<User>,
+}
+
+pub struct PullRequests {
+    pub client: Client,
+}
+
+impl PullRequests {
+    pub fn new(client: Client) -> Self {
+        PullRequests { client }
+    }
+
+    /// Gets a pull request.
+    ///
+    /// This function performs a `GET` to `/repos/:owner/:repo/pulls/:index`
+    /// From https://gitea.com/api/swagger#/repository/repoGetPullRequest
+    pub async fn get_pull_request(
+        &self,
+        owner: &str,
+        repository: &str,
+        index: u64,
+    ) -> ClientResult<Response<PullRequest>> {
+        let url = self.client.url(
+            &format!(
+                "/repos/{}/{}/pulls/{}",
+                support::encode_path_segment(owner),
+                support::encode_path_segment(repository),
+                index
+            ),
+            None,
+        );
+
+        self.client.get(&url, Message::default()).await
+    }
+
+    /// Gets the pull request that introduced a commit.
+    /// Responds with `404 Not Found` when no pull request contains the commit.
+    ///
+    /// This function performs a `GET` to `/repos/:owner/:repo/commits/:sha/pull`
+    /// From https://gitea.com/api/swagger#/repository/repoGetCommitPullRequest
+    pub async fn get_pull_request_for_commit(
+        &self,
+        owner: &str,
+        repository: &str,
+        sha: &str,
+    ) -> ClientResult<Response<PullRequest>> {
+        let url = self.client.url(
+            &format!(
+                "/repos/{}/{}/commits/{}/pull",
+                support::encode_path_segment(owner),
+                support::encode_path_segment(repository),
+                support::encode_path_segment(sha)
+            ),
+            None,
+        );
+
+        self.client.get(&url, Message::default()).await
+    }
+
+    /// Lists comments on a pull request, oldest first.
+    ///
+    /// This function performs a `GET` to `/repos/:owner/:repo/issues/:index/comments`
+    /// From https://gitea.com/api/swagger#/issue/issueGetComments
+    pub async fn list_all_pull_request_comments(
+        &self,
+        owner: &str,
+        repository: &str,
+        index: u64,
+        pagination: Option<OffsetBasedPagination>,
+    ) -> ClientResult<Response<Vec<Comment>>> {
+        let path = format!(
+            "/repos/{}/{}/issues/{}/comments",
+            support::encode_path_segment(owner),
+            support::encode_path_segment(repository),
+            index
+        );
+
+        self.client.get_list(path, Vec::new(), pagination).await
+    }
+
+    /// Creates a comment on a pull request.
+    ///
+    /// This function performs a `POST` to `/repos/:owner/:repo/issues/:index/comments`
+    /// From https://gitea.com/api/swagger#/issue/issueCreateComment
+    pub async fn create_pull_request_comment(
+        &self,
+        owner: &str,
+        repository: &str,
+        index: u64,
+        body: String,
+    ) -> ClientResult<Response<Comment>> {
+        let url = self.client.url(
+            &format!(
+                "/repos/{}/{}/issues/{}/comments",
+                support::encode_path_segment(owner),
+                support::encode_path_segment(repository),
+                index
+            ),
+            None,
+        );
+
+        self.client
+            .post(&url, Message::json(&CommentRequest { body })?)
+            .await
+    }
+
+    /// Updates a comment on a pull request.
+    ///
+    /// This function performs a `PATCH` to `/repos/:owner/:repo/issues/comments/:id`
+    /// From https://gitea.com/api/swagger#/issue/issueEditComment
+    pub async fn update_pull_request_comment(
+        &self,
+        owner: &str,
+        repository: &str,
+        comment_id: u64,
+        body: String,
+    ) -> ClientResult<Response<Comment>> {
+        let url = self.client.url(
+            &format!(
+                "/repos/{}/{}/issues/comments/{}",
+                support::encode_path_segment(owner),
+                support::encode_path_segment(repository),
+                comment_id
+            ),
+            None,
+        );
+
+        self.client
+            .patch(&url, Message::json(&CommentRequest { body })?)
+            .await
+    }
+
+    /// Requests reviews for a pull request from users and teams.
+    ///
+    /// This function performs a `POST` to `/repos/:owner/:repo/pulls/:index/requested_reviewers`
+    /// From https://gitea.com/api/swagger#/repository/repoCreatePullReviewRequests
+    pub async fn request_reviewers(
+        &self,
+        owner: &str,
+        repository: &str,
+        index: u64,
+        reviewers: &ReviewersRequest,
+    ) -> ClientResult<Response<Vec<PullReview>>> {
+        let url = self.client.url(
+            &format!(
+                "/repos/{}/{}/pulls/{}/requested_reviewers",
+                support::encode_path_segment(owner),
+                support::encode_path_segment(repository),
+                index
+            ),
+            None,
+        );
+
+        self.client.post(&url, Message::json(reviewers)?).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use wiremock::matchers::{body_json, header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use crate::client::{Client, ClientBuilder, Credentials};
+
+    fn comment(id: u64, body: &str) -> serde_json::Value {
+        json!({
+            "id": id,
+            "body": body,
+            "html_url": format!("https://gitea.com/owner/repo/pulls/7#issuecomment-{id}"),
+            "user": {"id": 1, "login": "doctavious"},
+            "created_at": "2025-01-02T03:04:05Z",
+            "updated_at": "2025-01-02T03:04:05Z"
+        })
+    }
+
+    fn client(server: &MockServer) -> Client {
+        ClientBuilder::new()
+            .unwrap()
+            .with_host_override(&server.uri())
+            .with_credentials(Credentials::Token("secret".to_string()))
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn should_list_comments_across_pages() {
+        let server = MockServer::start().await;
+        let comments = "/repos/owner/repo/issues/7/comments";
+        Mock::given(method("GET"))
+            .and(path(comments))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([comment(2, "second")])))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(comments))
+            .and(header("Authorization", "token secret"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header(
+                        "Link",
+                        format!(r#"<{}{comments}?page=2>; rel="next""#, server.uri()),
+                    )
+                    .set_body_json(json!([comment(1, "first")])),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let comments = client(&server)
+            .pull_requests()
+            .list_all_pull_request_comments("owner", "repo", 7, None)
+            .await
+            .unwrap()
+            .body;
+
+        assert_eq!(
+            vec![(1, "first"), (2, "second")],
+            comments
+                .iter()
+                .map(|c| (c.id, c.body.as_str()))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[tokio::test]
+    async fn should_create_and_update_comments() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/repos/owner/repo/issues/7/comments"))
+            .and(body_json(json!({"body": "hello"})))
+            .respond_with(ResponseTemplate::new(201).set_body_json(comment(1, "hello")))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("PATCH"))
+            .and(path("/repos/owner/repo/issues/comments/1"))
+            .and(body_json(json!({"body": "updated"})))
+            .respond_with(ResponseTemplate::new(200).set_body_json(comment(1, "updated")))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let pull_requests = client(&server).pull_requests();
+        let created = pull_requests
+            .create_pull_request_comment("owner", "repo", 7, "hello".to_string())
+            .await
+            .unwrap();
+        let updated = pull_requests
+            .update_pull_request_comment("owner", "repo", created.body.id, "updated".into())
+            .await
+            .unwrap();
+
+        assert_eq!("updated", updated.body.body);
+    }
+
+    #[tokio::test]
+    async fn should_get_pull_request_for_commit() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/owner/repo/commits/1f2e3d4c/pull"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 70,
+                "number": 7,
+                "title": "Add feature",
+                "state": "closed",
+                "merged": true,
+                "merged_at": "2025-01-03T03:04:05Z",
+                "merge_commit_sha": "1f2e3d4c",
+                "html_url": "https://gitea.com/owner/repo/pulls/7",
+                "base": {"label": "main", "ref": "main", "sha": "0a0b0c0d"},
+                "head": {"label": "feature", "ref": "feature", "sha": "5e6f7a8b"},
+                "created_at": "2025-01-02T03:04:05Z",
+                "updated_at": "2025-01-03T03:04:05Z"
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/owner/repo/commits/9e8d7c6b/pull"))
+            .respond_with(ResponseTemplate::new(404).set_body_json(json!({"message": "not found"})))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let pull_requests = client(&server).pull_requests();
+        let pull_request = pull_requests
+            .get_pull_request_for_commit("owner", "repo", "1f2e3d4c")
+            .await
+            .unwrap()
+            .body;
+        assert_eq!(7, pull_request.number);
+        assert_eq!("feature", pull_request.head.reference);
+        assert!(pull_request.merged);
+
+        let error = pull_requests
+            .get_pull_request_for_commit("owner", "repo", "9e8d7c6b")
+            .await
+            .unwrap_err();
+        assert!(error.is_not_found());
+    }
+}
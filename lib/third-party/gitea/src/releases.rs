@@ -0,0 +1,274 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::client::{Client, ClientResult, Message, OffsetBasedPagination, Response, support};
+use crate::pulls::User;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Release {
+    pub id: u64,
+    pub tag_name: String,
+    pub target_commitish: String,
+    pub name: String,
+    #[serde(default)]
+    pub body: String,
+    pub url: String,
+    pub html_url: String,
+    pub draft: bool,
+    pub prerelease: bool,
+    pub author: Option<User>,
+    pub created_at: DateTime<Utc>,
+    pub published_at: Option<DateTime<Utc>>,
+}
+
+/// Options to create a release. The tag is created from `target_commitish` when it doesn't exist
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CreateRelease {
+    pub tag_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_commitish: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    pub draft: bool,
+    pub prerelease: bool,
+}
+
+pub struct Releases {
+    pub client: Client,
+}
+
+impl Releases {
+    pub fn new(client: Client) -> Self {
+        Releases { client }
+    }
+
+    /// Lists releases, newest first.
+    ///
+    /// This function performs a `GET` to `/repos/:owner/:repo/releases`
+    /// From https://gitea.com/api/swagger#/repository/repoListReleases
+    ///
+    /// **Parameters**
+    ///
+    /// * draft - filter drafts or non-drafts
+    /// * prerelease - filter pre-releases or releases
+    pub async fn list_releases(
+        &self,
+        owner: &str,
+        repository: &str,
+        draft: Option<bool>,
+        prerelease: Option<bool>,
+        pagination: Option<OffsetBasedPagination>,
+    ) -> ClientResult<Response<Vec<Release>>> {
+        let path = format!(
+            "/repos/{}/{}/releases",
+            support::encode_path_segment(owner),
+            support::encode_path_segment(repository)
+        );
+
+        let mut query = Vec::new();
+        if let Some(draft) = draft {
+            query.push(("draft", draft.to_string()));
+        }
+
+        if let Some(prerelease) = prerelease {
+            query.push(("pre-release", prerelease.to_string()));
+        }
+
+        self.client.get_list(path, query, pagination).await
+    }
+
+    /// Gets the release of a tag.
+    ///
+    /// This function performs a `GET` to `/repos/:owner/:repo/releases/tags/:tag`
+    /// From https://gitea.com/api/swagger#/repository/repoGetReleaseByTag
+    pub async fn get_release_by_tag(
+        &self,
+        owner: &str,
+        repository: &str,
+        tag: &str,
+    ) -> ClientResult<Response<Release>> {
+        let url = self.client.url(
+            &format!(
+                "/repos/{}/{}/releases/tags/{}",
+                support::encode_path_segment(owner),
+                support::encode_path_segment(repository),
+                support::encode_path_segment(tag)
+            ),
+            None,
+        );
+
+        self.client.get(&url, Message::default()).await
+    }
+
+    /// Creates a release.
+    ///
+    /// This function performs a `POST` to `/repos/:owner/:repo/releases`
+    /// From https://gitea.com/api/swagger#/repository/repoCreateRelease
+    pub async fn create_release(
+        &self,
+        owner: &str,
+        repository: &str,
+        release: &CreateRelease,
+    ) -> ClientResult<Response<Release>> {
+        let url = self.client.url(
+            &format!(
+                "/repos/{}/{}/releases",
+                support::encode_path_segment(owner),
+                support::encode_path_segment(repository)
+            ),
+            None,
+        );
+
+        self.client.post(&url, Message::json(release)?).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use wiremock::matchers::{body_json, header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use crate::client::{Client, ClientBuilder, Credentials, OffsetBasedPagination};
+    use crate::releases::CreateRelease;
+
+    fn release(id: u64, tag: &str) -> serde_json::Value {
+        json!({
+            "id": id,
+            "tag_name": tag,
+            "target_commitish": "main",
+            "name": tag,
+            "body": format!("Release {tag}"),
+            "url": format!("https://gitea.com/api/v1/repos/owner/repo/releases/{id}"),
+            "html_url": format!("https://gitea.com/owner/repo/releases/tag/{tag}"),
+            "draft": false,
+            "prerelease": false,
+            "author": {"id": 1, "login": "doctavious"},
+            "created_at": "2025-01-02T03:04:05Z",
+            "published_at": "2025-01-02T03:04:05Z"
+        })
+    }
+
+    fn client(server: &MockServer) -> Client {
+        ClientBuilder::new()
+            .unwrap()
+            .with_host_override(&server.uri())
+            .with_credentials(Credentials::Token("secret".to_string()))
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn should_list_a_page_of_filtered_releases() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/owner/repo/releases"))
+            .and(query_param("draft", "false"))
+            .and(query_param("pre-release", "false"))
+            .and(query_param("page", "2"))
+            .and(query_param("limit", "1"))
+            .and(header("Authorization", "token secret"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header(
+                        "Link",
+                        format!(
+                            r#"<{}/repos/owner/repo/releases?page=3>; rel="next""#,
+                            server.uri()
+                        ),
+                    )
+                    .set_body_json(json!([release(2, "v1.0.0")])),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let releases = client(&server)
+            .releases()
+            .list_releases(
+                "owner",
+                "repo",
+                Some(false),
+                Some(false),
+                Some(OffsetBasedPagination {
+                    per_page: Some(1),
+                    page: Some(2),
+                }),
+            )
+            .await
+            .unwrap()
+            .body;
+
+        // only the requested page is fetched
+        assert_eq!(
+            vec!["v1.0.0"],
+            releases
+                .iter()
+                .map(|r| r.tag_name.as_str())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[tokio::test]
+    async fn should_get_release_by_tag() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/owner/repo/releases/tags/release%2F1.0"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(release(1, "release/1.0")))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let release = client(&server)
+            .releases()
+            .get_release_by_tag("owner", "repo", "release/1.0")
+            .await
+            .unwrap()
+            .body;
+
+        assert_eq!("release/1.0", release.tag_name);
+        assert_eq!(
+            Some("doctavious"),
+            release.author.as_ref().map(|a| a.login.as_str())
+        );
+    }
+
+    #[tokio::test]
+    async fn should_create_release() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/repos/owner/repo/releases"))
+            .and(body_json(json!({
+                "tag_name": "v1.1.0",
+                "target_commitish": "main",
+                "body": "Release v1.1.0",
+                "draft": false,
+                "prerelease": false
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(release(3, "v1.1.0")))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let release = client(&server)
+            .releases()
+            .create_release(
+                "owner",
+                "repo",
+                &CreateRelease {
+                    tag_name: "v1.1.0".to_string(),
+                    target_commitish: Some("main".to_string()),
+                    body: Some("Release v1.1.0".to_string()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap()
+            .body;
+
+        assert_eq!(3, release.id);
+        assert_eq!("v1.1.0", release.tag_name);
+    }
+}
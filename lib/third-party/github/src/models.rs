@@ -1,3 +1,4 @@
+pub mod apps;
 pub mod orgs;
 pub mod pulls;
 pub mod teams;
@@ -0,0 +1,217 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::client::{AuthenticationConstraint, Client, ClientResult, MediaType, Message, Response};
+use crate::models::{Installation, InstallationId, InstallationPermissions};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct InstallationToken {
+    pub token: String,
+    pub expires_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub permissions: Option<InstallationPermissions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repository_selection: Option<String>,
+}
+
+pub struct Apps {
+    pub client: Client,
+}
+
+impl Apps {
+    pub fn new(client: Client) -> Self {
+        Apps { client }
+    }
+
+    /// Gets the installation of the authenticated app on a repository.
+    /// Responds with `404 Not Found` when the app isn't installed on the repository.
+    ///
+    /// This function performs a `GET` to `/repos/:owner/:repo/installation`
+    /// From https://docs.github.com/en/rest/apps/apps#get-a-repository-installation-for-the-authenticated-app
+    pub async fn get_repository_installation(
+        &self,
+        owner: &str,
+        repository: &str,
+    ) -> ClientResult<Response<Installation>> {
+        let url = self.client.url(
+            &format!(
+                "/repos/{}/{}/installation",
+                crate::client::support::encode_path(owner),
+                crate::client::support::encode_path(repository),
+            ),
+            None,
+        );
+
+        self.client
+            .get_authenticated(&url, AuthenticationConstraint::JWT)
+            .await
+    }
+
+    /// Creates an installation access token which expires after an hour.
+    ///
+    /// This function performs a `POST` to `/app/installations/:installation_id/access_tokens`
+    /// From https://docs.github.com/en/rest/apps/apps#create-an-installation-access-token-for-an-app
+    pub async fn create_installation_access_token(
+        &self,
+        installation_id: InstallationId,
+    ) -> ClientResult<Response<InstallationToken>> {
+        let url = self.client.url(
+            &format!("/app/installations/{installation_id}/access_tokens"),
+            None,
+        );
+
+        self.client
+            .post_media(
+                &url,
+                Message::default(),
+                MediaType::Json,
+                AuthenticationConstraint::JWT,
+            )
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use serde_json::json;
+    use wiremock::matchers::{header, header_regex, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use crate::client::{AppCredentials, Client, ClientBuilder, InstallationTarget};
+    use crate::models::InstallationId;
+
+    const PRIVATE_KEY: &[u8] = include_bytes!("../../tests/fixtures/app-private-key.pem");
+    const COMMENTS: &str = "/repos/doctavious/doctavious/issues/7/comments";
+
+    fn user(login: &str) -> serde_json::Value {
+        let url = format!("https://api.github.com/users/{login}");
+        json!({
+            "login": login,
+            "id": 1,
+            "node_id": "MDQ6VXNlcjE=",
+            "avatar_url": "https://avatars.githubusercontent.com/u/1",
+            "gravatar_id": "",
+            "url": url,
+            "html_url": format!("https://github.com/{login}"),
+            "followers_url": format!("{url}/followers"),
+            "following_url": format!("{url}/following"),
+            "gists_url": format!("{url}/gists"),
+            "starred_url": format!("{url}/starred"),
+            "subscriptions_url": format!("{url}/subscriptions"),
+            "organizations_url": format!("{url}/orgs"),
+            "repos_url": format!("{url}/repos"),
+            "events_url": format!("{url}/events"),
+            "received_events_url": format!("{url}/received_events"),
+            "type": "Organization",
+            "site_admin": false
+        })
+    }
+
+    async fn mock_installation(server: &MockServer, expires_in: Duration, tokens: u64) {
+        Mock::given(method("GET"))
+            .and(path("/repos/doctavious/doctavious/installation"))
+            .and(header_regex("Authorization", "^Bearer ey"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 42,
+                "account": user("doctavious"),
+                "app_id": 1,
+                "permissions": {"issues": "write", "metadata": "read"},
+                "events": ["pull_request"]
+            })))
+            .expect(1)
+            .mount(server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/app/installations/42/access_tokens"))
+            .and(header_regex("Authorization", "^Bearer ey"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+                "token": "ghs_installation",
+                "expires_at": Utc::now() + expires_in,
+                "permissions": {"issues": "write"},
+                "repository_selection": "all"
+            })))
+            .expect(tokens)
+            .mount(server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(COMMENTS))
+            .and(header("Authorization", "Bearer ghs_installation"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+            .expect(2)
+            .mount(server)
+            .await;
+    }
+
+    fn client(server: &MockServer, app: &AppCredentials) -> Client {
+        ClientBuilder::new()
+            .unwrap()
+            .with_host_override(&server.uri())
+            .with_credentials(app.installation(InstallationTarget::Repository {
+                owner: "doctavious".to_string(),
+                repository: "doctavious".to_string(),
+            }))
+            .build()
+            .unwrap()
+    }
+
+    async fn list_comments(client: &Client) {
+        client
+            .pull_requests()
+            .list_all_pull_request_notes("doctavious", "doctavious", 7, None, None, None)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn should_cache_installation_tokens() {
+        let server = MockServer::start().await;
+        mock_installation(&server, Duration::hours(1), 1).await;
+
+        let app = AppCredentials::new(1, PRIVATE_KEY.to_vec()).unwrap();
+        list_comments(&client(&server, &app)).await;
+        // clients of the same app share its cache
+        list_comments(&client(&server, &app)).await;
+    }
+
+    #[tokio::test]
+    async fn should_refresh_installation_tokens_before_they_expire() {
+        let server = MockServer::start().await;
+        mock_installation(&server, Duration::seconds(30), 2).await;
+
+        let client = client(
+            &server,
+            &AppCredentials::new(1, PRIVATE_KEY.to_vec()).unwrap(),
+        );
+        list_comments(&client).await;
+        list_comments(&client).await;
+    }
+
+    #[tokio::test]
+    async fn should_fail_when_app_is_not_installed() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/app/installations/7/access_tokens"))
+            .respond_with(ResponseTemplate::new(404).set_body_json(json!({
+                "message": "Not Found"
+            })))
+            .mount(&server)
+            .await;
+
+        let app = AppCredentials::new(1, PRIVATE_KEY.to_vec()).unwrap();
+        let client = ClientBuilder::new()
+            .unwrap()
+            .with_host_override(&server.uri())
+            .with_credentials(app.installation(InstallationTarget::Id(InstallationId(7))))
+            .build()
+            .unwrap();
+
+        let error = client
+            .pull_requests()
+            .list_all_pull_request_notes("doctavious", "doctavious", 7, None, None, None)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("404"));
+    }
+}
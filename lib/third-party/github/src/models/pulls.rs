@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use url::Url;
 
-use crate::client::{Client, ClientResult, OffsetBasedPagination, Response};
+use crate::client::{Client, ClientResult, Message, OffsetBasedPagination, Response};
 use crate::models::{
     Author, AuthorAssociation, IssueState, Label, Milestone, PullRequestId, Repository, teams,
 };
@@ -241,8 +241,16 @@ pub enum MergeableState {
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PullRequestNote {
-    id: String,
-    body: String,
+    pub id: u64,
+    pub body: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ReviewersRequest {
+    /// Logins of the users to request reviews from
+    pub reviewers: Vec<String>,
+    /// Slugs of the teams to request reviews from
+    pub team_reviewers: Vec<String>,
 }
 
 pub struct PullRequests {
@@ -254,6 +262,17 @@ impl PullRequests {
         PullRequests { client }
     }
 
+    /// Lists comments on a pull request.
+    ///
+    /// Pull request comments are issue comments, as opposed to review comments on the diff.
+    /// This function performs a `GET` to `/repos/:owner/:repo/issues/:issue_number/comments`
+    /// From https://docs.github.com/en/rest/issues/comments#list-issue-comments
+    ///
+    /// **Parameters**
+    ///
+    /// * sort - `created` or `updated`
+    /// * order_by - direction to sort, `asc` or `desc`
+    /// * pagination - page to get. Every page is fetched when `None`
     pub async fn list_all_pull_request_notes(
         &self,
         owner: &str,
@@ -264,15 +283,46 @@ impl PullRequests {
         pagination: Option<OffsetBasedPagination>,
     ) -> ClientResult<Response<Vec<PullRequestNote>>> {
         let mut query_args: Vec<(String, String)> = Default::default();
-        if let Some(sort) = sort {}
+        if let Some(sort) = sort {
+            query_args.push(("sort".to_string(), sort.to_string()));
+        }
+
+        if let Some(order_by) = order_by {
+            query_args.push(("direction".to_string(), order_by.to_string()));
+        }
+
+        // without a page every page is fetched, using the maximum page size
+        let all_pages = pagination.is_none();
+        if all_pages {
+            query_args.push(("per_page".to_string(), "100".to_string()));
+        }
 
-        if let Some(order_by) = order_by {}
+        let mut query_ = serde_urlencoded::to_string(&query_args)?;
+        if let Some(pagination) = pagination {
+            let pagination_query = serde_urlencoded::to_string(pagination)?;
+            if !pagination_query.is_empty() {
+                if !query_.is_empty() {
+                    query_.push('&');
+                }
+                query_.push_str(&pagination_query);
+            }
+        }
 
-        if let Some(pagination) = pagination {}
+        let mut path = format!(
+            "/repos/{}/{}/issues/{}/comments",
+            crate::client::support::encode_path(owner),
+            crate::client::support::encode_path(repository),
+            pull_request_id,
+        );
+        if !query_.is_empty() {
+            path = format!("{path}?{query_}");
+        }
 
-        let query_ = serde_urlencoded::to_string(&query_args).unwrap();
+        let url = self.client.url(&path, None);
+        if all_pages {
+            return self.client.get_all_pages(&url, Message::default()).await;
+        }
 
-        let url = self.client.url(&format!(""), None);
         self.client
             .get(
                 &url,
@@ -284,25 +334,96 @@ impl PullRequests {
             .await
     }
 
-    // MergeRequestNote
+    /// Creates a comment on a pull request.
+    ///
+    /// This function performs a `POST` to `/repos/:owner/:repo/issues/:issue_number/comments`
+    /// From https://docs.github.com/en/rest/issues/comments#create-an-issue-comment
     pub async fn create_pull_request_note(
         &self,
         owner: &str,
         repository: &str,
-        merge_request_iid: u64,
+        pull_request_id: u64,
         body: String,
-    ) -> ClientResult<Response<Vec<()>>> {
-        todo!()
+    ) -> ClientResult<Response<PullRequestNote>> {
+        let url = self.client.url(
+            &format!(
+                "/repos/{}/{}/issues/{}/comments",
+                crate::client::support::encode_path(owner),
+                crate::client::support::encode_path(repository),
+                pull_request_id,
+            ),
+            None,
+        );
+
+        self.client
+            .post(
+                &url,
+                crate::client::Message {
+                    body: Some(reqwest::Body::from(
+                        serde_json::json!({ "body": body }).to_string(),
+                    )),
+                    content_type: Some("application/json".to_string()),
+                },
+            )
+            .await
     }
 
-    // MergeRequestNote
+    /// Updates a comment on a pull request.
+    ///
+    /// This function performs a `PATCH` to `/repos/:owner/:repo/issues/comments/:comment_id`
+    /// From https://docs.github.com/en/rest/issues/comments#update-an-issue-comment
     pub async fn update_pull_request_note(
         &self,
         owner: &str,
         repository: &str,
         note_id: u64,
         body: String,
-    ) -> ClientResult<Response<Vec<()>>> {
-        todo!()
+    ) -> ClientResult<Response<PullRequestNote>> {
+        let url = self.client.url(
+            &format!(
+                "/repos/{}/{}/issues/comments/{}",
+                crate::client::support::encode_path(owner),
+                crate::client::support::encode_path(repository),
+                note_id,
+            ),
+            None,
+        );
+
+        self.client
+            .patch(
+                &url,
+                crate::client::Message {
+                    body: Some(reqwest::Body::from(
+                        serde_json::json!({ "body": body }).to_string(),
+                    )),
+                    content_type: Some("application/json".to_string()),
+                },
+            )
+            .await
+    }
+
+    /// Requests reviews for a pull request from users and teams.
+    /// Requesting a review from the pull request's author is rejected by GitHub.
+    ///
+    /// This function performs a `POST` to `/repos/:owner/:repo/pulls/:pull_number/requested_reviewers`
+    /// From https://docs.github.com/en/rest/pulls/review-requests#request-reviewers-for-a-pull-request
+    pub async fn request_reviewers(
+        &self,
+        owner: &str,
+        repository: &str,
+        pull_request_id: u64,
+        reviewers: &ReviewersRequest,
+    ) -> ClientResult<Response<PullRequest>> {
+        let url = self.client.url(
+            &format!(
+                "/repos/{}/{}/pulls/{}/requested_reviewers",
+                crate::client::support::encode_path(owner),
+                crate::client::support::encode_path(repository),
+                pull_request_id,
+            ),
+            None,
+        );
+
+        self.client.post(&url, Message::json(reviewers)?).await
     }
 }
@@ -2,6 +2,7 @@ use chrono;
 use serde::{Deserialize, Serialize};
 use url::Url;
 
+use crate::client::{Client, ClientResult, Message, Response};
 use crate::models::{Author, TeamId, orgs};
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -67,3 +68,116 @@ pub enum TeamPrivacy {
     Closed,
     Secret,
 }
+
+pub struct Teams {
+    pub client: Client,
+}
+
+impl Teams {
+    pub fn new(client: Client) -> Self {
+        Teams { client }
+    }
+
+    /// Lists the members of a team, including those of its child teams.
+    ///
+    /// This function performs a `GET` to `/orgs/:org/teams/:team_slug/members`
+    /// From https://docs.github.com/en/rest/teams/members#list-team-members
+    pub async fn list_team_members(
+        &self,
+        org: &str,
+        team_slug: &str,
+    ) -> ClientResult<Response<Vec<Author>>> {
+        let url = self.client.url(
+            &format!(
+                "/orgs/{}/teams/{}/members?per_page=100",
+                crate::client::support::encode_path(org),
+                crate::client::support::encode_path(team_slug),
+            ),
+            None,
+        );
+
+        self.client.get_all_pages(&url, Message::default()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use crate::client::{ClientBuilder, Credentials};
+
+    fn user(login: &str) -> serde_json::Value {
+        let url = format!("https://api.github.com/users/{login}");
+        json!({
+            "login": login,
+            "id": 1,
+            "node_id": "MDQ6VXNlcjE=",
+            "avatar_url": "https://github.com/images/error/octocat_happy.gif",
+            "gravatar_id": "",
+            "url": url,
+            "html_url": format!("https://github.com/{login}"),
+            "followers_url": format!("{url}/followers"),
+            "following_url": format!("{url}/following"),
+            "gists_url": format!("{url}/gists"),
+            "starred_url": format!("{url}/starred"),
+            "subscriptions_url": format!("{url}/subscriptions"),
+            "organizations_url": format!("{url}/orgs"),
+            "repos_url": format!("{url}/repos"),
+            "events_url": format!("{url}/events"),
+            "received_events_url": format!("{url}/received_events"),
+            "type": "User",
+            "site_admin": false,
+            "name": null,
+            "patch_url": null
+        })
+    }
+
+    #[tokio::test]
+    async fn should_list_team_members_of_every_page() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/orgs/doctavious/teams/docs/members"))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([user("hubot")])))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/orgs/doctavious/teams/docs/members"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header(
+                        "Link",
+                        format!(
+                            r#"<{}/orgs/doctavious/teams/docs/members?per_page=100&page=2>; rel="next""#,
+                            server.uri()
+                        ),
+                    )
+                    .set_body_json(json!([user("octocat")])),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = ClientBuilder::new()
+            .unwrap()
+            .with_host_override(&server.uri())
+            .with_credentials(Credentials::PrivateToken("token".to_string()))
+            .build()
+            .unwrap();
+
+        let members = client
+            .teams()
+            .list_team_members("doctavious", "docs")
+            .await
+            .unwrap()
+            .body;
+
+        assert_eq!(
+            vec!["octocat", "hubot"],
+            members.iter().map(|m| m.login.as_str()).collect::<Vec<_>>()
+        );
+    }
+}
@@ -3,3 +3,4 @@
 
 pub mod client;
 pub mod merge_requests;
+pub mod users;
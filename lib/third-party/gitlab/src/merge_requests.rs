@@ -1,23 +1,28 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::client::{Client, ClientResult, OffsetBasedPagination, Response};
+use crate::client::{Client, ClientResult, Message, OffsetBasedPagination, Response};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MergeRequestNote {
-    id: u64,
-    body: String,
+    pub id: u64,
+    pub body: String,
     author: MergeRequestNoteAuthor,
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
     system: bool,
-    notable_id: u64,
+    noteable_id: u64,
     // TODO: could make into an enum
-    notable_type: String,
+    noteable_type: String,
     project_id: u64,
     noteable_iid: u64,
-    resolvable: bool,
+    pub resolvable: bool,
+    /// Whether the note's thread is resolved, `None` when it isn't resolvable
+    #[serde(default)]
+    pub resolved: Option<bool>,
+    #[serde(default)]
     confidential: bool,
+    #[serde(default)]
     internal: bool,
 }
 
@@ -25,11 +30,111 @@ pub struct MergeRequestNote {
 pub struct MergeRequestNoteAuthor {
     id: u64,
     username: String,
-    email: String,
+    // only visible to administrators
+    email: Option<String>,
     name: String,
     // TODO: could make this an enum
     state: String,
-    created_at: DateTime<Utc>,
+    created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeRequest {
+    pub id: u64,
+    pub iid: u64,
+    pub project_id: u64,
+    pub title: String,
+    // TODO: could make into an enum
+    pub state: String,
+    #[serde(default)]
+    pub draft: bool,
+    pub author: MergeRequestUser,
+    #[serde(default)]
+    pub reviewers: Vec<MergeRequestUser>,
+    pub web_url: String,
+}
+
+/// Thread of notes on a merge request. Notes which don't start a thread are discussions of a
+/// single, individual, note.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeRequestDiscussion {
+    pub id: String,
+    pub individual_note: bool,
+    pub notes: Vec<MergeRequestNote>,
+}
+
+impl MergeRequestDiscussion {
+    /// Whether the thread can be resolved, which individual notes can't be
+    pub fn resolvable(&self) -> bool {
+        self.notes.iter().any(|n| n.resolvable)
+    }
+
+    /// Whether all resolvable notes of the thread are resolved
+    pub fn resolved(&self) -> bool {
+        self.resolvable()
+            && self
+                .notes
+                .iter()
+                .filter(|n| n.resolvable)
+                .all(|n| n.resolved.unwrap_or(false))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeRequestUser {
+    pub id: u64,
+    pub username: String,
+    pub name: String,
+    // TODO: could make this an enum
+    pub state: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeRequestGroup {
+    pub id: u64,
+    pub name: String,
+    pub full_path: String,
+}
+
+/// Rule requiring a number of approvals from its eligible approvers before a merge request can be
+/// merged
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeRequestApprovalRule {
+    pub id: u64,
+    pub name: String,
+    // TODO: could make into an enum. regular, code_owner, any_approver or report_approver
+    pub rule_type: String,
+    pub approvals_required: u64,
+    /// Users of the rule along with the members of its groups
+    #[serde(default)]
+    pub eligible_approvers: Vec<MergeRequestUser>,
+    #[serde(default)]
+    pub users: Vec<MergeRequestUser>,
+    #[serde(default)]
+    pub groups: Vec<MergeRequestGroup>,
+}
+
+/// Approval rule to create, or replace an existing rule with
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MergeRequestApprovalRuleRequest {
+    pub name: String,
+    pub approvals_required: u64,
+    pub user_ids: Vec<u64>,
+    pub usernames: Vec<String>,
+    pub group_ids: Vec<u64>,
+}
+
+/// Changes of a file in a merge request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergeRequestDiff {
+    pub old_path: String,
+    pub new_path: String,
+    pub a_mode: String,
+    pub b_mode: String,
+    pub diff: String,
+    pub new_file: bool,
+    pub renamed_file: bool,
+    pub deleted_file: bool,
 }
 
 pub struct ListAllMergeRequestNotesRequest {
@@ -58,6 +163,7 @@ impl MergeRequests {
     /// * merge_request_iid
     /// * sort - asc or desc
     /// * order_by - Return merge request notes ordered by created_at or updated_at fields. Default is created_at
+    /// * pagination - page to get. Every page is fetched when `None`
     pub async fn list_all_merge_request_notes(
         &self,
         project_id: &str,
@@ -67,22 +173,45 @@ impl MergeRequests {
         pagination: Option<OffsetBasedPagination>,
     ) -> ClientResult<Response<Vec<MergeRequestNote>>> {
         let mut query_args: Vec<(String, String)> = Default::default();
-        if let Some(sort) = sort {}
+        if let Some(sort) = sort {
+            query_args.push(("sort".to_string(), sort.to_string()));
+        }
 
-        if let Some(order_by) = order_by {}
+        if let Some(order_by) = order_by {
+            query_args.push(("order_by".to_string(), order_by.to_string()));
+        }
 
-        if let Some(pagination) = pagination {}
+        // without a page every page is fetched, using the maximum page size
+        let all_pages = pagination.is_none();
+        if all_pages {
+            query_args.push(("per_page".to_string(), "100".to_string()));
+        }
 
-        let query_ = serde_urlencoded::to_string(&query_args).unwrap();
+        let mut query_ = serde_urlencoded::to_string(&query_args)?;
+        if let Some(pagination) = pagination {
+            let pagination_query = serde_urlencoded::to_string(pagination)?;
+            if !pagination_query.is_empty() {
+                if !query_.is_empty() {
+                    query_.push('&');
+                }
+                query_.push_str(&pagination_query);
+            }
+        }
 
-        let url = self.client.url(
-            &format!(
-                "/projects/{}/merge_requests/{}/notes",
-                crate::client::support::encode_path(project_id),
-                merge_request_iid,
-            ),
-            None,
+        let mut path = format!(
+            "/projects/{}/merge_requests/{}/notes",
+            crate::client::support::encode_path(project_id),
+            merge_request_iid,
         );
+        if !query_.is_empty() {
+            path = format!("{path}?{query_}");
+        }
+
+        let url = self.client.url(&path, None);
+        if all_pages {
+            return self.client.get_all_pages(&url, Message::default()).await;
+        }
+
         self.client
             .get(
                 &url,
@@ -112,7 +241,7 @@ impl MergeRequests {
         project_id: &str,
         merge_request_iid: u64,
         body: String,
-    ) -> ClientResult<Response<Vec<MergeRequestNote>>> {
+    ) -> ClientResult<Response<MergeRequestNote>> {
         let url = self.client.url(
             &format!(
                 "/projects/{}/merge_requests/{}/notes",
@@ -126,8 +255,10 @@ impl MergeRequests {
             .post(
                 &url,
                 crate::client::Message {
-                    body: Some(reqwest::Body::from(body)),
-                    content_type: None,
+                    body: Some(reqwest::Body::from(
+                        serde_json::json!({ "body": body }).to_string(),
+                    )),
+                    content_type: Some("application/json".to_string()),
                 },
             )
             .await
@@ -150,13 +281,13 @@ impl MergeRequests {
         merge_request_iid: u64,
         note_id: u64,
         body: String,
-    ) -> ClientResult<Response<Vec<MergeRequestNote>>> {
+    ) -> ClientResult<Response<MergeRequestNote>> {
         let url = self.client.url(
             &format!(
                 "/projects/{}/merge_requests/{}/notes/{}",
                 crate::client::support::encode_path(project_id),
-                note_id,
                 merge_request_iid,
+                note_id,
             ),
             None,
         );
@@ -165,10 +296,540 @@ impl MergeRequests {
             .put(
                 &url,
                 crate::client::Message {
-                    body: Some(reqwest::Body::from(body)),
-                    content_type: None,
+                    body: Some(reqwest::Body::from(
+                        serde_json::json!({ "body": body }).to_string(),
+                    )),
+                    content_type: Some("application/json".to_string()),
+                },
+            )
+            .await
+    }
+
+    /// Gets a single merge request.
+    ///
+    /// This function performs a `GET` to `/projects/:id/merge_requests/:merge_request_iid`
+    /// From https://docs.gitlab.com/api/merge_requests/#get-single-mr
+    ///
+    /// **Parameters**
+    ///
+    /// * project_id - The ID or URL-encoded path of the project
+    /// * merge_request_iid
+    pub async fn get_merge_request(
+        &self,
+        project_id: &str,
+        merge_request_iid: u64,
+    ) -> ClientResult<Response<MergeRequest>> {
+        let url = self.client.url(
+            &format!(
+                "/projects/{}/merge_requests/{}",
+                crate::client::support::encode_path(project_id),
+                merge_request_iid,
+            ),
+            None,
+        );
+
+        self.client.get(&url, Message::default()).await
+    }
+
+    /// Sets the reviewers of a single merge request, replacing any existing reviewers.
+    ///
+    /// This function performs a `PUT` to `/projects/:id/merge_requests/:merge_request_iid`
+    /// From https://docs.gitlab.com/api/merge_requests/#update-mr
+    ///
+    /// **Parameters**
+    ///
+    /// * project_id - The ID or URL-encoded path of the project
+    /// * merge_request_iid
+    /// * reviewer_ids - The IDs of the users to set as reviewers. Empty to unassign all reviewers
+    pub async fn update_merge_request_reviewers(
+        &self,
+        project_id: &str,
+        merge_request_iid: u64,
+        reviewer_ids: Vec<u64>,
+    ) -> ClientResult<Response<MergeRequest>> {
+        let url = self.client.url(
+            &format!(
+                "/projects/{}/merge_requests/{}",
+                crate::client::support::encode_path(project_id),
+                merge_request_iid,
+            ),
+            None,
+        );
+
+        self.client
+            .put(
+                &url,
+                Message::json(&serde_json::json!({ "reviewer_ids": reviewer_ids }))?,
+            )
+            .await
+    }
+
+    /// Gets a list of all discussions, both threads and individual notes, of a single merge request.
+    ///
+    /// This function performs a `GET` to `/projects/:id/merge_requests/:merge_request_iid/discussions`
+    /// From https://docs.gitlab.com/api/discussions/#list-project-merge-request-discussion-items
+    ///
+    /// **Parameters**
+    ///
+    /// * project_id - The ID or URL-encoded path of the project
+    /// * merge_request_iid
+    pub async fn list_merge_request_discussions(
+        &self,
+        project_id: &str,
+        merge_request_iid: u64,
+    ) -> ClientResult<Response<Vec<MergeRequestDiscussion>>> {
+        let url = self.client.url(
+            &format!(
+                "/projects/{}/merge_requests/{}/discussions?per_page=100",
+                crate::client::support::encode_path(project_id),
+                merge_request_iid,
+            ),
+            None,
+        );
+
+        self.client.get_all_pages(&url, Message::default()).await
+    }
+
+    /// Creates a new thread on a single merge request.
+    ///
+    /// This function performs a `POST` to `/projects/:id/merge_requests/:merge_request_iid/discussions`
+    /// From https://docs.gitlab.com/api/discussions/#create-new-merge-request-thread
+    ///
+    /// **Parameters**
+    ///
+    /// * project_id - The ID or URL-encoded path of the project
+    /// * merge_request_iid
+    /// * body - The content of the thread
+    pub async fn create_merge_request_discussion(
+        &self,
+        project_id: &str,
+        merge_request_iid: u64,
+        body: String,
+    ) -> ClientResult<Response<MergeRequestDiscussion>> {
+        let url = self.client.url(
+            &format!(
+                "/projects/{}/merge_requests/{}/discussions",
+                crate::client::support::encode_path(project_id),
+                merge_request_iid,
+            ),
+            None,
+        );
+
+        self.client
+            .post(&url, Message::json(&serde_json::json!({ "body": body }))?)
+            .await
+    }
+
+    /// Adds a note to a thread of a single merge request.
+    ///
+    /// This function performs a `POST` to `/projects/:id/merge_requests/:merge_request_iid/discussions/:discussion_id/notes`
+    /// From https://docs.gitlab.com/api/discussions/#add-note-to-existing-merge-request-thread
+    ///
+    /// **Parameters**
+    ///
+    /// * project_id - The ID or URL-encoded path of the project
+    /// * merge_request_iid
+    /// * discussion_id
+    /// * body
+    pub async fn add_merge_request_discussion_note(
+        &self,
+        project_id: &str,
+        merge_request_iid: u64,
+        discussion_id: &str,
+        body: String,
+    ) -> ClientResult<Response<MergeRequestNote>> {
+        let url = self.client.url(
+            &format!(
+                "/projects/{}/merge_requests/{}/discussions/{}/notes",
+                crate::client::support::encode_path(project_id),
+                merge_request_iid,
+                crate::client::support::encode_path(discussion_id),
+            ),
+            None,
+        );
+
+        self.client
+            .post(&url, Message::json(&serde_json::json!({ "body": body }))?)
+            .await
+    }
+
+    /// Resolves or unresolves a thread of a single merge request.
+    ///
+    /// This function performs a `PUT` to `/projects/:id/merge_requests/:merge_request_iid/discussions/:discussion_id`
+    /// From https://docs.gitlab.com/api/discussions/#resolve-a-merge-request-thread
+    ///
+    /// **Parameters**
+    ///
+    /// * project_id - The ID or URL-encoded path of the project
+    /// * merge_request_iid
+    /// * discussion_id
+    /// * resolved - Resolve or unresolve the thread
+    pub async fn resolve_merge_request_discussion(
+        &self,
+        project_id: &str,
+        merge_request_iid: u64,
+        discussion_id: &str,
+        resolved: bool,
+    ) -> ClientResult<Response<MergeRequestDiscussion>> {
+        let url = self.client.url(
+            &format!(
+                "/projects/{}/merge_requests/{}/discussions/{}?resolved={}",
+                crate::client::support::encode_path(project_id),
+                merge_request_iid,
+                crate::client::support::encode_path(discussion_id),
+                resolved,
+            ),
+            None,
+        );
+
+        self.client.put(&url, Message::default()).await
+    }
+
+    /// Gets the approval rules of a single merge request.
+    ///
+    /// This function performs a `GET` to `/projects/:id/merge_requests/:merge_request_iid/approval_rules`
+    /// From https://docs.gitlab.com/api/merge_request_approvals/#get-merge-request-level-rules
+    ///
+    /// **Parameters**
+    ///
+    /// * project_id - The ID or URL-encoded path of the project
+    /// * merge_request_iid
+    pub async fn list_merge_request_approval_rules(
+        &self,
+        project_id: &str,
+        merge_request_iid: u64,
+    ) -> ClientResult<Response<Vec<MergeRequestApprovalRule>>> {
+        let url = self.client.url(
+            &format!(
+                "/projects/{}/merge_requests/{}/approval_rules?per_page=100",
+                crate::client::support::encode_path(project_id),
+                merge_request_iid,
+            ),
+            None,
+        );
+
+        self.client.get_all_pages(&url, Message::default()).await
+    }
+
+    /// Creates an approval rule for a single merge request.
+    ///
+    /// This function performs a `POST` to `/projects/:id/merge_requests/:merge_request_iid/approval_rules`
+    /// From https://docs.gitlab.com/api/merge_request_approvals/#create-merge-request-level-rule
+    ///
+    /// **Parameters**
+    ///
+    /// * project_id - The ID or URL-encoded path of the project
+    /// * merge_request_iid
+    /// * rule - Name, number of approvals required and eligible approvers of the rule
+    pub async fn create_merge_request_approval_rule(
+        &self,
+        project_id: &str,
+        merge_request_iid: u64,
+        rule: &MergeRequestApprovalRuleRequest,
+    ) -> ClientResult<Response<MergeRequestApprovalRule>> {
+        let url = self.client.url(
+            &format!(
+                "/projects/{}/merge_requests/{}/approval_rules",
+                crate::client::support::encode_path(project_id),
+                merge_request_iid,
+            ),
+            None,
+        );
+
+        self.client.post(&url, Message::json(rule)?).await
+    }
+
+    /// Updates an approval rule of a single merge request, replacing its eligible approvers.
+    ///
+    /// This function performs a `PUT` to `/projects/:id/merge_requests/:merge_request_iid/approval_rules/:approval_rule_id`
+    /// From https://docs.gitlab.com/api/merge_request_approvals/#update-merge-request-level-rule
+    ///
+    /// **Parameters**
+    ///
+    /// * project_id - The ID or URL-encoded path of the project
+    /// * merge_request_iid
+    /// * approval_rule_id
+    /// * rule - Name, number of approvals required and eligible approvers of the rule
+    pub async fn update_merge_request_approval_rule(
+        &self,
+        project_id: &str,
+        merge_request_iid: u64,
+        approval_rule_id: u64,
+        rule: &MergeRequestApprovalRuleRequest,
+    ) -> ClientResult<Response<MergeRequestApprovalRule>> {
+        let url = self.client.url(
+            &format!(
+                "/projects/{}/merge_requests/{}/approval_rules/{}",
+                crate::client::support::encode_path(project_id),
+                merge_request_iid,
+                approval_rule_id,
+            ),
+            None,
+        );
+
+        self.client.put(&url, Message::json(rule)?).await
+    }
+
+    /// Deletes an approval rule of a single merge request.
+    ///
+    /// This function performs a `DELETE` to `/projects/:id/merge_requests/:merge_request_iid/approval_rules/:approval_rule_id`
+    /// From https://docs.gitlab.com/api/merge_request_approvals/#delete-merge-request-level-rule
+    ///
+    /// **Parameters**
+    ///
+    /// * project_id - The ID or URL-encoded path of the project
+    /// * merge_request_iid
+    /// * approval_rule_id
+    pub async fn delete_merge_request_approval_rule(
+        &self,
+        project_id: &str,
+        merge_request_iid: u64,
+        approval_rule_id: u64,
+    ) -> ClientResult<Response<()>> {
+        let url = self.client.url(
+            &format!(
+                "/projects/{}/merge_requests/{}/approval_rules/{}",
+                crate::client::support::encode_path(project_id),
+                merge_request_iid,
+                approval_rule_id,
+            ),
+            None,
+        );
+
+        self.client.delete(&url, Message::default()).await
+    }
+
+    /// Gets the changes of each file of a single merge request.
+    ///
+    /// This function performs a `GET` to `/projects/:id/merge_requests/:merge_request_iid/diffs`
+    /// From https://docs.gitlab.com/api/merge_requests/#list-merge-request-diffs
+    ///
+    /// **Parameters**
+    ///
+    /// * project_id - The ID or URL-encoded path of the project
+    /// * merge_request_iid
+    pub async fn list_merge_request_diffs(
+        &self,
+        project_id: &str,
+        merge_request_iid: u64,
+    ) -> ClientResult<Response<Vec<MergeRequestDiff>>> {
+        let url = self.client.url(
+            &format!(
+                "/projects/{}/merge_requests/{}/diffs?per_page=100",
+                crate::client::support::encode_path(project_id),
+                merge_request_iid,
+            ),
+            None,
+        );
+
+        self.client.get_all_pages(&url, Message::default()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use wiremock::matchers::{body_json, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    use crate::client::{Client, ClientBuilder, Credentials};
+    use crate::merge_requests::MergeRequestApprovalRuleRequest;
+
+    fn note(id: u64, resolvable: bool, resolved: Option<bool>) -> serde_json::Value {
+        json!({
+            "id": id,
+            "body": "Files changed",
+            "author": {
+                "id": 1,
+                "username": "doctavious",
+                "name": "Doctavious",
+                "state": "active"
+            },
+            "created_at": "2025-05-20T12:00:00Z",
+            "updated_at": "2025-05-20T12:00:00Z",
+            "system": false,
+            "noteable_id": 3,
+            "noteable_type": "MergeRequest",
+            "project_id": 8,
+            "noteable_iid": 7,
+            "resolvable": resolvable,
+            "resolved": resolved
+        })
+    }
+
+    fn client(server: &MockServer) -> Client {
+        ClientBuilder::new()
+            .unwrap()
+            .with_host_override(&server.uri())
+            .with_credentials(Credentials::PrivateToken("token".to_string()))
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn should_list_notes_of_every_page() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/projects/8/merge_requests/7/notes"))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([note(2, false, None)])))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/projects/8/merge_requests/7/notes"))
+            .and(query_param("per_page", "100"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header(
+                        "Link",
+                        format!(
+                            r#"<{}/projects/8/merge_requests/7/notes?page=2&per_page=100>; rel="next""#,
+                            server.uri()
+                        ),
+                    )
+                    .set_body_json(json!([note(1, false, None)])),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let notes = client(&server)
+            .merge_requests()
+            .list_all_merge_request_notes("8", 7, None, None, None)
+            .await
+            .unwrap()
+            .body;
+
+        assert_eq!(vec![1, 2], notes.iter().map(|n| n.id).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn should_list_discussions_of_every_page() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/projects/8/merge_requests/7/discussions"))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([{
+                "id": "b",
+                "individual_note": false,
+                "notes": [note(2, true, Some(true)), note(3, true, Some(false))]
+            }])))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/projects/8/merge_requests/7/discussions"))
+            .and(query_param("PRIVATE-TOKEN", "token"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header(
+                        "Link",
+                        format!(
+                            r#"<{}/projects/8/merge_requests/7/discussions?page=2&per_page=100>; rel="next""#,
+                            server.uri()
+                        ),
+                    )
+                    .set_body_json(json!([{
+                        "id": "a",
+                        "individual_note": true,
+                        "notes": [note(1, false, None)]
+                    }])),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let discussions = client(&server)
+            .merge_requests()
+            .list_merge_request_discussions("8", 7)
+            .await
+            .unwrap()
+            .body;
+
+        assert_eq!(2, discussions.len());
+        assert!(!discussions[0].resolvable());
+        assert!(discussions[1].resolvable());
+        assert!(!discussions[1].resolved());
+    }
+
+    #[tokio::test]
+    async fn should_resolve_discussion() {
+        let server = MockServer::start().await;
+        Mock::given(method("PUT"))
+            .and(path("/projects/8/merge_requests/7/discussions/a"))
+            .and(query_param("resolved", "true"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": "a",
+                "individual_note": false,
+                "notes": [note(1, true, Some(true))]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let discussion = client(&server)
+            .merge_requests()
+            .resolve_merge_request_discussion("8", 7, "a", true)
+            .await
+            .unwrap()
+            .body;
+
+        assert!(discussion.resolved());
+    }
+
+    #[tokio::test]
+    async fn should_create_approval_rule() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/projects/8/merge_requests/7/approval_rules"))
+            .and(body_json(json!({
+                "name": "CODEOWNERS",
+                "approvals_required": 1,
+                "user_ids": [],
+                "usernames": ["octocat"],
+                "group_ids": []
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+                "id": 5,
+                "name": "CODEOWNERS",
+                "rule_type": "regular",
+                "approvals_required": 1,
+                "eligible_approvers": [{
+                    "id": 2,
+                    "username": "octocat",
+                    "name": "Octocat",
+                    "state": "active"
+                }],
+                "users": [{
+                    "id": 2,
+                    "username": "octocat",
+                    "name": "Octocat",
+                    "state": "active"
+                }],
+                "groups": []
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let rule = client(&server)
+            .merge_requests()
+            .create_merge_request_approval_rule(
+                "8",
+                7,
+                &MergeRequestApprovalRuleRequest {
+                    name: "CODEOWNERS".to_string(),
+                    approvals_required: 1,
+                    usernames: vec!["octocat".to_string()],
+                    ..Default::default()
                 },
             )
             .await
+            .unwrap()
+            .body;
+
+        assert_eq!(5, rule.id);
+        assert_eq!("octocat", rule.users[0].username);
     }
 }
@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+use crate::client::{Client, ClientResult, Message, Response};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct User {
+    pub id: u64,
+    pub username: String,
+    pub name: String,
+    // TODO: could make this an enum
+    pub state: String,
+    pub web_url: String,
+}
+
+pub struct Users {
+    pub client: Client,
+}
+
+impl Users {
+    pub fn new(client: Client) -> Self {
+        Users { client }
+    }
+
+    /// Gets the users with a username, at most one as usernames are unique and case-insensitive.
+    ///
+    /// This function performs a `GET` to `/users?username=:username`
+    /// From https://docs.gitlab.com/api/users/#list-users
+    ///
+    /// **Parameters**
+    ///
+    /// * username - Username of the user, without a leading `@`
+    pub async fn list_users_by_username(
+        &self,
+        username: &str,
+    ) -> ClientResult<Response<Vec<User>>> {
+        let query = serde_urlencoded::to_string([("username", username)]).unwrap();
+        let url = self.client.url(&format!("/users?{query}"), None);
+
+        self.client.get(&url, Message::default()).await
+    }
+}